@@ -0,0 +1,92 @@
+//! 批量签名演示 (Batch Signing)
+//!
+//! cggmp24 DKG (3-of-5) -> 签名者 {1, 2, 4} 一次确定 -> cggmp24 与 Synedrion 各自批量签名 8 个消息哈希。
+//! 每个结果单独报告，断言所有签名都能恢复出钱包地址。
+//! 运行方式: `cargo run --example batch_signing`
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation::{self, FastSecp256k1, SimpleVerifier};
+use std::collections::{BTreeMap, BTreeSet};
+
+type SynedrionParams = FastSecp256k1;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let threshold = 3;
+    let signer_ids = [1u16, 2, 4];
+    let message_hashes: Vec<[u8; 32]> = (1..=8u8).map(|k| [k; 32]).collect();
+
+    println!("[1] 运行 cggmp24 DKG ({}-of-5) ...", threshold);
+    let cggmp_shares =
+        simulation::run_dkg(5, threshold, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let y_hex = hex::encode(&pubkey_bytes);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    // DKG 委员会是连续的 party id 0..n
+    let committee = bridge::EvaluationPoints::contiguous(5);
+    println!("      钱包地址: {:?}", address);
+
+    println!(
+        "[2] cggmp24 批量签名 {} 条消息 (签名者 {:?}) ...",
+        message_hashes.len(),
+        signer_ids
+    );
+    let signers: Vec<_> = cggmp_shares
+        .iter()
+        .filter(|s| signer_ids.contains(&s.core.i))
+        .cloned()
+        .collect();
+    let results = simulation::run_cggmp_batch_signing(&signers, &message_hashes).await?;
+    check_results("cggmp24", &results, &message_hashes, address)?;
+
+    println!("[3] Synedrion 批量签名 {} 条消息 ...", message_hashes.len());
+    let party_ids: BTreeSet<u16> = cggmp_shares.iter().map(|s| s.core.i).collect();
+    let aux_map = simulation::run_synedrion_aux_gen::<SynedrionParams>(party_ids).await?;
+    let mut synedrion_shares = BTreeMap::new();
+    let synedrion_data =
+        simulation::cggmp_shares_to_synedrion(&cggmp_shares, &committee, &aux_map)?;
+    for (share, aux) in synedrion_data {
+        let verifier = SimpleVerifier(*share.owner());
+        let converted =
+            bridge::convert_synedrion_types::<SynedrionParams, u16, SimpleVerifier>(&share, &aux)?;
+        synedrion_shares.insert(verifier, converted);
+    }
+    let threshold_shares = simulation::synedrion_threshold_shares::<SynedrionParams>(
+        &synedrion_shares,
+        &committee,
+        threshold,
+        &y_hex,
+    )?;
+    let signer_keys: Vec<SimpleVerifier> = signer_ids.iter().map(|&id| SimpleVerifier(id)).collect();
+    let signing_subset = simulation::threshold_signing_subset(&threshold_shares, &signer_keys)?;
+    let results =
+        simulation::run_synedrion_batch_signing::<SynedrionParams>(&signing_subset, &message_hashes)
+            .await?;
+    check_results("Synedrion", &results, &message_hashes, address)?;
+
+    println!("=== 批量签名完成 ===");
+    Ok(())
+}
+
+fn check_results(
+    backend: &str,
+    results: &[anyhow::Result<([u8; 32], [u8; 32], u8)>],
+    message_hashes: &[[u8; 32]],
+    address: ethers::types::Address,
+) -> anyhow::Result<()> {
+    assert_eq!(results.len(), message_hashes.len());
+    for (index, (result, message_hash)) in results.iter().zip(message_hashes).enumerate() {
+        match result {
+            Ok((r, s, v)) => {
+                let recovered_v = calc_recovery_id(r, s, message_hash, address)
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                assert_eq!(recovered_v, *v);
+                println!("      [{}] 消息 {}: OK", backend, index);
+            }
+            Err(e) => panic!("[{}] 消息 {} 签名失败: {:#}", backend, index, e),
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! 委员会轮换演示 (Committee Rotation)
+//!
+//! cggmp24 DKG (3-of-5, 参与方 {0..4}) -> 旧成员 {0, 2, 3} 把密钥转移给新委员会 {0, 2, 3, 7, 9}
+//! (参与方 1、4 离开，7、9 加入) -> 为新委员会构造 cggmp24 与 Synedrion 分片 -> 两个后端分别签名。
+//! 每一步都断言共享公钥与以太坊地址保持不变。
+//! 运行方式: `cargo run --example committee_rotation`
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge::resharing::VssScheme;
+use my_mpc_bridge::bridge::{self, EvaluationPoints, IndexBase, PortableKeyShare};
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation::{self, FastSecp256k1, SimpleVerifier};
+use std::collections::BTreeMap;
+
+type SynedrionParams = FastSecp256k1;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let old_threshold = 3;
+    let new_threshold = 3;
+    let dealers = [0u16, 2, 3];
+    let new_committee = [0u16, 2, 3, 7, 9];
+    let message_hash = [0x42u8; 32];
+
+    println!("[1] 运行 cggmp24 DKG ({}-of-5) ...", old_threshold);
+    let cggmp_shares =
+        simulation::run_dkg(5, old_threshold, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let y_hex = hex::encode(&pubkey_bytes);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    println!("      钱包地址: {:?}", address);
+
+    println!(
+        "[2] 委员会轮换: Dealers {:?} -> 新委员会 {:?} ({}-of-{}) ...",
+        dealers,
+        new_committee,
+        new_threshold,
+        new_committee.len()
+    );
+    let old_committee = EvaluationPoints::contiguous(5);
+    let old_shares = cggmp_shares
+        .iter()
+        .filter(|s| dealers.contains(&s.core.i))
+        .map(|share| bridge::from_cggmp_to_portable(share, Some(&old_committee)))
+        .collect::<anyhow::Result<Vec<PortableKeyShare>>>()?;
+    // 新委员会的 party id 不连续，求值点 x = id + 1 显式随分片保存
+    let committee_points = EvaluationPoints::from_ids(new_committee, IndexBase::Zero)
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let new_shares = bridge::rotate_committee(
+        old_shares,
+        &committee_points,
+        new_threshold,
+        VssScheme::Feldman,
+    )?;
+    assert_eq!(
+        new_shares.iter().map(|s| s.i).collect::<Vec<_>>(),
+        new_committee
+    );
+    assert!(new_shares
+        .iter()
+        .all(|s| s.evaluation_points.as_ref() == Some(&committee_points)));
+
+    println!("[3] 构造新委员会的 cggmp24 分片并签名 ...");
+    let new_cggmp_shares = simulation::build_cggmp_committee_shares(
+        &new_shares,
+        ExecutionId::new(b"demo-committee-rotation"),
+    )
+    .await?;
+    for share in &new_cggmp_shares {
+        let new_pubkey_bytes = share.shared_public_key.to_bytes(true);
+        assert_eq!(new_pubkey_bytes.as_ref(), pubkey_bytes.as_ref());
+        assert_eq!(compute_eth_address_from_pubkey(&new_pubkey_bytes), address);
+    }
+    // 新委员会中位置 1, 3, 4 对应 party 2, 7, 9
+    let signers: Vec<_> = [1usize, 3, 4]
+        .iter()
+        .map(|&k| new_cggmp_shares[k].clone())
+        .collect();
+    let (r, s, v) = simulation::run_cggmp_signing(&signers, message_hash).await?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+        v
+    );
+    println!("      cggmp24 签名可恢复出原地址");
+
+    println!("[4] 构造新委员会的 Synedrion 分片并签名 ...");
+    let synedrion_data =
+        simulation::portable_shares_to_synedrion::<SynedrionParams>(&new_shares).await?;
+    let mut synedrion_shares = BTreeMap::new();
+    for (id, (share, aux)) in &synedrion_data {
+        let converted =
+            bridge::convert_synedrion_types::<SynedrionParams, u16, SimpleVerifier>(share, aux)?;
+        synedrion_shares.insert(SimpleVerifier(*id), converted);
+    }
+    let signer_keys = [SimpleVerifier(2), SimpleVerifier(7), SimpleVerifier(9)];
+    let threshold_shares = simulation::synedrion_threshold_shares::<SynedrionParams>(
+        &synedrion_shares,
+        &committee_points,
+        new_threshold,
+        &y_hex,
+    )?;
+    let signing_subset = simulation::threshold_signing_subset(&threshold_shares, &signer_keys)?;
+    let (r, s, v) = simulation::run_synedrion_signing_simulation::<SynedrionParams>(
+        &signing_subset,
+        message_hash,
+    )
+    .await?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+        v
+    );
+    println!("      Synedrion 签名可恢复出原地址");
+
+    println!("=== 委员会轮换完成，地址保持不变 ===");
+    Ok(())
+}
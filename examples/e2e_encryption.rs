@@ -0,0 +1,275 @@
+//! P2P 消息端到端加密演示 (End-to-End Encryption)
+//!
+//! 1. 不经网络直接检查 `network::secure`: 正常消息能解密；篡改密文、修改头部、挪到其他会话、
+//!    重放、伪造或替换 X25519 公钥声明都会被拒绝。
+//! 2. 本机启动一个中继并让它记录所有 P2P 消息，3 个参与方经中继交换经身份密钥签名的 X25519 公钥后
+//!    运行 cggmp24 keygen (2-of-3)，断言中继看到的 P2P `payload` 全部是密文。
+//! 3. 发送一条带标记的 P2P 消息，断言中继看不到明文；再让中继篡改密文，断言接收方报错。
+//!
+//! 运行方式: `cargo run --example e2e_encryption --features relay-testing`
+
+use anyhow::{anyhow, Context};
+use futures_util::future::try_join_all;
+use k256::ecdsa::VerifyingKey;
+use my_mpc_bridge::network::{self, Envelope, KeyExchange, NetworkNode, Relay, SealedPayload};
+use my_mpc_bridge::identity::NodeSigner;
+use rand_core::{OsRng, RngCore};
+use std::collections::BTreeMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use synedrion::signature::Keypair;
+
+const PARTIES: u16 = 3;
+const THRESHOLD: u16 = 2;
+const SECRET: &[u8] = b"resharing sub-share for Party 1";
+
+fn p2p(session: &str, sender: u16, recipient: u16, payload: Vec<u8>) -> Envelope {
+    Envelope {
+        session: session.to_string(),
+        sender,
+        recipient: Some(recipient),
+        reliable: false,
+        payload,
+    }
+}
+
+/// 翻转密文的第一个字节 (保持 `SealedPayload` 格式合法，只破坏认证)
+fn flip_ciphertext(payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut sealed: SealedPayload = serde_json::from_slice(payload)?;
+    sealed.ciphertext[0] ^= 0x01;
+    Ok(serde_json::to_vec(&sealed)?)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    println!("[1] 生成 {} 个节点身份密钥与公钥登记表 ...", PARTIES);
+    let signers: Vec<NodeSigner> = (0..PARTIES).map(NodeSigner::generate).collect();
+    let registry: BTreeMap<u16, VerifyingKey> = signers
+        .iter()
+        .map(|s| Ok((s.id(), s.verifying_key().public_key()?)))
+        .collect::<anyhow::Result<_>>()?;
+
+    check_channels(&signers, &registry)?;
+    check_relay(&signers, &registry).await?;
+    println!("完成: 中继只看到密文，篡改、重放与伪造的公钥声明都被发现。");
+    Ok(())
+}
+
+/// 直接使用 `KeyExchange` / `SecureChannels` 检查加密通道
+fn check_channels(
+    signers: &[NodeSigner],
+    registry: &BTreeMap<u16, VerifyingKey>,
+) -> anyhow::Result<()> {
+    println!("[2] Party 0 与 Party 1 交换 X25519 公钥声明 ...");
+    let exchange_0 = KeyExchange::new(0);
+    let exchange_1 = KeyExchange::new(1);
+    let announcement_0 = exchange_0.announcement(signers[0].signing_key());
+    let announcement_1 = exchange_1.announcement(signers[1].signing_key());
+    let channels_0 = Arc::new(exchange_0.finish(
+        signers[0].signing_key(),
+        [announcement_1.clone()],
+        registry,
+    )?);
+    let channels_1 =
+        Arc::new(exchange_1.finish(signers[1].signing_key(), [announcement_0], registry)?);
+    assert_eq!(channels_0.peers().collect::<Vec<_>>(), [1]);
+    assert_eq!(channels_1.peers().collect::<Vec<_>>(), [0]);
+
+    let (sealer, _) = channels_0.session("demo/a");
+    let (_, mut opener) = channels_1.session("demo/a");
+    let sealed = sealer.seal(1, false, SECRET)?;
+    assert!(!contains(&sealed, SECRET), "密文中出现了明文");
+    let envelope = p2p("demo/a", 0, 1, sealed);
+    assert_eq!(opener.open(&envelope)?, SECRET);
+    println!(
+        "      密文 {} 字节，Party 1 解密成功",
+        envelope.payload.len()
+    );
+
+    println!("[3] 篡改与重放 ...");
+    let result = opener.open(&envelope);
+    assert!(result.is_err(), "重放的消息被接受");
+    println!("      重放: {:#}", result.unwrap_err());
+
+    let sealed = sealer.seal(1, false, SECRET)?;
+    let tampered = p2p("demo/a", 0, 1, flip_ciphertext(&sealed)?);
+    let result = opener.open(&tampered);
+    assert!(result.is_err(), "被篡改的密文被接受");
+    println!("      篡改密文: {:#}", result.unwrap_err());
+
+    let mut relabeled = p2p("demo/a", 0, 1, sealed.clone());
+    relabeled.reliable = true;
+    assert!(opener.open(&relabeled).is_err(), "修改头部的消息被接受");
+    // 未通过认证的消息不影响后续正常消息
+    assert_eq!(opener.open(&p2p("demo/a", 0, 1, sealed))?, SECRET);
+
+    let (_, mut other_session) = channels_1.session("demo/b");
+    let result = other_session.open(&p2p("demo/b", 0, 1, sealer.seal(1, false, SECRET)?));
+    assert!(result.is_err(), "挪到其他会话的消息被接受");
+    println!("      挪到其他会话: {:#}", result.unwrap_err());
+
+    let (reverse_sealer, _) = channels_1.session("demo/a");
+    let reflected = p2p("demo/a", 0, 1, reverse_sealer.seal(0, false, SECRET)?);
+    assert!(opener.open(&reflected).is_err(), "反射回发送方的消息被接受");
+
+    println!("[4] 伪造 X25519 公钥声明 ...");
+    // 未登记的身份密钥冒充 Party 1
+    let attacker = NodeSigner::generate(1);
+    let forged = KeyExchange::new(1).announcement(attacker.signing_key());
+    let result = KeyExchange::new(0).finish(signers[0].signing_key(), [forged], registry);
+    assert!(result.is_err(), "未登记密钥签名的声明被接受");
+    println!(
+        "      冒充 Party 1: {:#}",
+        result.err().context("expected error")?
+    );
+
+    // 保留 Party 1 的签名，只替换其中的 X25519 公钥 (中间人)
+    let mut substituted = announcement_1;
+    substituted.x25519_public = KeyExchange::new(1)
+        .announcement(signers[1].signing_key())
+        .x25519_public;
+    let result = KeyExchange::new(0).finish(signers[0].signing_key(), [substituted], registry);
+    assert!(result.is_err(), "被替换 X25519 公钥的声明被接受");
+    println!(
+        "      替换 X25519 公钥: {:#}",
+        result.err().context("expected error")?
+    );
+    Ok(())
+}
+
+/// 一个参与方: 连接中继、交换密钥、运行 cggmp24 keygen
+async fn run_party(
+    signer: &NodeSigner,
+    registry: &BTreeMap<u16, VerifyingKey>,
+    relay_addr: SocketAddr,
+    session: &str,
+) -> anyhow::Result<(NetworkNode, String)> {
+    let committee: Vec<u16> = (0..PARTIES).collect();
+    let mut node =
+        NetworkNode::connect_relay(signer.id(), &committee, relay_addr, signer.signing_key())
+            .await?;
+    node.enable_encryption(signer.signing_key(), registry)
+        .await?;
+    let share =
+        network::run_cggmp_keygen_party(&node, &format!("{}/keygen", session), THRESHOLD).await?;
+    Ok((node, hex::encode(share.shared_public_key.to_bytes(true))))
+}
+
+/// 经记录并篡改消息的中继运行
+async fn check_relay(
+    signers: &[NodeSigner],
+    registry: &BTreeMap<u16, VerifyingKey>,
+) -> anyhow::Result<()> {
+    println!("[5] 启动记录所有 P2P 消息的中继 ...");
+    let relay = Relay::bind("127.0.0.1:0".parse()?, registry.clone()).await?;
+    let observed: Arc<Mutex<Vec<Envelope>>> = Arc::default();
+    let tamper = Arc::new(AtomicBool::new(false));
+    {
+        let observed = observed.clone();
+        let tamper = tamper.clone();
+        relay.intercept(move |envelope| {
+            if envelope.is_broadcast() {
+                return;
+            }
+            if tamper.load(Ordering::SeqCst) {
+                if let Ok(payload) = flip_ciphertext(&envelope.payload) {
+                    envelope.payload = payload;
+                }
+            }
+            if let Ok(mut observed) = observed.lock() {
+                observed.push(envelope.clone());
+            }
+        });
+    }
+    let mut session_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut session_bytes);
+    let session = format!("e2e-demo-{}", hex::encode(session_bytes));
+
+    println!(
+        "[6] 3 个参与方经中继交换密钥并运行 cggmp24 keygen ({}-of-{}) ...",
+        THRESHOLD, PARTIES
+    );
+    let results = try_join_all(
+        signers
+            .iter()
+            .map(|signer| run_party(signer, registry, relay.local_addr(), &session)),
+    )
+    .await?;
+    assert!(
+        results.iter().all(|(_, key)| key == &results[0].1),
+        "公钥不一致"
+    );
+    println!("      公钥: {}", results[0].1);
+
+    let keygen_session = format!("{}/keygen", session);
+    let keygen_p2p: Vec<Envelope> = observed
+        .lock()
+        .map_err(|_| anyhow!("lock poisoned"))?
+        .iter()
+        .filter(|e| e.session == keygen_session)
+        .cloned()
+        .collect();
+    assert!(!keygen_p2p.is_empty(), "keygen 应有 P2P 消息");
+    for envelope in &keygen_p2p {
+        let sealed: SealedPayload =
+            serde_json::from_slice(&envelope.payload).context("中继看到了未加密的 P2P 消息")?;
+        assert!(!sealed.ciphertext.is_empty());
+    }
+    println!(
+        "      中继转发的 {} 条 keygen P2P 消息全部是密文",
+        keygen_p2p.len()
+    );
+
+    println!("[7] 中继看不到 P2P 消息的明文 ...");
+    let nodes: Vec<NetworkNode> = results.into_iter().map(|(node, _)| node).collect();
+    let secret_session = format!("{}/secret", session);
+    {
+        let sender = nodes[0].session(&secret_session, &[0, 1])?;
+        let mut receiver = nodes[1].session(&secret_session, &[0, 1])?;
+        sender.send(Some(1), false, SECRET.to_vec())?;
+        let received = receiver.recv().await.context("connection closed")??;
+        assert_eq!(received.payload, SECRET);
+    }
+    let relayed = observed
+        .lock()
+        .map_err(|_| anyhow!("lock poisoned"))?
+        .iter()
+        .find(|e| e.session == secret_session)
+        .cloned()
+        .context("中继没有看到这条消息")?;
+    assert!(!contains(&relayed.payload, SECRET));
+    assert!(!contains(&relayed.payload, hex::encode(SECRET).as_bytes()));
+    println!(
+        "      Party 1 收到明文，中继只看到 {} 字节密文",
+        relayed.payload.len()
+    );
+
+    println!("[8] 中继篡改密文 ...");
+    tamper.store(true, Ordering::SeqCst);
+    let tamper_session = format!("{}/tamper", session);
+    {
+        let sender = nodes[0].session(&tamper_session, &[0, 1])?;
+        let mut receiver = nodes[1].session(&tamper_session, &[0, 1])?;
+        sender.send(Some(1), false, SECRET.to_vec())?;
+        let error = match receiver.recv().await.context("connection closed")? {
+            Ok(_) => return Err(anyhow!("被中继篡改的消息被接受")),
+            Err(e) => e,
+        };
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        println!("      Party 1 报错: {}", error);
+    }
+
+    // 所有 SessionLink 已丢弃，关闭节点前先送出剩余消息
+    for node in nodes {
+        node.shutdown().await;
+    }
+    let stats = relay.stats();
+    assert!(stats[&keygen_session].p2p > 0);
+    Ok(())
+}
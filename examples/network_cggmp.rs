@@ -0,0 +1,169 @@
+//! 多进程 cggmp24 演示 (Networked cggmp24)
+//!
+//! 启动 5 个独立进程，经本机 TCP 全连接依次完成 keygen (3-of-5)、aux_info_gen 与签名。
+//! 主进程只负责分配端口、启动子进程并校验结果: 所有参与方得到同一个公钥，
+//! 签名者得到同一个签名且能恢复出钱包地址。
+//! 运行方式: `cargo run --example network_cggmp`
+//!
+//! 子进程参数 (由主进程传入): `--party <id> --peers <addr0,addr1,...> --session <id>`
+
+use anyhow::{anyhow, Context};
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::network::{self, NetworkNode};
+use my_mpc_bridge::selection::{select_signers, SignerPolicy};
+use my_mpc_bridge::simulation;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{SocketAddr, TcpListener};
+use std::process::Stdio;
+use std::time::Duration;
+
+const PARTIES: u16 = 5;
+const THRESHOLD: u16 = 3;
+const MESSAGE: &[u8] = b"networked cggmp24 demo";
+
+/// 子进程输出到 stdout 的结果
+#[derive(Serialize, Deserialize, Debug)]
+struct PartyReport {
+    party: u16,
+    public_key: String,
+    signers: Vec<u16>,
+    signature: Option<(String, String, u8)>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    match arg_value(&args, "--party") {
+        Some(party) => run_party(&args, party.parse()?).await,
+        None => run_coordinator().await,
+    }
+}
+
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn message_hash() -> [u8; 32] {
+    Sha256::digest(MESSAGE).into()
+}
+
+/// 子进程: 作为一个参与方运行完整流程
+async fn run_party(args: &[String], my_id: u16) -> anyhow::Result<()> {
+    let peers: BTreeMap<u16, SocketAddr> = arg_value(args, "--peers")
+        .context("missing --peers")?
+        .split(',')
+        .enumerate()
+        .map(|(id, addr)| Ok((id as u16, addr.parse()?)))
+        .collect::<anyhow::Result<_>>()?;
+    let session = arg_value(args, "--session").context("missing --session")?;
+
+    let node = NetworkNode::connect_mesh(my_id, &peers).await?;
+    let primes = simulation::load_or_generate_primes()?;
+    let share = network::run_cggmp_dkg_party(&node, session, THRESHOLD, primes).await?;
+
+    // 每个参与方独立算出同一个签名者集合
+    let hash = message_hash();
+    let committee = node.parties().to_vec();
+    let available: BTreeSet<u16> = committee.iter().copied().collect();
+    let signers = select_signers(&committee, &available, THRESHOLD, &SignerPolicy::default(), &hash)?;
+    let signature = if signers.contains(&my_id) {
+        let (r, s, v) = network::run_cggmp_signing_party(
+            &node,
+            &format!("{}/sign", session),
+            &share,
+            &signers,
+            hash,
+        )
+        .await?;
+        Some((hex::encode(r), hex::encode(s), v))
+    } else {
+        None
+    };
+    node.shutdown().await;
+
+    let report = PartyReport {
+        party: my_id,
+        public_key: hex::encode(share.shared_public_key.to_bytes(true)),
+        signers,
+        signature,
+    };
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+/// 主进程: 分配端口，启动 5 个子进程并校验结果
+async fn run_coordinator() -> anyhow::Result<()> {
+    println!("[1] 准备素数 (data/primes.txt) ...");
+    simulation::load_or_generate_primes()?;
+
+    println!("[2] 为 {} 个参与方分配本机端口 ...", PARTIES);
+    let listeners = (0..PARTIES)
+        .map(|_| TcpListener::bind("127.0.0.1:0"))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let addrs = listeners
+        .iter()
+        .map(|l| l.local_addr().map(|a| a.to_string()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    drop(listeners);
+    let mut session_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut session_bytes);
+    let session = format!("network-demo-{}", hex::encode(session_bytes));
+    println!("      {:?}", addrs);
+
+    println!("[3] 启动子进程: keygen -> aux_info_gen -> signing ...");
+    let exe = std::env::current_exe()?;
+    let mut children = Vec::new();
+    for party in 0..PARTIES {
+        let child = tokio::process::Command::new(&exe)
+            .args(["--party", &party.to_string()])
+            .args(["--peers", &addrs.join(",")])
+            .args(["--session", &session])
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn Party {}", party))?;
+        children.push(child);
+    }
+
+    let mut reports = Vec::new();
+    for (party, child) in children.into_iter().enumerate() {
+        let output = tokio::time::timeout(Duration::from_secs(600), child.wait_with_output())
+            .await
+            .map_err(|_| anyhow!("Party {} 超时", party))??;
+        assert!(output.status.success(), "Party {} 失败: {}", party, output.status);
+        let line = String::from_utf8(output.stdout)?;
+        let report: PartyReport = serde_json::from_str(line.trim())
+            .with_context(|| format!("Party {} 输出无法解析: {}", party, line))?;
+        assert_eq!(report.party, party as u16);
+        reports.push(report);
+    }
+
+    println!("[4] 校验结果 ...");
+    let public_key = &reports[0].public_key;
+    assert!(reports.iter().all(|r| &r.public_key == public_key), "公钥不一致");
+    let signers = &reports[0].signers;
+    assert_eq!(signers.len(), THRESHOLD as usize);
+    assert!(reports.iter().all(|r| &r.signers == signers), "签名者集合不一致");
+    let signatures: Vec<_> = reports.iter().filter_map(|r| r.signature.clone()).collect();
+    assert_eq!(signatures.len(), THRESHOLD as usize);
+    assert!(signatures.iter().all(|s| s == &signatures[0]), "签名不一致");
+
+    let address = compute_eth_address_from_pubkey(&hex::decode(public_key)?);
+    let (r_hex, s_hex, v) = &signatures[0];
+    let r: [u8; 32] = hex::decode(r_hex)?.as_slice().try_into()?;
+    let s: [u8; 32] = hex::decode(s_hex)?.as_slice().try_into()?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash(), address).map_err(|e| anyhow!(e))?,
+        *v
+    );
+    println!("      钱包地址: {:?}", address);
+    println!("      签名者 {:?} 的签名可恢复出该地址", signers);
+    println!("完成: 5 个进程经 TCP 完成 keygen、aux_info_gen 与签名。");
+    Ok(())
+}
@@ -0,0 +1,274 @@
+//! 经中继运行的多进程演示 (Relay)
+//!
+//! 主进程为 3 个参与方准备节点身份密钥 (`data/relay_demo_keys/`)，用公钥登记表在本机启动一个
+//! `network::Relay`，再启动 3 个只连接中继的子进程 (模拟 NAT 之后的参与方)，子进程用身份私钥完成中继握手。
+//! 每个子进程依次运行:
+//! - `round_based` 流程: cggmp24 keygen (2-of-3) -> aux_info_gen -> 2 个签名者签名
+//! - manul 流程: Synedrion KeyInit -> AuxGen -> 3 方签名
+//!
+//! 主进程断言两种流程的公钥与签名一致且能恢复出钱包地址，并检查中继按会话 id 记录了转发统计。
+//! 运行方式: `cargo run --example network_relay`
+//!
+//! 子进程参数 (由主进程传入): `--party <id> --relay <addr> --session <id> --keys <dir>`
+
+use anyhow::{anyhow, Context};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::identity::NodeSigner;
+use my_mpc_bridge::network::{self, NetworkNode, Relay};
+use my_mpc_bridge::selection::{select_signers, SignerPolicy};
+use my_mpc_bridge::simulation::{
+    self, FastSecp256k1, SimpleSessionParams, SimpleSigner, SimpleVerifier,
+};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::SocketAddr;
+use std::process::Stdio;
+use std::time::Duration;
+
+type SynedrionParams = FastSecp256k1;
+type Signature = (String, String, u8);
+
+const PARTIES: u16 = 3;
+const CGGMP_THRESHOLD: u16 = 2;
+const MESSAGE: &[u8] = b"relayed mpc demo";
+const KEY_DIR: &str = "data/relay_demo_keys";
+
+fn key_path(key_dir: &str, party: u16) -> String {
+    format!("{}/party_{}.json", key_dir, party)
+}
+
+/// 子进程输出到 stdout 的结果
+#[derive(Serialize, Deserialize, Debug)]
+struct PartyReport {
+    party: u16,
+    cggmp_public_key: String,
+    cggmp_signers: Vec<u16>,
+    cggmp_signature: Option<Signature>,
+    synedrion_public_key: String,
+    synedrion_signature: Signature,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    match arg_value(&args, "--party") {
+        Some(party) => run_party(&args, party.parse()?).await,
+        None => run_coordinator().await,
+    }
+}
+
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn message_hash() -> [u8; 32] {
+    Sha256::digest(MESSAGE).into()
+}
+
+fn encode_signature((r, s, v): ([u8; 32], [u8; 32], u8)) -> Signature {
+    (hex::encode(r), hex::encode(s), v)
+}
+
+/// 子进程: 只连接中继，依次运行 cggmp24 与 Synedrion 流程
+async fn run_party(args: &[String], my_id: u16) -> anyhow::Result<()> {
+    let relay_addr: SocketAddr = arg_value(args, "--relay").context("missing --relay")?.parse()?;
+    let session = arg_value(args, "--session").context("missing --session")?;
+    let key_dir = arg_value(args, "--keys").context("missing --keys")?;
+    let identity = NodeSigner::load(key_path(key_dir, my_id))?;
+    let committee: Vec<u16> = (0..PARTIES).collect();
+    let node =
+        NetworkNode::connect_relay(my_id, &committee, relay_addr, identity.signing_key()).await?;
+    let hash = message_hash();
+
+    // 1. round_based: cggmp24
+    let primes = simulation::load_or_generate_primes()?;
+    let share = network::run_cggmp_dkg_party(
+        &node,
+        &format!("{}/cggmp", session),
+        CGGMP_THRESHOLD,
+        primes,
+    )
+    .await?;
+    let available: BTreeSet<u16> = committee.iter().copied().collect();
+    let cggmp_signers = select_signers(
+        &committee,
+        &available,
+        CGGMP_THRESHOLD,
+        &SignerPolicy::default(),
+        &hash,
+    )?;
+    let cggmp_signature = if cggmp_signers.contains(&my_id) {
+        Some(encode_signature(
+            network::run_cggmp_signing_party(
+                &node,
+                &format!("{}/cggmp/sign", session),
+                &share,
+                &cggmp_signers,
+                hash,
+            )
+            .await?,
+        ))
+    } else {
+        None
+    };
+
+    // 2. manul: Synedrion
+    let verifiers: BTreeMap<u16, SimpleVerifier> =
+        committee.iter().map(|&id| (id, SimpleVerifier(id))).collect();
+    let signer = SimpleSigner {
+        id: my_id,
+        verifier: SimpleVerifier(my_id),
+    };
+    let key_share = network::run_synedrion_key_init_party::<SynedrionParams, SimpleSessionParams>(
+        &node,
+        &format!("{}/synedrion/key-init", session),
+        signer.clone(),
+        &verifiers,
+    )
+    .await?;
+    let aux = network::run_synedrion_aux_gen_party::<SynedrionParams, SimpleSessionParams>(
+        &node,
+        &format!("{}/synedrion/aux-gen", session),
+        signer.clone(),
+        &verifiers,
+    )
+    .await?;
+    let synedrion_public_key = hex::encode(
+        bridge::get_global_public_key_point(&key_share)?
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let synedrion_signature = encode_signature(
+        network::run_synedrion_signing_party::<SynedrionParams, SimpleSessionParams>(
+            &node,
+            &format!("{}/synedrion/sign", session),
+            signer,
+            &verifiers,
+            key_share,
+            aux,
+            hash,
+        )
+        .await?,
+    );
+    node.shutdown().await;
+
+    let report = PartyReport {
+        party: my_id,
+        cggmp_public_key: hex::encode(share.shared_public_key.to_bytes(true)),
+        cggmp_signers,
+        cggmp_signature,
+        synedrion_public_key,
+        synedrion_signature,
+    };
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+fn assert_recovers(public_key_hex: &str, (r_hex, s_hex, v): &Signature) -> anyhow::Result<()> {
+    let address = compute_eth_address_from_pubkey(&hex::decode(public_key_hex)?);
+    let r: [u8; 32] = hex::decode(r_hex)?.as_slice().try_into()?;
+    let s: [u8; 32] = hex::decode(s_hex)?.as_slice().try_into()?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash(), address).map_err(|e| anyhow!(e))?,
+        *v
+    );
+    println!("      签名可恢复出 {:?}", address);
+    Ok(())
+}
+
+/// 主进程: 启动中继与 3 个子进程并校验结果
+async fn run_coordinator() -> anyhow::Result<()> {
+    println!("[1] 准备素数 (data/primes.txt)、节点身份密钥并在本机启动中继 ...");
+    simulation::load_or_generate_primes()?;
+    let mut registry = BTreeMap::new();
+    for party in 0..PARTIES {
+        let signer = NodeSigner::load_or_generate(party, key_path(KEY_DIR, party))?;
+        registry.insert(party, *signer.signing_key().verifying_key());
+    }
+    let relay = Relay::bind("127.0.0.1:0".parse()?, registry).await?;
+    println!("      中继地址: {}", relay.local_addr());
+
+    let mut session_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut session_bytes);
+    let session = format!("relay-demo-{}", hex::encode(session_bytes));
+
+    println!("[2] 启动 {} 个只连接中继的子进程 ...", PARTIES);
+    let exe = std::env::current_exe()?;
+    let mut children = Vec::new();
+    for party in 0..PARTIES {
+        let child = tokio::process::Command::new(&exe)
+            .args(["--party", &party.to_string()])
+            .args(["--relay", &relay.local_addr().to_string()])
+            .args(["--session", &session])
+            .args(["--keys", KEY_DIR])
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn Party {}", party))?;
+        children.push(child);
+    }
+
+    let mut reports = Vec::new();
+    for (party, child) in children.into_iter().enumerate() {
+        let output = tokio::time::timeout(Duration::from_secs(900), child.wait_with_output())
+            .await
+            .map_err(|_| anyhow!("Party {} 超时", party))??;
+        assert!(output.status.success(), "Party {} 失败: {}", party, output.status);
+        let line = String::from_utf8(output.stdout)?;
+        let report: PartyReport = serde_json::from_str(line.trim())
+            .with_context(|| format!("Party {} 输出无法解析: {}", party, line))?;
+        assert_eq!(report.party, party as u16);
+        reports.push(report);
+    }
+
+    println!("[3] 校验 cggmp24 (round_based) 结果 ...");
+    let first = &reports[0];
+    assert!(reports.iter().all(|r| r.cggmp_public_key == first.cggmp_public_key));
+    assert!(reports.iter().all(|r| r.cggmp_signers == first.cggmp_signers));
+    let cggmp_signatures: Vec<_> = reports
+        .iter()
+        .filter_map(|r| r.cggmp_signature.clone())
+        .collect();
+    assert_eq!(cggmp_signatures.len(), CGGMP_THRESHOLD as usize);
+    assert!(cggmp_signatures.iter().all(|s| s == &cggmp_signatures[0]));
+    assert_recovers(&first.cggmp_public_key, &cggmp_signatures[0])?;
+
+    println!("[4] 校验 Synedrion (manul) 结果 ...");
+    assert!(reports.iter().all(|r| r.synedrion_public_key == first.synedrion_public_key));
+    assert!(reports.iter().all(|r| r.synedrion_signature == first.synedrion_signature));
+    assert_recovers(&first.synedrion_public_key, &first.synedrion_signature)?;
+
+    println!("[5] 中继转发统计 ...");
+    let stats = relay.stats();
+    for name in [
+        "cggmp/keygen",
+        "cggmp/aux",
+        "cggmp/sign",
+        "synedrion/key-init",
+        "synedrion/aux-gen",
+        "synedrion/sign",
+    ] {
+        let session_stats = stats
+            .get(&format!("{}/{}", session, name))
+            .with_context(|| format!("中继没有会话 {} 的记录", name))?;
+        println!(
+            "      {:<20} broadcast={:<3} p2p={:<3} bytes={}",
+            name, session_stats.broadcast, session_stats.p2p, session_stats.payload_bytes
+        );
+    }
+    let sign_senders = &stats[&format!("{}/cggmp/sign", session)].senders;
+    assert_eq!(
+        sign_senders.iter().copied().collect::<Vec<_>>(),
+        first.cggmp_signers,
+        "只有签名者应在签名会话中发送消息"
+    );
+    println!("完成: 3 个进程经中继完成 cggmp24 与 Synedrion 的建钥和签名。");
+    Ok(())
+}
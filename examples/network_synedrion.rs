@@ -0,0 +1,222 @@
+//! 多进程 Synedrion 演示 (Networked manul Sessions)
+//!
+//! 为 3 个参与方生成节点身份密钥与公钥登记表，再启动 3 个独立进程，经本机 TCP 依次运行
+//! KeyInit -> AuxGen -> InteractiveSigning -> KeyRefresh -> InteractiveSigning。
+//! 每个会话都由 `network::run_manul_session` 驱动并用 `NodeSessionParams` 认证消息。
+//! 主进程断言: 所有参与方得到同一个公钥，刷新前后公钥不变，两次签名都能恢复出钱包地址。
+//! 运行方式: `cargo run --example network_synedrion`
+//!
+//! 子进程参数 (由主进程传入): `--party <id> --peers <addr0,addr1,...> --session <id> --keys <dir>`
+
+use anyhow::{anyhow, Context};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::network::{self, NetworkNode};
+use my_mpc_bridge::identity::{self, NodeSessionParams, NodeSigner, NodeVerifier};
+use my_mpc_bridge::simulation::FastSecp256k1;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::net::{SocketAddr, TcpListener};
+use std::process::Stdio;
+use std::time::Duration;
+use synedrion::signature::Keypair;
+
+type SynedrionParams = FastSecp256k1;
+
+const PARTIES: u16 = 3;
+const MESSAGES: [&[u8]; 2] = [b"before refresh", b"after refresh"];
+
+/// 子进程输出到 stdout 的结果
+#[derive(Serialize, Deserialize, Debug)]
+struct PartyReport {
+    party: u16,
+    public_key_before: String,
+    public_key_after: String,
+    signatures: Vec<(String, String, u8)>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    match arg_value(&args, "--party") {
+        Some(party) => run_party(&args, party.parse()?).await,
+        None => run_coordinator().await,
+    }
+}
+
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str)
+}
+
+fn message_hash(message: &[u8]) -> [u8; 32] {
+    Sha256::digest(message).into()
+}
+
+fn public_key_hex(share: &synedrion::KeyShare<SynedrionParams, NodeVerifier>) -> anyhow::Result<String> {
+    let point = bridge::get_global_public_key_point(share)?;
+    Ok(hex::encode(point.to_encoded_point(true).as_bytes()))
+}
+
+/// 子进程: 作为一个参与方运行完整流程
+async fn run_party(args: &[String], my_id: u16) -> anyhow::Result<()> {
+    let peers: BTreeMap<u16, SocketAddr> = arg_value(args, "--peers")
+        .context("missing --peers")?
+        .split(',')
+        .enumerate()
+        .map(|(id, addr)| Ok((id as u16, addr.parse()?)))
+        .collect::<anyhow::Result<_>>()?;
+    let session = arg_value(args, "--session").context("missing --session")?;
+    let key_dir = arg_value(args, "--keys").context("missing --keys")?;
+
+    // 本方私钥只保存在本机，其他参与方的公钥来自登记表
+    let signer = NodeSigner::load(format!("{}/party_{}.json", key_dir, my_id))?;
+    let verifiers = identity::load_verifiers(format!("{}/registry.json", key_dir))?;
+
+    let node = NetworkNode::connect_mesh(my_id, &peers).await?;
+
+    let key_share = network::run_synedrion_key_init_party::<SynedrionParams, NodeSessionParams>(
+        &node,
+        &format!("{}/key-init", session),
+        signer.clone(),
+        &verifiers,
+    )
+    .await?;
+    let public_key_before = public_key_hex(&key_share)?;
+    let aux = network::run_synedrion_aux_gen_party::<SynedrionParams, NodeSessionParams>(
+        &node,
+        &format!("{}/aux-gen", session),
+        signer.clone(),
+        &verifiers,
+    )
+    .await?;
+
+    let mut signatures = Vec::new();
+    let (r, s, v) = network::run_synedrion_signing_party::<SynedrionParams, NodeSessionParams>(
+        &node,
+        &format!("{}/sign-0", session),
+        signer.clone(),
+        &verifiers,
+        key_share.clone(),
+        aux,
+        message_hash(MESSAGES[0]),
+    )
+    .await?;
+    signatures.push((hex::encode(r), hex::encode(s), v));
+
+    let (key_share, aux) = network::run_synedrion_refresh_party::<SynedrionParams, NodeSessionParams>(
+        &node,
+        &format!("{}/refresh", session),
+        signer.clone(),
+        &verifiers,
+        key_share,
+    )
+    .await?;
+    let public_key_after = public_key_hex(&key_share)?;
+
+    let (r, s, v) = network::run_synedrion_signing_party::<SynedrionParams, NodeSessionParams>(
+        &node,
+        &format!("{}/sign-1", session),
+        signer,
+        &verifiers,
+        key_share,
+        aux,
+        message_hash(MESSAGES[1]),
+    )
+    .await?;
+    signatures.push((hex::encode(r), hex::encode(s), v));
+    node.shutdown().await;
+
+    let report = PartyReport {
+        party: my_id,
+        public_key_before,
+        public_key_after,
+        signatures,
+    };
+    println!("{}", serde_json::to_string(&report)?);
+    Ok(())
+}
+
+/// 主进程: 生成身份密钥、分配端口，启动 3 个子进程并校验结果
+async fn run_coordinator() -> anyhow::Result<()> {
+    let key_dir = "data/network_synedrion";
+
+    println!("[1] 生成 {} 个节点身份密钥与公钥登记表 ...", PARTIES);
+    let node_signers = (0..PARTIES)
+        .map(|id| NodeSigner::load_or_generate(id, format!("{}/party_{}.json", key_dir, id)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let verifiers: Vec<_> = node_signers.iter().map(|s| s.verifying_key()).collect();
+    identity::save_verifiers(format!("{}/registry.json", key_dir), &verifiers)?;
+
+    println!("[2] 分配本机端口 ...");
+    let listeners = (0..PARTIES)
+        .map(|_| TcpListener::bind("127.0.0.1:0"))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let addrs = listeners
+        .iter()
+        .map(|l| l.local_addr().map(|a| a.to_string()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    drop(listeners);
+    let mut session_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut session_bytes);
+    let session = format!("network-synedrion-{}", hex::encode(session_bytes));
+    println!("      {:?}", addrs);
+
+    println!("[3] 启动子进程: KeyInit -> AuxGen -> 签名 -> KeyRefresh -> 签名 ...");
+    let exe = std::env::current_exe()?;
+    let mut children = Vec::new();
+    for party in 0..PARTIES {
+        let child = tokio::process::Command::new(&exe)
+            .args(["--party", &party.to_string()])
+            .args(["--peers", &addrs.join(",")])
+            .args(["--session", &session])
+            .args(["--keys", key_dir])
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn Party {}", party))?;
+        children.push(child);
+    }
+
+    let mut reports = Vec::new();
+    for (party, child) in children.into_iter().enumerate() {
+        let output = tokio::time::timeout(Duration::from_secs(600), child.wait_with_output())
+            .await
+            .map_err(|_| anyhow!("Party {} 超时", party))??;
+        assert!(output.status.success(), "Party {} 失败: {}", party, output.status);
+        let line = String::from_utf8(output.stdout)?;
+        let report: PartyReport = serde_json::from_str(line.trim())
+            .with_context(|| format!("Party {} 输出无法解析: {}", party, line))?;
+        assert_eq!(report.party, party as u16);
+        reports.push(report);
+    }
+
+    println!("[4] 校验结果 ...");
+    let public_key = &reports[0].public_key_before;
+    for report in &reports {
+        assert_eq!(&report.public_key_before, public_key, "公钥不一致");
+        assert_eq!(&report.public_key_after, public_key, "刷新改变了公钥");
+        assert_eq!(report.signatures, reports[0].signatures, "签名不一致");
+    }
+    let address = compute_eth_address_from_pubkey(&hex::decode(public_key)?);
+    for (message, (r_hex, s_hex, v)) in MESSAGES.iter().zip(&reports[0].signatures) {
+        let r: [u8; 32] = hex::decode(r_hex)?.as_slice().try_into()?;
+        let s: [u8; 32] = hex::decode(s_hex)?.as_slice().try_into()?;
+        assert_eq!(
+            calc_recovery_id(&r, &s, &message_hash(message), address).map_err(|e| anyhow!(e))?,
+            *v
+        );
+        println!(
+            "      \"{}\" 的签名可恢复出 {:?}",
+            String::from_utf8_lossy(message),
+            address
+        );
+    }
+    println!("完成: 3 个进程经 TCP 完成 KeyInit、AuxGen、KeyRefresh 与两次签名。");
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! 节点身份认证演示 (Node Identity)
+//!
+//! 为 3 个节点生成 secp256k1 身份密钥与公钥登记表 -> 用 `NodeSessionParams` 运行 Synedrion KeyInit ->
+//! 演示伪造的消息被拒绝: 冒充其他节点、篡改消息、用未登记的密钥签名。
+//! 作为对照，`SimpleVerifier` 会接受同样伪造的消息。
+//! 运行方式: `cargo run --example node_identity`
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use manul::dev::tokio::run_async;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::identity::{self, NodeSessionParams, NodeSigner, NodeVerifier};
+use my_mpc_bridge::simulation::{FastSecp256k1, SimpleSigner, SimpleVerifier};
+use rand_core::OsRng;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use synedrion::signature::{DigestVerifier, Keypair, RandomizedDigestSigner};
+use synedrion::KeyInit;
+
+type SynedrionParams = FastSecp256k1;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let key_dir = "data/node_keys";
+    let registry_path = format!("{}/registry.json", key_dir);
+
+    println!("[1] 加载或生成节点身份密钥 ...");
+    let signers = (0..3u16)
+        .map(|id| NodeSigner::load_or_generate(id, format!("{}/party_{}.json", key_dir, id)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let own_verifiers: Vec<NodeVerifier> = signers.iter().map(|s| s.verifying_key()).collect();
+    identity::save_verifiers(&registry_path, &own_verifiers)?;
+    let registry = identity::load_verifiers(&registry_path)?;
+    for verifier in registry.values() {
+        println!("      {:?}", verifier);
+    }
+
+    println!("[2] 使用 NodeSessionParams 运行 Synedrion KeyInit ...");
+    let all_verifiers: BTreeSet<NodeVerifier> = registry.values().cloned().collect();
+    let mut entry_points = Vec::new();
+    for signer in &signers {
+        let entry_point = KeyInit::<SynedrionParams, NodeVerifier>::new(all_verifiers.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to create KeyInit: {}", e))?;
+        entry_points.push((signer.clone(), entry_point));
+    }
+    let results = run_async::<_, NodeSessionParams>(&mut OsRng, entry_points, true)
+        .await
+        .map_err(|e| anyhow::anyhow!("KeyInit simulation failed: {}", e))?
+        .results()
+        .map_err(|e| anyhow::anyhow!("KeyInit protocol execution failed: {}", e))?;
+    let public_keys: BTreeSet<Vec<u8>> = results
+        .values()
+        .map(|share| {
+            bridge::get_global_public_key_point(share)
+                .map(|p| p.to_encoded_point(true).as_bytes().to_vec())
+        })
+        .collect::<anyhow::Result<_>>()?;
+    assert_eq!(public_keys.len(), 1, "所有节点应得到同一个共享公钥");
+    println!("      KeyInit 完成，{} 个节点得到同一个共享公钥", results.len());
+
+    println!("[3] 伪造消息检测 ...");
+    let message = Sha256::new_with_prefix(b"round 1 broadcast from party 1");
+    let signature = signers[1].try_sign_digest_with_rng(&mut OsRng, message.clone())?;
+    assert!(registry[&1].verify_digest(message.clone(), &signature).is_ok());
+    println!("      Party 1 的签名通过 Party 1 公钥验证");
+
+    assert!(registry[&2].verify_digest(message.clone(), &signature).is_err());
+    println!("      Party 1 冒充 Party 2: 拒绝");
+
+    let tampered = Sha256::new_with_prefix(b"round 1 broadcast from party 1 (tampered)");
+    assert!(registry[&1].verify_digest(tampered, &signature).is_err());
+    println!("      篡改后的消息: 拒绝");
+
+    let attacker = NodeSigner::generate(0);
+    let forged = attacker.try_sign_digest_with_rng(&mut OsRng, message.clone())?;
+    assert!(registry[&0].verify_digest(message.clone(), &forged).is_err());
+    println!("      未登记的密钥冒充 Party 0: 拒绝");
+
+    let dummy_attacker = SimpleSigner {
+        id: 2,
+        verifier: SimpleVerifier(2),
+    };
+    let dummy_forged = dummy_attacker.try_sign_digest_with_rng(&mut OsRng, message.clone())?;
+    assert!(SimpleVerifier(0).verify_digest(message, &dummy_forged).is_ok());
+    println!("      (对照) SimpleVerifier 接受了伪造的消息");
+
+    println!("=== 节点身份认证演示完成 ===");
+    Ok(())
+}
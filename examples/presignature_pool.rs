@@ -0,0 +1,148 @@
+//! 预签名池演示 (Presignature Pool)
+//!
+//! cggmp24 DKG (3-of-5) -> 离线为签名者 {0, 2, 4} 生成 3 份预签名，每个签名者只把自己的预签名
+//! 存入自己的池 (`data/presignatures_demo/party_{i}.json`) -> 在线阶段每条消息各方各消耗一份预签名 ->
+//! 池耗尽后退化为完整签名。
+//! 断言每个签名都能恢复出钱包地址，池文件中只有本方的预签名，已消费的预签名不能再次放入池中，
+//! 密钥刷新后旧预签名不会再被取出。
+//! 运行方式: `cargo run --example presignature_pool`
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation::{self, CggmpPresignature, PresignaturePool};
+use std::collections::BTreeMap;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let pool_dir = "data/presignatures_demo";
+    let _ = std::fs::remove_dir_all(pool_dir);
+
+    println!("[1] 运行 cggmp24 DKG (3-of-5) ...");
+    let cggmp_shares =
+        simulation::run_dkg(5, 3, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    println!("      钱包地址: {:?}", address);
+
+    let signers: Vec<_> = [0usize, 2, 4]
+        .iter()
+        .map(|&k| cggmp_shares[k].clone())
+        .collect();
+    let participants: Vec<u16> = signers.iter().map(|s| s.core.i).collect();
+    let key = simulation::cggmp_presignature_key(&signers[0], &participants)?;
+
+    println!("[2] 离线阶段: 生成 3 份预签名，各方只保存自己的 ...");
+    let mut pools: BTreeMap<u16, PresignaturePool<CggmpPresignature>> = BTreeMap::new();
+    for &party in &participants {
+        let path = simulation::cggmp_presignature_pool_path(pool_dir, party);
+        pools.insert(party, PresignaturePool::open(path)?);
+    }
+    for (party, presignatures) in simulation::generate_cggmp_presignatures(&signers, 3).await? {
+        let pool = pools.get_mut(&party).expect("pool for every signer");
+        for presignature in presignatures {
+            assert_eq!(presignature.party, party);
+            simulation::store_cggmp_presignature(pool, &key, presignature)?;
+        }
+    }
+    for &party in &participants {
+        assert_eq!(pools[&party].available(&key), 3);
+        let path = simulation::cggmp_presignature_pool_path(pool_dir, party);
+        let content: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let owners: Vec<u64> = content["entries"][&key]
+            .as_array()
+            .expect("entries under key")
+            .iter()
+            .filter_map(|e| e["presignature"]["party"].as_u64())
+            .collect();
+        assert!(
+            owners.iter().all(|&p| p == party as u64),
+            "池文件中出现了其他签名者的预签名"
+        );
+    }
+    println!("      每个池文件只包含本方的 3 份预签名");
+
+    println!("[3] 在线阶段: 签名 4 条消息 (前 3 条使用预签名) ...");
+    for k in 0..4u8 {
+        let message_hash = [k + 1; 32];
+        let (r, s, v) =
+            simulation::run_cggmp_signing_with_pool(&signers, &mut pools, message_hash).await?;
+        assert_eq!(
+            calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+            v
+        );
+        println!("      消息 {} 签名可恢复出原地址", k + 1);
+    }
+    for pool in pools.values() {
+        assert_eq!(pool.available(&key), 0);
+    }
+
+    println!("[4] 一次性校验 ...");
+    let party = participants[0];
+    let mut pool = PresignaturePool::<CggmpPresignature>::open(
+        simulation::cggmp_presignature_pool_path(pool_dir, party),
+    )?;
+    assert_eq!(pool.available(&key), 0);
+    let presignature = simulation::generate_cggmp_presignatures(&signers, 1)
+        .await?
+        .remove(&party)
+        .and_then(|mut list| list.pop())
+        .expect("presignature for party");
+    let id = presignature.id.clone();
+    simulation::store_cggmp_presignature(&mut pool, &key, presignature)?;
+    let entry = pool.take(&key)?.expect("presignature just stored");
+    assert_eq!(entry.id, id);
+    assert!(
+        pool.insert(&key, entry).is_err(),
+        "已消费的预签名不应被重新放入"
+    );
+    println!("      已消费的预签名无法再次使用");
+
+    println!("[5] 密钥刷新后旧预签名失效 ...");
+    let mut pools: BTreeMap<u16, PresignaturePool<CggmpPresignature>> = BTreeMap::new();
+    for &party in &participants {
+        let path = simulation::cggmp_presignature_pool_path(pool_dir, party);
+        pools.insert(party, PresignaturePool::open(path)?);
+    }
+    for (party, presignatures) in simulation::generate_cggmp_presignatures(&signers, 1).await? {
+        for presignature in presignatures {
+            simulation::store_cggmp_presignature(
+                pools.get_mut(&party).unwrap(),
+                &key,
+                presignature,
+            )?;
+        }
+    }
+    let refreshed = simulation::run_bridge_reshare_refresh(
+        &cggmp_shares,
+        &bridge::EvaluationPoints::contiguous(cggmp_shares.len() as u16),
+        ExecutionId::new(b"presignature-pool-demo-refresh"),
+    )
+    .await?;
+    let refreshed_signers: Vec<_> = [0usize, 2, 4]
+        .iter()
+        .map(|&k| refreshed[k].clone())
+        .collect();
+    let refreshed_key = simulation::cggmp_presignature_key(&refreshed_signers[0], &participants)?;
+    assert_ne!(refreshed_key, key, "刷新后预签名的分组 key 必须改变");
+    for pool in pools.values() {
+        assert_eq!(pool.available(&key), 1);
+        assert_eq!(pool.available(&refreshed_key), 0);
+    }
+    // 刷新后的分片不会取到旧预签名，直接运行完整签名
+    let message_hash = [0x55; 32];
+    let (r, s, v) =
+        simulation::run_cggmp_signing_with_pool(&refreshed_signers, &mut pools, message_hash)
+            .await?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+        v
+    );
+    for pool in pools.values_mut() {
+        assert_eq!(pool.discard(&key)?, 1);
+    }
+    println!("      刷新后的分片未使用旧预签名，旧预签名已丢弃");
+
+    println!("=== 预签名池演示完成 ===");
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Key Refresh 对比演示 (Reshare vs Synedrion)
+//!
+//! cggmp24 DKG (3-of-5) 之后分别走两条刷新路径:
+//! 1. 本库重共享 + cggmp24 AuxGen: `simulation::run_bridge_reshare_refresh`
+//!    (cggmp24 的 `key_refresh` 只支持 n-of-n 密钥)
+//! 2. Synedrion 往返: cggmp24 -> portable -> Synedrion `KeyRefresh` -> portable -> cggmp24
+//!
+//! 断言两条路径得到的私钥分片互不相同且都已更新，但都能签出同一个以太坊地址。
+//! 运行方式: `cargo run --example refresh_comparison`
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation::{self, FastSecp256k1};
+use std::collections::BTreeSet;
+
+type SynedrionParams = FastSecp256k1;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let n_parties = 5;
+    let threshold = 3;
+    let message_hash = [0x42u8; 32];
+
+    println!("[1] 运行 cggmp24 DKG ({}-of-{}) ...", threshold, n_parties);
+    let cggmp_shares = simulation::run_dkg(
+        n_parties,
+        threshold,
+        ExecutionId::new(b"demo-mixed-mpc-session"),
+    )
+    .await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let y_hex = hex::encode(&pubkey_bytes);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    // DKG 委员会是连续的 party id 0..n
+    let committee = bridge::EvaluationPoints::contiguous(n_parties);
+    println!("      钱包地址: {:?}", address);
+
+    println!("[2] 重共享 Key Refresh ...");
+    let reshare_shares = simulation::run_bridge_reshare_refresh(
+        &cggmp_shares,
+        &committee,
+        ExecutionId::new(b"demo-reshare-refresh"),
+    )
+    .await?;
+
+    println!("[3] Synedrion 往返 Key Refresh ...");
+    let party_ids: BTreeSet<u16> = cggmp_shares.iter().map(|s| s.core.i).collect();
+    let aux_map = simulation::run_synedrion_aux_gen::<SynedrionParams>(party_ids).await?;
+    let synedrion_data =
+        simulation::cggmp_shares_to_synedrion(&cggmp_shares, &committee, &aux_map)?;
+    let refreshed = simulation::run_refresh_workflow(
+        synedrion_data,
+        &committee,
+        "data/refresh_comparison_synedrion.json",
+        true,
+    )
+    .await?;
+    let synedrion_shares = simulation::synedrion_shares_to_cggmp(
+        &cggmp_shares,
+        &refreshed,
+        &y_hex,
+        threshold,
+        &committee,
+    )?;
+
+    println!("[4] 对比两条路径的结果 ...");
+    for ((old, reshared), synedrion) in cggmp_shares
+        .iter()
+        .zip(&reshare_shares)
+        .zip(&synedrion_shares)
+    {
+        let old_x = bridge::from_cggmp_to_portable(old, Some(&committee))?.x_hex;
+        let reshared_x = bridge::from_cggmp_to_portable(reshared, Some(&committee))?.x_hex;
+        let synedrion_x = bridge::from_cggmp_to_portable(synedrion, Some(&committee))?.x_hex;
+        assert_eq!(reshared.core.i, old.core.i);
+        assert_eq!(synedrion.core.i, old.core.i);
+        assert_ne!(reshared_x, old_x, "重共享刷新未更新 Party {} 的分片", old.core.i);
+        assert_ne!(synedrion_x, old_x, "Synedrion 刷新未更新 Party {} 的分片", old.core.i);
+        assert_ne!(reshared_x, synedrion_x);
+        for share in [reshared, synedrion] {
+            let new_pubkey_bytes = share.shared_public_key.to_bytes(true);
+            assert_eq!(new_pubkey_bytes.as_ref(), pubkey_bytes.as_ref());
+            assert_eq!(bridge::from_cggmp_to_portable(share, Some(&committee))?.t, threshold);
+        }
+    }
+    println!("      私钥分片均已更新，共享公钥不变");
+
+    for (name, shares) in [("重共享", &reshare_shares), ("Synedrion", &synedrion_shares)] {
+        let signers: Vec<_> = [0usize, 2, 4].iter().map(|&k| shares[k].clone()).collect();
+        let (r, s, v) = simulation::run_cggmp_signing(&signers, message_hash).await?;
+        assert_eq!(
+            calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+            v
+        );
+        println!("      {} 刷新后的分片签名可恢复出原地址 {:?}", name, address);
+    }
+
+    println!("=== 两条刷新路径结果一致: 地址保持不变 ===");
+    Ok(())
+}
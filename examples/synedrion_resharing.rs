@@ -0,0 +1,87 @@
+//! Synedrion 原生重共享演示 (Synedrion KeyResharing)
+//!
+//! cggmp24 DKG (3-of-5, 参与方 {0..4}) -> 导入为 Synedrion ThresholdKeyShare -> 旧持有者 {0, 2, 3}
+//! 通过 Synedrion `KeyResharing` 把密钥转移给新持有者 {0, 2, 3, 7, 9} -> 转换回 cggmp24 并签名。
+//! 与 `committee_rotation` (本库的重共享协议) 是两条独立实现的委员会变更路径。
+//! 运行方式: `cargo run --example synedrion_resharing`
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let old_threshold = 3;
+    let new_threshold = 3;
+    let dealers = [0u16, 2, 3];
+    let new_holders: BTreeSet<u16> = [0u16, 2, 3, 7, 9].into_iter().collect();
+    let message_hash = [0x42u8; 32];
+
+    println!("[1] 运行 cggmp24 DKG ({}-of-5) ...", old_threshold);
+    let cggmp_shares =
+        simulation::run_dkg(5, old_threshold, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let y_hex = hex::encode(&pubkey_bytes);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    println!("      钱包地址: {:?}", address);
+
+    println!("[2] 旧持有者 {:?} 导入为 Synedrion ThresholdKeyShare ...", dealers);
+    let old_committee = bridge::EvaluationPoints::contiguous(5);
+    let mut old_shares = BTreeMap::new();
+    for share in cggmp_shares.iter().filter(|s| dealers.contains(&s.core.i)) {
+        let portable = bridge::from_cggmp_to_portable(share, Some(&old_committee))?;
+        old_shares.insert(
+            share.core.i,
+            bridge::from_portable_to_synedrion_threshold(&portable)?,
+        );
+    }
+
+    println!(
+        "[3] Synedrion KeyResharing -> 新持有者 {:?} ({}-of-{}) ...",
+        new_holders,
+        new_threshold,
+        new_holders.len()
+    );
+    let new_shares =
+        simulation::run_synedrion_resharing(&old_shares, &y_hex, new_holders.clone(), new_threshold)
+            .await?;
+    assert_eq!(new_shares.keys().copied().collect::<BTreeSet<_>>(), new_holders);
+
+    println!("[4] 转换回 cggmp24 并签名 ...");
+    // 新委员会的求值点由 Synedrion 分配 (share_ids)，从任一新分片读出
+    let new_committee = new_shares
+        .values()
+        .next()
+        .map(|share| bridge::from_synedrion_threshold_to_portable(share, y_hex.clone()))
+        .transpose()?
+        .map(|portable| portable.committee_points())
+        .transpose()?
+        .expect("KeyResharing 至少输出一个新分片");
+    let new_cggmp_shares = simulation::synedrion_threshold_shares_to_cggmp(
+        &new_shares,
+        &y_hex,
+        ExecutionId::new(b"demo-synedrion-resharing"),
+    )
+    .await?;
+    for share in &new_cggmp_shares {
+        let new_pubkey_bytes = share.shared_public_key.to_bytes(true);
+        assert_eq!(new_pubkey_bytes.as_ref(), pubkey_bytes.as_ref());
+        assert_eq!(bridge::from_cggmp_to_portable(share, Some(&new_committee))?.t, new_threshold);
+    }
+    // 新委员会中位置 1, 3, 4 对应 party 2, 7, 9
+    let signers: Vec<_> = [1usize, 3, 4]
+        .iter()
+        .map(|&k| new_cggmp_shares[k].clone())
+        .collect();
+    let (r, s, v) = simulation::run_cggmp_signing(&signers, message_hash).await?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+        v
+    );
+    println!("      cggmp24 签名可恢复出原地址 {:?}", address);
+
+    println!("=== Synedrion KeyResharing 完成，地址保持不变 ===");
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! 门限变更演示 (Threshold Change)
+//!
+//! cggmp24 DKG (3-of-5) -> 重共享为 4-of-5 (Feldman 与 Pedersen 各一次) -> 用新分片签名。
+//! 每一步都断言共享公钥与以太坊地址保持不变，任何断言失败都会以非零状态码退出。
+//! 运行方式: `cargo run --example threshold_change`
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge::resharing::VssScheme;
+use my_mpc_bridge::bridge::{self, PortableKeyShare};
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation::{self, run_cggmp_signing};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let n_parties = 5;
+    let old_threshold = 3;
+    let new_threshold = 4;
+
+    println!("[1] 运行 cggmp24 DKG ({}-of-{}) ...", old_threshold, n_parties);
+    let cggmp_shares = simulation::run_dkg(
+        n_parties,
+        old_threshold,
+        ExecutionId::new(b"demo-mixed-mpc-session"),
+    )
+    .await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    println!("      钱包地址: {:?}", address);
+
+    // DKG 委员会是连续的 party id 0..n
+    let committee = bridge::EvaluationPoints::contiguous(n_parties);
+    let portable_shares = cggmp_shares
+        .iter()
+        .map(|share| bridge::from_cggmp_to_portable(share, Some(&committee)))
+        .collect::<anyhow::Result<Vec<PortableKeyShare>>>()?;
+
+    for scheme in [VssScheme::Feldman, VssScheme::Pedersen] {
+        println!(
+            "[2] {:?}: 重共享 {}-of-{} -> {}-of-{} ...",
+            scheme, old_threshold, n_parties, new_threshold, n_parties
+        );
+        let reshared =
+            bridge::reshare_with_new_threshold(portable_shares.clone(), new_threshold, scheme)?;
+
+        let new_cggmp_shares = cggmp_shares
+            .iter()
+            .zip(&reshared)
+            .map(|(template, share)| bridge::from_portable_to_cggmp(template, share, None, None))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for share in &new_cggmp_shares {
+            let new_pubkey_bytes = share.shared_public_key.to_bytes(true);
+            assert_eq!(new_pubkey_bytes.as_ref(), pubkey_bytes.as_ref());
+            assert_eq!(compute_eth_address_from_pubkey(&new_pubkey_bytes), address);
+            assert_eq!(bridge::from_cggmp_to_portable(share, Some(&committee))?.t, new_threshold);
+        }
+        println!("      共享公钥与地址不变, min_signers = {}", new_threshold);
+
+        println!("[3] {:?}: 使用 {} 个新分片签名 ...", scheme, new_threshold);
+        let message_hash = [0x42u8; 32];
+        let signers = &new_cggmp_shares[n_parties as usize - new_threshold as usize..];
+        let (r, s, v) = run_cggmp_signing(signers, message_hash).await?;
+        let recovered_v = calc_recovery_id(&r, &s, &message_hash, address)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        assert_eq!(recovered_v, v);
+        println!("      签名可恢复出原地址 {:?}", address);
+    }
+
+    println!("=== 门限变更完成，地址保持不变 ===");
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! `mpc-relay` 消息中继
+//!
+//! ```text
+//! mpc-relay --listen 127.0.0.1:7000 --registry data/node_keys/registry.json
+//! ```
+//!
+//! 只接受身份公钥登记表 (`{"<party id>": "<公钥 hex>"}`) 中的参与方，参与方通过
+//! `NetworkNode::connect_relay` 用身份私钥完成握手。按 Ctrl-C 退出时向 stdout 输出各会话的转发统计 (JSON)。
+
+use clap::Parser;
+use my_mpc_bridge::network::{self, Relay};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "mpc-relay",
+    version,
+    about = "按会话 id 与 party id 转发 MPC 协议消息的中继，不解析消息内容"
+)]
+struct Args {
+    /// 监听地址
+    #[arg(long, default_value = "127.0.0.1:7000")]
+    listen: SocketAddr,
+
+    /// 身份公钥登记表，只接受并转发给其中的参与方
+    #[arg(long)]
+    registry: PathBuf,
+
+    /// 每隔多少秒向 stderr 输出一次在线参与方 (0 表示不输出)
+    #[arg(long, default_value_t = 0)]
+    status_interval: u64,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let registry = network::load_registry(&args.registry)?;
+    let relay = Relay::bind(args.listen, registry.clone()).await?;
+    eprintln!(
+        "[RELAY] listening on {}, 登记的参与方 {:?}",
+        relay.local_addr(),
+        registry.keys().collect::<Vec<_>>()
+    );
+
+    if args.status_interval > 0 {
+        let mut interval = tokio::time::interval(Duration::from_secs(args.status_interval));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    eprintln!(
+                        "[RELAY] 在线参与方 {:?}，会话数 {}",
+                        relay.connected_parties(),
+                        relay.stats().len()
+                    );
+                }
+                result = tokio::signal::ctrl_c() => {
+                    result?;
+                    break;
+                }
+            }
+        }
+    } else {
+        tokio::signal::ctrl_c().await?;
+    }
+
+    println!("{}", serde_json::to_string_pretty(&relay.stats())?);
+    Ok(())
+}
@@ -1,4 +1,9 @@
-use super::common::{pad_hex, strip_0x, PortableKeyShare};
+use super::common::{
+    pad_hex, strip_0x, EvaluationPoints, Origin, PortableKeyShare, PublicShare, ShareKind,
+};
+#[cfg(feature = "synedrion")]
+use super::core::{apply_refresh_deltas, parse_k256_point, RefreshDeltaDealing};
+use super::core::{derive_vss_commitments, global_params_from_public_data};
 use anyhow::{anyhow, Context, Result};
 use cggmp24::generic_ec::{Point, Scalar};
 use cggmp24::key_share::AnyKeyShare; // 尽管未直接使用，但保留以防 trait 依赖
@@ -15,8 +20,18 @@ use serde_json::Value;
 /// 从 cggmp24 导出密钥分片
 ///
 /// **功能**: 将 `cggmp24::KeyShare` 转换为通用的 `PortableKeyShare`。
+///
+/// **参数**:
+/// - `committee`: 委员会的 party id -> 求值点映射。cggmp24 只保存位置 `core.i` 与求值点 `vss_setup.I`，
+///   不保存 party id，因此 party id 必须由调用方给出 (与 `update_cggmp_shares_from_portable` 相同)。
+///
+/// **注意**:
+/// - t-of-n 密钥必须提供 `committee`，且按 party id 升序排列的求值点必须与 `vss_setup.I` 完全一致，否则报错。
+/// - n-of-n 密钥 (没有 `vss_setup`) 导出为 `ShareKind::Additive`，`t = n`，不携带 VSS Commitments；
+///   没有提供 `committee` 时 party id 即位置 0..n。
 pub fn from_cggmp_to_portable<E: cggmp24::generic_ec::Curve, L: SecurityLevel>(
     share: &CggmpKeyShare<E, L>,
+    committee: Option<&EvaluationPoints>,
 ) -> Result<PortableKeyShare> {
     // Use serde to bypass private field access
     let v: Value = serde_json::to_value(share)?;
@@ -34,29 +49,113 @@ pub fn from_cggmp_to_portable<E: cggmp24::generic_ec::Curve, L: SecurityLevel>(
     let y_hex = hex::encode(share.shared_public_key().to_bytes(true));
 
     // 3. Extract metadata
-    let i = v.pointer("/core/i").and_then(|v| v.as_u64()).unwrap_or(0) as u16;
+    // core.i 是本方在委员会中的位置，第 k 个位置对应 committee 中第 k 小的 party id
+    let position = v
+        .pointer("/core/i")
+        .and_then(|v| v.as_u64())
+        .context("Missing core.i")? as usize;
+    let raw_public_shares = v
+        .pointer("/core/public_shares")
+        .and_then(|v| v.as_array())
+        .context("Missing core.public_shares")?;
+    let n = raw_public_shares.len() as u16;
     let min_signers = v
         .pointer("/core/vss_setup/min_signers")
         .and_then(|v| v.as_u64())
-        .unwrap_or(0) as u16;
-    let n = v
-        .pointer("/core/vss_setup/I")
-        .and_then(|v| v.as_array())
-        .map(|a| a.len())
-        .unwrap_or(0) as u16;
-
-    Ok(PortableKeyShare {
-        i,
-        t: min_signers,
-        n,
-        x_hex,
-        y_hex,
-    })
+        .map(|t| t as u16);
+    let points = match v.pointer("/core/vss_setup/I").and_then(|v| v.as_array()) {
+        Some(list) => Some(
+            list.iter()
+                .map(|x| parse_evaluation_point(x.as_str().context("Invalid evaluation point")?))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        None => None,
+    };
+    if let (Some(points), Some(committee)) = (&points, committee) {
+        if &committee.points() != points {
+            return Err(anyhow!(
+                "委员会 {:?} 的求值点 {:?} 与 vss_setup.I {:?} 不一致",
+                committee.ids(),
+                committee.points(),
+                points
+            ));
+        }
+    }
+    let party_ids: Vec<u16> = match (committee, &points) {
+        (Some(committee), _) => committee.ids(),
+        (None, Some(_)) => {
+            return Err(anyhow!(
+                "t-of-n 分片需要调用方提供委员会的 EvaluationPoints 才能确定 party id"
+            ))
+        }
+        (None, None) => (0..n).collect(),
+    };
+    if party_ids.len() != n as usize {
+        return Err(anyhow!(
+            "委员会大小 {} 与公钥分片数量 {} 不一致",
+            party_ids.len(),
+            n
+        ));
+    }
+    let i = *party_ids
+        .get(position)
+        .with_context(|| format!("core.i = {} 超出委员会大小 {}", position, n))?;
+
+    // 4. Extract public data (公钥分片按位置排列，对应 party_ids)
+    let public_shares = raw_public_shares
+        .iter()
+        .zip(&party_ids)
+        .map(|(p, &id)| {
+            let point_hex = p.as_str().context("Invalid public share")?;
+            Ok(PublicShare {
+                i: id,
+                point_hex: strip_0x(point_hex).to_string(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut portable = match (min_signers, committee) {
+        (Some(t), Some(committee)) => {
+            let mut portable = PortableKeyShare::new(Origin::Cggmp24, i, t, n, x_hex, y_hex);
+            portable.evaluation_points = Some(committee.clone());
+            portable
+        }
+        _ => {
+            // n-of-n: x = sum x_i，没有 Shamir 多项式，也就没有 VSS Commitments
+            let mut portable = PortableKeyShare::new(Origin::Cggmp24, i, n, n, x_hex, y_hex);
+            portable.kind = ShareKind::Additive {
+                signers: party_ids.clone(),
+            };
+            portable
+        }
+    };
+    portable.public_shares = Some(public_shares);
+    if portable.is_shamir() {
+        portable.vss_commitments = Some(derive_vss_commitments(&portable)?);
+    }
+
+    Ok(portable)
+}
+
+/// 解析 cggmp24 `vss_setup.I` 中的求值点 (32 字节 big-endian hex)
+fn parse_evaluation_point(hex_str: &str) -> Result<u64> {
+    let bytes = hex::decode(pad_hex(strip_0x(hex_str).to_string()))?;
+    let (high, low) = bytes.split_at(bytes.len().saturating_sub(8));
+    if high.iter().any(|&b| b != 0) {
+        return Err(anyhow!("Evaluation point {} does not fit in u64", hex_str));
+    }
+    let mut buf = [0u8; 8];
+    buf[8 - low.len()..].copy_from_slice(low);
+    Ok(u64::from_be_bytes(buf))
 }
 
 /// 更新 cggmp24 密钥分片
 ///
 /// **功能**: 使用刷新后的数据 (PortableKeyShare) 更新旧的 cggmp24::KeyShare 模板。
+///
+/// 如果没有显式传入 `new_public_shares` / `new_vss_commitments`，而 `refreshed` 携带了
+/// 公开数据，则直接使用 `refreshed` 中的公钥分片 (见 `global_params_from_public_data`)，
+/// 只需要本方的私钥分片即可重建 KeyShare。
 pub fn from_portable_to_cggmp<E, L>(
     template_share: &CggmpKeyShare<E, L>,
     refreshed: &PortableKeyShare,
@@ -67,6 +166,24 @@ where
     E: cggmp24::generic_ec::Curve,
     L: SecurityLevel,
 {
+    if !refreshed.is_shamir() {
+        return Err(anyhow!(
+            "cggmp24 只接受 Shamir 分片，Party {} 是 {:?}",
+            refreshed.i,
+            refreshed.kind
+        ));
+    }
+
+    let derived;
+    let (new_public_shares, new_vss_commitments) = match (new_public_shares, new_vss_commitments)
+    {
+        (None, None) if refreshed.public_shares.is_some() => {
+            derived = global_params_from_public_data(refreshed)?;
+            (Some(derived.1.as_slice()), Some(derived.0.as_slice()))
+        }
+        explicit => explicit,
+    };
+
     let mut share_json = serde_json::to_value(template_share)?;
 
     // Update Core (x)
@@ -75,6 +192,13 @@ where
             *x_field = serde_json::Value::String(refreshed.x_hex.clone());
         }
 
+        // Update threshold (门限变更后 min_signers 随之改变)
+        if refreshed.t > 0 {
+            if let Some(min_signers) = core.pointer_mut("/vss_setup/min_signers") {
+                *min_signers = serde_json::json!(refreshed.t);
+            }
+        }
+
         // Update Public Shares
         if let Some(ps) = new_public_shares {
             if let Some(ps_field) = core.get_mut("public_shares") {
@@ -107,6 +231,61 @@ where
     Ok(updated_share)
 }
 
+/// 为新委员会构造 cggmp24 核心分片 (Build IncompleteKeyShare without Template)
+///
+/// **功能**: 委员会轮换后，新加入的成员没有旧的 cggmp24 KeyShare 可作模板。
+/// 这里只用 `share` 中的私钥分片与公开数据直接构造 `IncompleteKeyShare`，
+/// 再与新委员会运行 `aux_info_gen` 得到的 AuxInfo 组合 (`KeyShare::from_parts`) 即可。
+///
+/// **索引映射**: 委员会由 `share.committee_points()` 决定 (party id 升序)。
+/// cggmp24 的 `i` 是本方在委员会中的位置，`vss_setup.I` 保存每个位置对应的求值点，
+/// 因此 party id 与求值点都不必连续。
+pub fn from_portable_to_cggmp_core<E: cggmp24::generic_ec::Curve>(
+    share: &PortableKeyShare,
+) -> Result<cggmp24::IncompleteKeyShare<E>> {
+    if !share.is_shamir() {
+        return Err(anyhow!(
+            "cggmp24 只接受 Shamir 分片，Party {} 是 {:?}",
+            share.i,
+            share.kind
+        ));
+    }
+    let (commitments, public_shares_hex) = global_params_from_public_data(share)?;
+
+    let committee = share.committee_points()?;
+    let position = committee
+        .ids()
+        .iter()
+        .position(|&id| id == share.i)
+        .with_context(|| format!("Party {} 不在委员会 {:?} 中", share.i, committee.ids()))?;
+    if public_shares_hex.len() != committee.len() {
+        return Err(anyhow!(
+            "公钥分片数量 {} 与委员会大小 {} 不一致",
+            public_shares_hex.len(),
+            committee.len()
+        ));
+    }
+    let evaluation_points: Vec<String> = committee
+        .points()
+        .iter()
+        .map(|x| format!("{:064x}", x))
+        .collect();
+
+    let core_json = serde_json::json!({
+        "curve": E::CURVE_NAME,
+        "i": position,
+        "shared_public_key": commitments[0],
+        "public_shares": public_shares_hex,
+        "vss_setup": {
+            "min_signers": share.t,
+            "I": evaluation_points,
+        },
+        "x": pad_hex(strip_0x(&share.x_hex).to_string()),
+    });
+
+    serde_json::from_value(core_json).context("Failed to deserialize IncompleteKeyShare")
+}
+
 /// 重构全局参数 (Reconstruct Global Parameters)
 ///
 /// **功能**: 根据一组 PortableKeyShare (Shamir 分片)，重构多项式，计算 VSS Commitments 和 Public Shares。
@@ -128,20 +307,21 @@ where
 ///   在实际 MPC 协议 (如 DKG/Resharing) 中，各方会交互**公钥分片**或**Commitments**，
 ///   而不是汇聚私钥来计算。此函数相当于模拟了 DKG 结束时各方达成共识的全局参数。
 ///
-/// todo:
-// 目前的实现中，CGGMP 部分是通过私钥重算公钥，而 Synedrion 是通过协议输出公钥。
-// 未来优化时，
-// 可以让 CGGMP 的更新逻辑直接使用 Synedrion 产出的公钥列表，从而避免重复计算和对私钥的依赖。
+/// **替代方案**: 如果 PortableKeyShare 携带了 `public_shares` (例如来自 `from_synedrion_to_portable`)，
+/// 应使用 `global_params_from_public_data`，它只依赖公开数据。
+///
+/// **求值点**: `committee` 给出委员会中每个 party id 的求值点，输出的 Public Shares 按 party id 升序排列
+/// (即 cggmp24 的位置顺序)。party id 不必连续。
 pub fn reconstruct_global_params<E: cggmp24::generic_ec::Curve>(
     refreshed_data: &[PortableKeyShare],
+    committee: &EvaluationPoints,
 ) -> Result<(Vec<String>, Vec<String>)> {
     // 1. 重构多项式系数 (Reconstruct Polynomial Coefficients)
     let mut shares_points = Vec::new();
     for data in refreshed_data {
         let x_bytes = hex::decode(&data.x_hex)?;
         let x_scalar = Scalar::<E>::from_be_bytes_mod_order(&x_bytes);
-        // x coordinate for party i is i+1 (cggmp convention)
-        let x_coord = Scalar::<E>::from(data.i as u64 + 1);
+        let x_coord = Scalar::<E>::from(committee.point(data.i)?);
         let y_point = Point::<E>::generator() * x_scalar;
         shares_points.push((x_coord, y_point));
     }
@@ -200,11 +380,10 @@ pub fn reconstruct_global_params<E: cggmp24::generic_ec::Curve>(
         .collect();
 
     // 2. 计算所有人的 Public Shares
-    let n_total = refreshed_data.first().map(|d| d.n).unwrap_or(0) as usize;
-    let mut new_public_shares_hex = Vec::with_capacity(n_total);
+    let mut new_public_shares_hex = Vec::with_capacity(committee.len());
 
-    for i in 0..n_total {
-        let x_coord = Scalar::<E>::from((i + 1) as u64);
+    for (_, x) in committee.iter() {
+        let x_coord = Scalar::<E>::from(x);
         let mut y_point = Point::<E>::generator() * Scalar::<E>::from(0u64);
         let mut x_pow = Scalar::<E>::one();
         for coeff in &coeffs {
@@ -217,19 +396,77 @@ pub fn reconstruct_global_params<E: cggmp24::generic_ec::Curve>(
     Ok((new_commitments_hex, new_public_shares_hex))
 }
 
+/// 单方更新 cggmp24 密钥分片 (Per-party Update from Synedrion Refresh)
+///
+/// **功能**: 只使用**本方**收到的子分片 + Synedrion `KeyRefresh` 输出的 `KeyShareChange`，
+/// 更新本方的 cggmp24 KeyShare。
+///
+/// **流程**:
+/// 1. 用 `extract_refresh_delta` 读出每个参与方的加法增量 D_j = d_j·G (sum d_j = 0)。
+/// 2. 每个参与方已用 `deal_refresh_delta` 把 d_j 重共享为 t-of-n Feldman 分片 (`dealings`)，
+///    `apply_refresh_deltas` 校验 H_{j,0} == D_j 后把增量换算到 Shamir 域，
+///    x_i' = x_i + sum_j h_j(x_i)，X_k' = X_k + sum_j H_j(x_k)。
+/// 3. 在指数上插值得到新的 VSS Commitments，并校验 x_i'·G == X_i'、F_0 == Y。
+///
+/// **为什么需要重共享**: d_j 只满足 sum d_j = 0，直接加到 Shamir 分片上 (或除以 Lagrange 系数后再加)
+/// 得到的点一般落在 n-1 次多项式上，n > t 时无法构造 t-of-n 的 cggmp24 分片。
+///
+/// **与 `update_cggmp_shares_from_portable` 的区别**: 后者需要所有人的私钥分片，
+/// 本函数可以在每个参与方的节点上独立运行。
+#[cfg(feature = "synedrion")]
+pub fn update_cggmp_share_from_refresh_change<E, L, P, Id>(
+    template_share: &CggmpKeyShare<E, L>,
+    change: &synedrion::KeyShareChange<P, Id>,
+    dealings: &[RefreshDeltaDealing],
+    committee: &EvaluationPoints,
+    party_id: impl Fn(u16) -> Id,
+) -> Result<CggmpKeyShare<E, L>>
+where
+    E: cggmp24::generic_ec::Curve,
+    L: SecurityLevel,
+    P: synedrion::SchemeParams,
+    Id: manul::protocol::PartyId,
+    <P::Curve as elliptic_curve::CurveArithmetic>::ProjectivePoint:
+        elliptic_curve::sec1::FromEncodedPoint<P::Curve>
+            + elliptic_curve::sec1::ToEncodedPoint<P::Curve>,
+{
+    use elliptic_curve::sec1::ToEncodedPoint;
+
+    let old = from_cggmp_to_portable(template_share, Some(committee))?;
+    let mut additive_deltas = std::collections::BTreeMap::new();
+    for id in committee.ids() {
+        let delta = super::synedrion::extract_refresh_delta(change, party_id(id))?
+            .with_context(|| format!("Missing refresh delta for party {}", id))?;
+        additive_deltas.insert(
+            id,
+            parse_k256_point(&hex::encode(delta.to_encoded_point(true).as_bytes()))?,
+        );
+    }
+    let updated = apply_refresh_deltas(&old, &additive_deltas, dealings)?;
+
+    from_portable_to_cggmp(template_share, &updated, None, None)
+}
+
 /// 批量更新 cggmp24 密钥分片 (Batch Update)
 /// **功能**: 根据一组新的 PortableKeyShare (Shamir 分片)，重构多项式，计算全局参数 (VSS Commitments, Public Shares)，
 /// 并更新所有的 cggmp24 KeyShare。
+///
+/// **注意**: 需要所有人的私钥分片 (Trusted Dealer 模式)。去中心化场景请使用
+/// `update_cggmp_share_from_refresh_change`。
+///
+/// **索引映射**: 模板的 `core.i` 是 cggmp24 中的位置，对应 `committee` 中第 `core.i` 个 party id。
 pub fn update_cggmp_shares_from_portable<E, L>(
     old_shares_templates: &[CggmpKeyShare<E, L>],
     refreshed_data: &[PortableKeyShare],
+    committee: &EvaluationPoints,
 ) -> Result<Vec<CggmpKeyShare<E, L>>>
 where
     E: cggmp24::generic_ec::Curve,
     L: SecurityLevel,
 {
     let (new_commitments_hex, new_public_shares_hex) =
-        reconstruct_global_params::<E>(refreshed_data)?;
+        reconstruct_global_params::<E>(refreshed_data, committee)?;
+    let party_ids = committee.ids();
 
     let mut updated_cggmp_shares = Vec::new();
     // Create a map for refreshed data to match by ID
@@ -237,7 +474,9 @@ where
         refreshed_data.iter().map(|s| (s.i, s)).collect();
 
     for template_share in old_shares_templates {
-        let party_id = template_share.core.i;
+        let party_id = *party_ids
+            .get(template_share.core.i as usize)
+            .with_context(|| format!("cggmp24 位置 {} 不在委员会中", template_share.core.i))?;
         if let Some(refreshed) = refreshed_map.get(&party_id) {
             let updated_share = from_portable_to_cggmp(
                 template_share,
@@ -1,18 +1,356 @@
+use std::collections::BTreeMap;
+
 // ============================================================================
 // 1. 数据结构定义 (Data Structures)
 // ============================================================================
 
+/// 当前 `PortableKeyShare` 的格式版本
+///
+/// - v0: 旧格式 `{i, t, n, x_hex, y_hex}`，没有 `version` 字段 (反序列化时自动迁移)。
+/// - v1: 增加 `curve` / `index_base` / `kind` / `origin`，以及可选的 `public_shares` / `vss_commitments`。
+/// - v2: 增加可选的 `evaluation_points` (party id -> 求值点)，支持不连续的 party id。
+pub const PORTABLE_KEY_SHARE_VERSION: u32 = 2;
+
+/// 椭圆曲线标识
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Curve {
+    Secp256k1,
+}
+
+/// 参与方索引 `i` 与多项式求值点 x 之间的约定
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IndexBase {
+    /// `i` 从 0 开始，求值点 x = i + 1 (cggmp24 / Synedrion 的约定)
+    Zero,
+    /// `i` 从 1 开始，`i` 本身就是求值点 x
+    One,
+}
+
+impl IndexBase {
+    /// 求值点相对于 `i` 的偏移量
+    pub fn offset(self) -> u64 {
+        match self {
+            IndexBase::Zero => 1,
+            IndexBase::One => 0,
+        }
+    }
+}
+
+/// 参与方 id -> 多项式求值点 x 的映射 (Evaluation Points)
+///
+/// 委员会轮换后 party id 可能不连续 (例如 {0, 2, 3, 7})，求值点也不能再由 n 推出。
+/// 需要求值点的桥接 / 模拟函数都显式接收这个映射。构造时保证求值点非零且互不相同。
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(try_from = "BTreeMap<u16, u64>", into = "BTreeMap<u16, u64>")]
+pub struct EvaluationPoints(BTreeMap<u16, u64>);
+
+impl EvaluationPoints {
+    pub fn new(points: BTreeMap<u16, u64>) -> Result<Self, String> {
+        let mut seen = std::collections::BTreeSet::new();
+        for (&id, &x) in &points {
+            if x == 0 {
+                return Err(format!("party {} has evaluation point 0", id));
+            }
+            if !seen.insert(x) {
+                return Err(format!("duplicate evaluation point {}", x));
+            }
+        }
+        Ok(Self(points))
+    }
+
+    /// 按索引约定 (x = id + offset) 生成映射
+    ///
+    /// 与 [`EvaluationPoints::new`] 相同地检查求值点: `IndexBase::One` 下 party 0 的求值点为 0，报错。
+    pub fn from_ids(
+        ids: impl IntoIterator<Item = u16>,
+        index_base: IndexBase,
+    ) -> Result<Self, String> {
+        Self::new(
+            ids.into_iter()
+                .map(|id| (id, id as u64 + index_base.offset()))
+                .collect(),
+        )
+    }
+
+    /// 连续的 party id 0..n (cggmp24 / Synedrion DKG 的默认委员会)
+    pub fn contiguous(n: u16) -> Self {
+        Self::from_ids(0..n, IndexBase::Zero).expect("x = id + 1 is nonzero and unique")
+    }
+
+    /// 参与方 `id` 的求值点
+    pub fn point(&self, id: u16) -> anyhow::Result<u64> {
+        self.0
+            .get(&id)
+            .copied()
+            .ok_or_else(|| anyhow::anyhow!("Party {} 没有求值点", id))
+    }
+
+    /// 求值点 `x` 对应的参与方
+    pub fn party_of(&self, x: u64) -> Option<u16> {
+        self.0.iter().find(|(_, &p)| p == x).map(|(&id, _)| id)
+    }
+
+    /// 只保留 `ids` 中的参与方 (例如签名者集合)
+    pub fn subset(&self, ids: &[u16]) -> anyhow::Result<Self> {
+        ids.iter()
+            .map(|&id| Ok((id, self.point(id)?)))
+            .collect::<anyhow::Result<BTreeMap<_, _>>>()
+            .map(Self)
+    }
+
+    /// 所有 party id (升序)
+    pub fn ids(&self) -> Vec<u16> {
+        self.0.keys().copied().collect()
+    }
+
+    /// 所有求值点 (按 party id 升序)
+    pub fn points(&self) -> Vec<u64> {
+        self.0.values().copied().collect()
+    }
+
+    pub fn contains(&self, id: u16) -> bool {
+        self.0.contains_key(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u16, u64)> + '_ {
+        self.0.iter().map(|(&id, &x)| (id, x))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl TryFrom<BTreeMap<u16, u64>> for EvaluationPoints {
+    type Error = String;
+
+    fn try_from(points: BTreeMap<u16, u64>) -> Result<Self, Self::Error> {
+        Self::new(points)
+    }
+}
+
+impl From<EvaluationPoints> for BTreeMap<u16, u64> {
+    fn from(points: EvaluationPoints) -> Self {
+        points.0
+    }
+}
+
+/// 分片类型
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ShareKind {
+    /// Shamir (t-of-n) 分片，任意 t 个参与方可以协作
+    Shamir,
+    /// 加法 (n-of-n) 分片，只对生成它时的签名者集合有效
+    Additive { signers: Vec<u16> },
+}
+
+/// 分片的来源库
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Origin {
+    Cggmp24,
+    Synedrion,
+    /// 由本库的 Resharing / Lagrange 转换产生
+    Bridge,
+    /// 从 v0 旧格式迁移而来，来源未知
+    Unknown,
+}
+
+/// 单个参与方的公钥分片 x_i·G
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PublicShare {
+    pub i: u16,            // 节点索引 (与 PortableKeyShare::i 的约定相同)
+    pub point_hex: String, // 公钥分片 (Point, compressed hex)
+}
+
 /// 便携式密钥分片 (PortableKeyShare)
 ///
 /// 这是一个中间格式，完全由 String (Hex) 组成，用于在不同 MPC 库之间传输数据，
 /// 或者保存到磁盘。它剥离了具体的 Rust 类型依赖。
-#[derive(serde::Serialize, serde::Deserialize, Debug)]
+///
+/// 反序列化时会检查 `version`：旧的 v0 数据自动迁移，高于当前版本的数据直接拒绝。
+///
+/// `public_shares` 与 `vss_commitments` 都是**公开数据**。携带它们时，接收方只需要本方的
+/// `x_hex` 就能重建完整的 cggmp24 `KeyShare`，不必接触其他参与方的私钥分片。
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(try_from = "PortableKeyShareRepr")]
 pub struct PortableKeyShare {
-    pub i: u16,        // 节点索引
-    pub t: u16,        // 阈值
-    pub n: u16,        // 总人数
-    pub x_hex: String, // 私钥分片 (Scalar)
-    pub y_hex: String, // 总公钥 (Point, compressed hex)
+    pub version: u32,           // 格式版本
+    pub curve: Curve,           // 曲线
+    pub index_base: IndexBase,  // 索引约定
+    pub kind: ShareKind,        // Shamir / Additive
+    pub origin: Origin,         // 来源库
+    pub i: u16,                 // 节点索引
+    pub t: u16,                 // 阈值
+    pub n: u16,                 // 总人数
+    pub x_hex: String,          // 私钥分片 (Scalar)
+    pub y_hex: String,          // 总公钥 (Point, compressed hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_shares: Option<Vec<PublicShare>>, // 所有参与方的公钥分片
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vss_commitments: Option<Vec<String>>, // Feldman Commitments a_k·G (compressed hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evaluation_points: Option<EvaluationPoints>, // 委员会的求值点，缺省时 x = i + offset
+}
+
+impl PortableKeyShare {
+    /// 构造一个当前版本的 secp256k1 Shamir 分片 (0-based 索引)
+    pub fn new(origin: Origin, i: u16, t: u16, n: u16, x_hex: String, y_hex: String) -> Self {
+        Self {
+            version: PORTABLE_KEY_SHARE_VERSION,
+            curve: Curve::Secp256k1,
+            index_base: IndexBase::Zero,
+            kind: ShareKind::Shamir,
+            origin,
+            i,
+            t,
+            n,
+            x_hex,
+            y_hex,
+            public_shares: None,
+            vss_commitments: None,
+            evaluation_points: None,
+        }
+    }
+
+    /// 本分片在多项式上的求值点 x
+    pub fn evaluation_point(&self) -> anyhow::Result<u64> {
+        self.point_of(self.i)
+    }
+
+    /// 参与方 `id` 的求值点
+    ///
+    /// 携带 `evaluation_points` 时只从映射中查找，`id` 不在委员会中直接报错；
+    /// 否则按 `index_base` 推导 (x = id + offset)，推导出 0 时报错。
+    pub fn point_of(&self, id: u16) -> anyhow::Result<u64> {
+        if let Some(points) = &self.evaluation_points {
+            return points.point(id);
+        }
+        match id as u64 + self.index_base.offset() {
+            0 => Err(anyhow::anyhow!(
+                "Party {} 的求值点为 0 ({:?})",
+                id,
+                self.index_base
+            )),
+            x => Ok(x),
+        }
+    }
+
+    /// 将求值点 x 映射回参与方索引
+    ///
+    /// 携带 `evaluation_points` 时只从映射中查找；否则按 `index_base` 反推，结果越界时报错。
+    pub fn party_index(&self, evaluation_point: u64) -> anyhow::Result<u16> {
+        if let Some(points) = &self.evaluation_points {
+            return points
+                .party_of(evaluation_point)
+                .ok_or_else(|| anyhow::anyhow!("求值点 {} 不属于任何参与方", evaluation_point));
+        }
+        evaluation_point
+            .checked_sub(self.index_base.offset())
+            .filter(|_| evaluation_point != 0)
+            .and_then(|id| u16::try_from(id).ok())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "求值点 {} 无法按 {:?} 约定映射到参与方索引",
+                    evaluation_point,
+                    self.index_base
+                )
+            })
+    }
+
+    /// 本分片所属委员会的求值点映射
+    ///
+    /// 依次使用 `evaluation_points`、`public_shares` 中的 party id，最后按 `index_base` 退化为
+    /// 0..n (`Zero`) 或 1..=n (`One`)。推导出的求值点为 0 或重复时报错。
+    pub fn committee_points(&self) -> anyhow::Result<EvaluationPoints> {
+        if let Some(points) = &self.evaluation_points {
+            return Ok(points.clone());
+        }
+        match (&self.public_shares, self.index_base) {
+            (Some(list), base) => EvaluationPoints::from_ids(list.iter().map(|ps| ps.i), base),
+            (None, IndexBase::Zero) => EvaluationPoints::from_ids(0..self.n, IndexBase::Zero),
+            (None, IndexBase::One) => EvaluationPoints::from_ids(1..=self.n, IndexBase::One),
+        }
+        .map_err(|e| anyhow::anyhow!("Party {} 的委员会求值点无效: {}", self.i, e))
+    }
+
+    pub fn is_shamir(&self) -> bool {
+        self.kind == ShareKind::Shamir
+    }
+}
+
+/// `PortableKeyShare` 的线上格式，兼容所有历史版本
+#[derive(serde::Deserialize)]
+struct PortableKeyShareRepr {
+    #[serde(default)]
+    version: u32,
+    curve: Option<Curve>,
+    index_base: Option<IndexBase>,
+    kind: Option<ShareKind>,
+    origin: Option<Origin>,
+    i: u16,
+    t: u16,
+    n: u16,
+    x_hex: String,
+    y_hex: String,
+    #[serde(default)]
+    public_shares: Option<Vec<PublicShare>>,
+    #[serde(default)]
+    vss_commitments: Option<Vec<String>>,
+    #[serde(default)]
+    evaluation_points: Option<EvaluationPoints>,
+}
+
+impl TryFrom<PortableKeyShareRepr> for PortableKeyShare {
+    type Error = String;
+
+    fn try_from(repr: PortableKeyShareRepr) -> Result<Self, Self::Error> {
+        match repr.version {
+            // v0: 旧格式只在 cggmp24 <-> Synedrion 之间传递 Shamir 分片，索引均为 0-based
+            0 => Ok(Self {
+                version: PORTABLE_KEY_SHARE_VERSION,
+                curve: Curve::Secp256k1,
+                index_base: IndexBase::Zero,
+                kind: ShareKind::Shamir,
+                origin: Origin::Unknown,
+                i: repr.i,
+                t: repr.t,
+                n: repr.n,
+                x_hex: repr.x_hex,
+                y_hex: repr.y_hex,
+                public_shares: None,
+                vss_commitments: None,
+                evaluation_points: None,
+            }),
+            // v1: 没有 evaluation_points，求值点由 index_base 推导
+            1..=PORTABLE_KEY_SHARE_VERSION => Ok(Self {
+                version: PORTABLE_KEY_SHARE_VERSION,
+                curve: repr.curve.ok_or("missing field `curve`")?,
+                index_base: repr.index_base.ok_or("missing field `index_base`")?,
+                kind: repr.kind.ok_or("missing field `kind`")?,
+                origin: repr.origin.ok_or("missing field `origin`")?,
+                i: repr.i,
+                t: repr.t,
+                n: repr.n,
+                x_hex: repr.x_hex,
+                y_hex: repr.y_hex,
+                public_shares: repr.public_shares,
+                vss_commitments: repr.vss_commitments,
+                evaluation_points: repr.evaluation_points,
+            }),
+            v => Err(format!(
+                "unsupported PortableKeyShare version {} (max supported: {})",
+                v, PORTABLE_KEY_SHARE_VERSION
+            )),
+        }
+    }
 }
 
 // ============================================================================
@@ -38,3 +376,143 @@ pub fn pad_hex(s: String) -> String {
         s
     }
 }
+
+/// `#[serde(with = "hex_bytes")]`: 把 `Vec<u8>` 序列化为不带 `0x` 的 hex 字符串
+pub mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(super::strip_0x(&s)).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X_HEX: &str = "0a";
+    const Y_HEX: &str = "02aa";
+
+    #[test]
+    fn v0_share_migrates_to_current_version() {
+        let json = format!(
+            r#"{{"i": 2, "t": 3, "n": 5, "x_hex": "{}", "y_hex": "{}"}}"#,
+            X_HEX, Y_HEX
+        );
+        let share: PortableKeyShare = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(share.version, PORTABLE_KEY_SHARE_VERSION);
+        assert_eq!(share.curve, Curve::Secp256k1);
+        assert_eq!(share.index_base, IndexBase::Zero);
+        assert_eq!(share.kind, ShareKind::Shamir);
+        assert_eq!(share.origin, Origin::Unknown);
+        assert_eq!((share.i, share.t, share.n), (2, 3, 5));
+        assert!(share.evaluation_points.is_none());
+        assert_eq!(share.evaluation_point().unwrap(), 3);
+
+        // 迁移后再序列化得到当前版本，且能原样读回
+        let round_trip: PortableKeyShare =
+            serde_json::from_value(serde_json::to_value(&share).unwrap()).unwrap();
+        assert_eq!(round_trip.version, PORTABLE_KEY_SHARE_VERSION);
+        assert_eq!(round_trip.i, 2);
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut value = serde_json::to_value(PortableKeyShare::new(
+            Origin::Bridge,
+            0,
+            2,
+            3,
+            X_HEX.to_string(),
+            Y_HEX.to_string(),
+        ))
+        .unwrap();
+        value["version"] = serde_json::json!(PORTABLE_KEY_SHARE_VERSION + 1);
+
+        let err = serde_json::from_value::<PortableKeyShare>(value).unwrap_err();
+        assert!(err.to_string().contains("unsupported PortableKeyShare version"));
+    }
+
+    #[test]
+    fn point_of_requires_listed_party() {
+        let mut share = PortableKeyShare::new(
+            Origin::Bridge,
+            7,
+            2,
+            3,
+            X_HEX.to_string(),
+            Y_HEX.to_string(),
+        );
+        share.evaluation_points = Some(
+            EvaluationPoints::new(BTreeMap::from([(0, 1), (2, 3), (7, 8)])).unwrap(),
+        );
+
+        assert_eq!(share.evaluation_point().unwrap(), 8);
+        assert_eq!(share.party_index(3).unwrap(), 2);
+        // 不在委员会中的 party id / 求值点不会退化为 id + offset
+        assert!(share.point_of(1).is_err());
+        assert!(share.party_index(2).is_err());
+    }
+
+    #[test]
+    fn party_index_rejects_out_of_range_points() {
+        let mut share = PortableKeyShare::new(
+            Origin::Bridge,
+            0,
+            2,
+            3,
+            X_HEX.to_string(),
+            Y_HEX.to_string(),
+        );
+        assert_eq!(share.party_index(1).unwrap(), 0);
+        assert!(share.party_index(0).is_err());
+        assert!(share.party_index(u16::MAX as u64 + 2).is_err());
+
+        share.index_base = IndexBase::One;
+        assert_eq!(share.party_index(1).unwrap(), 1);
+        assert!(share.party_index(0).is_err());
+        assert!(share.point_of(0).is_err());
+    }
+
+    #[test]
+    fn from_ids_rejects_zero_point() {
+        assert!(EvaluationPoints::from_ids([0, 1], IndexBase::One).is_err());
+        let points = EvaluationPoints::from_ids([1, 4], IndexBase::One).unwrap();
+        assert_eq!(points.points(), [1, 4]);
+        assert_eq!(EvaluationPoints::contiguous(3).points(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn committee_points_falls_back_to_one_based_ids() {
+        let mut share = PortableKeyShare::new(
+            Origin::Bridge,
+            1,
+            2,
+            3,
+            X_HEX.to_string(),
+            Y_HEX.to_string(),
+        );
+        share.index_base = IndexBase::One;
+
+        let committee = share.committee_points().unwrap();
+        assert_eq!(committee.ids(), [1, 2, 3]);
+        assert_eq!(committee.points(), [1, 2, 3]);
+
+        // public_shares 中出现 party 0 时，One 约定下得到求值点 0，报错
+        share.public_shares = Some(
+            [0, 1, 2]
+                .map(|i| PublicShare {
+                    i,
+                    point_hex: Y_HEX.to_string(),
+                })
+                .to_vec(),
+        );
+        assert!(share.committee_points().is_err());
+    }
+}
@@ -27,7 +27,7 @@
 //! - **方法**: 完备秘密共享 (Verifiable Secret Sharing) 或重共享 (Resharing)。
 //! - **流程**: 每个持有加法分片 w_i 的方，将其视为秘密，生成一个新的多项式 g_i(x) 并分发子分片。
 //!   最终的新 Shamir 分片是所有收到的子分片之和。
-//! - **注意**: 这是一个交互式协议，涉及 O(n^2)的网络通信。
+//! - **注意**: 这是一个交互式协议，涉及 O(n^2)的网络通信。协议实现见 `bridge::resharing`。
 //!
 //! ## 难点
 //! 1. **数学假设不一致**: 不同库对椭圆曲线标量域、参与方索引 (0-based vs 1-based) 的处理可能不同，微小的偏差会导致无法恢复私钥。
@@ -36,61 +36,21 @@
 //!    - `Additive -> Shamir` 必须通过 MPC 协议（如 Resharing）进行，如果简化为“收集所有分片再重新分发”，则破坏了 MPC 的去中心化假设（变成了 Trusted Dealer 模式）。
 //! 3. **状态同步**: 必须确保所有参与方在转换时使用相同的参数（如阈值 t、参与方列表 S），否则计算出的碎片将不匹配。
 
-use super::common::{pad_hex, strip_0x, PortableKeyShare};
+use super::common::{
+    pad_hex, strip_0x, EvaluationPoints, Origin, PortableKeyShare, PublicShare, ShareKind,
+};
+use super::resharing::{ResharingIdentity, ResharingRoles, VssScheme};
 use anyhow::{anyhow, Context, Result};
-use elliptic_curve::{Field, PrimeField};
-use k256::Scalar;
-
-/// 生成重共享多项式 (Generate Resharing Polynomial)
-///
-/// **功能**: 为单个加法分片生成 Shamir 子分片。
-///
-/// **原理**:
-/// 这是 Shamir 秘密共享 (SSS) 的核心步骤。为了将一个秘密 $s$ (这里是加法分片) 分发给 $n$ 个人，
-/// 我们构造一个 $t-1$ 次多项式 $f(x)$，使得 $f(0) = s$。
-///
-/// **流程**:
-/// 1. 解析输入的 Hex 字符串为标量 (Scalar)。
-/// 2. 调用 `math::generate_polynomial_shares` 生成 $n$ 个点的值 $f(1), \dots, f(n)$。
-/// 3. 将结果编码回 Hex 字符串。
-///
-/// **生产环境通信**:
-/// 此函数本身是纯计算，但在协议中，生成的这些子分片需要通过安全通道发送给其他 $n-1$ 个参与方。
-pub fn generate_resharing_polynomial(
-    additive_share_hex: &str,
-    threshold: u16, // min_signers (degree = threshold - 1)
-    n: u16,         // total parties
-) -> Result<Vec<String>> {
-    // 1. Parse secret (additive share)
-    let padded = pad_hex(strip_0x(additive_share_hex).to_string());
-    let bytes = hex::decode(&padded)?;
-
-    let mut s_bytes = k256::FieldBytes::default();
-    // Handle potential size mismatch
-    if bytes.len() > 32 {
-        return Err(anyhow!("Scalar bytes too long"));
-    }
-    let offset = 32 - bytes.len();
-    s_bytes[offset..].copy_from_slice(&bytes);
-
-    let secret = Option::<k256::Scalar>::from(k256::Scalar::from_repr(s_bytes))
-        .context("Invalid scalar")?;
-
-    // 2. Delegate math to math.rs
-    let scalar_shares = crate::math::generate_polynomial_shares(secret, threshold, n);
-
-    // 3. Convert back to Hex
-    let hex_shares = scalar_shares
-        .iter()
-        .map(|s| hex::encode(s.to_bytes()))
-        .collect();
-
-    Ok(hex_shares)
-}
+use elliptic_curve::sec1::ToEncodedPoint;
+use elliptic_curve::PrimeField;
+use k256::{ProjectivePoint, Scalar};
+use rand_core::OsRng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// 执行加法分片到 Shamir 分片的重共享 (Reshare Additive -> Shamir)
 ///
-/// **功能**: 模拟 MPC 协议中的重共享过程。将一组加法分片转换为一组 Shamir 分片。
+/// **功能**: 在本地模拟器中运行分布式重共享协议，将一组加法分片转换为一组 Shamir 分片。
 ///
 /// **原理**:
 /// 假设全局秘密 x = sum w_i (其中 w_i 是各方的加法分片)。
@@ -99,57 +59,102 @@ pub fn generate_resharing_polynomial(
 /// 根据多项式的加法同态性，新的 x_j 是全局秘密 x 的一个有效 Shamir 分片
 ///
 /// **流程**:
-/// 1. **生成 (Generate)**: 每个参与方 i 为其他人生成子分片矩阵
-/// 2. **分发 (Distribute)**: (模拟) 将分片发送给对应的接收方
-/// 3. **聚合 (Aggregate)**: 每个接收方 j 将收到的所有子分片相加，得到新的私钥分片
+/// 每个参与方独立运行 `resharing::reshare_additive_share`，子分片经加密的 P2P 消息传递，
+/// 并用 VSS Commitments 验证。这里用 `round_based::sim::Simulation` 把 n 个参与方放在同一进程中。
+///
+/// **求值点**: `points` 给出每个参与方在新多项式上的求值点，必须恰好覆盖所有加法分片的 party id。
+/// party id 不必连续。
+///
+/// **协议版本**: `scheme` 选择 Feldman (两轮) 或 Pedersen (三轮，承诺信息论隐藏)。
 ///
 /// **生产环境通信**:
-/// **涉及**。这是一个交互式协议。在生产环境中，步骤 2 需要 O(n^2) 的网络通信，
-/// 且必须通过加密通道 (如 TLS) 进行，以防子分片泄露。
+/// **涉及**。生产环境中每个参与方在自己的节点上调用 `resharing::reshare_additive_share`，
+/// 协议需要一轮可靠广播与一轮 O(n^2) 的 P2P 通信。
 pub fn additive_portable_to_shamir_portable(
-    mut additive_shares: Vec<PortableKeyShare>,
+    additive_shares: Vec<PortableKeyShare>,
+    points: &EvaluationPoints,
     threshold: u16,
+    scheme: VssScheme,
 ) -> Result<Vec<PortableKeyShare>> {
-    let n = additive_shares.len() as u16;
-
-    // 必须按索引排序，确保矩阵处理顺序一致 (Party 0, Party 1, ...)
-    additive_shares.sort_by_key(|s| s.i);
-
-    // 1. 每个参与方为其他所有人生成子分片 (Generate Sub-shares)
-    // matrix[i][j] 表示 Party i 发送给 Party j 的分片
-    let mut shares_sent: Vec<Vec<String>> = Vec::with_capacity(n as usize);
-    for i in 0..n as usize {
-        let my_additive_share = &additive_shares[i].x_hex;
-        // 生成多项式 f_i(x) 并计算 f_i(1)...f_i(n)
-        let sub_shares = generate_resharing_polynomial(my_additive_share, threshold, n)?;
-        shares_sent.push(sub_shares);
-    }
-
-    // 2. 每个参与方聚合收到的子分片 (Aggregate Sub-shares)
-    // Party j 的新分片 = sum(matrix[i][j] for i in 0..n)
-    for j in 0..n as usize {
-        let mut sum_scalar = k256::Scalar::ZERO;
-        for i in 0..n as usize {
-            // Party j 接收来自 Party i 的分片
-            let share_hex = &shares_sent[i][j];
-
-            let padded = pad_hex(strip_0x(share_hex).to_string());
-            let bytes = hex::decode(&padded)?;
-
-            let mut s_bytes = k256::FieldBytes::default();
-            let offset = 32 - bytes.len();
-            s_bytes[offset..].copy_from_slice(&bytes);
-
-            let s = Option::<k256::Scalar>::from(k256::Scalar::from_repr(s_bytes))
-                .context("Invalid scalar")?;
-            sum_scalar += s;
-        }
-        // 更新为新的 Shamir 分片
-        additive_shares[j].x_hex = hex::encode(sum_scalar.to_bytes());
-        additive_shares[j].t = threshold;
+    let first = additive_shares.first().context("No key shares provided")?;
+    let y_hex = first.y_hex.clone();
+
+    if let Some(share) = additive_shares.iter().find(|s| s.is_shamir()) {
+        return Err(anyhow!("Party {} 的分片不是加法分片", share.i));
+    }
+    let mut ids: Vec<u16> = additive_shares.iter().map(|s| s.i).collect();
+    ids.sort();
+    if ids != points.ids() {
+        return Err(anyhow!(
+            "加法分片的参与方 {:?} 与求值点映射 {:?} 不一致",
+            ids,
+            points.ids()
+        ));
     }
 
-    Ok(additive_shares)
+    let roles = ResharingRoles::new(ids, points.clone());
+    let dealer_shares = additive_shares.into_iter().map(|s| (s.i, s)).collect();
+    run_resharing_simulation(&roles, dealer_shares, &y_hex, threshold, scheme)
+}
+
+/// 在同一进程中运行 D ∪ R 的重共享协议，返回 Receiver 的新分片 (按 party id 升序)
+fn run_resharing_simulation(
+    roles: &ResharingRoles,
+    mut dealer_shares: BTreeMap<u16, PortableKeyShare>,
+    y_hex: &str,
+    threshold: u16,
+    scheme: VssScheme,
+) -> Result<Vec<PortableKeyShare>> {
+    // 模拟中为每个参与方生成一次性的身份密钥，用于认证 Round 1 的加密公钥
+    let signing_keys: BTreeMap<u16, k256::ecdsa::SigningKey> = roles
+        .parties()
+        .into_iter()
+        .map(|id| (id, k256::ecdsa::SigningKey::random(&mut OsRng)))
+        .collect();
+    let registry: BTreeMap<u16, k256::ecdsa::VerifyingKey> = signing_keys
+        .iter()
+        .map(|(&id, key)| (id, *key.verifying_key()))
+        .collect();
+
+    // 按协议索引顺序添加参与方 (D ∪ R 升序)
+    let mut simulation = round_based::sim::Simulation::empty();
+    for (id, signing_key) in signing_keys {
+        let identity = ResharingIdentity {
+            signing_key,
+            registry: registry.clone(),
+        };
+        let roles = roles.clone();
+        let y_hex = y_hex.to_string();
+        let my_share = dealer_shares.remove(&id);
+        simulation.add_async_party(move |party| async move {
+            let mut rng = OsRng;
+            super::resharing::reshare_to_committee(
+                party,
+                &roles,
+                id,
+                my_share.as_ref(),
+                &y_hex,
+                threshold,
+                scheme,
+                &identity,
+                &mut rng,
+            )
+            .await
+        });
+    }
+    let results = simulation
+        .run()
+        .map_err(|e| anyhow!("Resharing simulation failed: {:?}", e))?;
+
+    // 离开的成员返回 None
+    let mut new_shares = Vec::with_capacity(roles.receivers.len());
+    for result in results {
+        if let Some(share) = result? {
+            new_shares.push(share);
+        }
+    }
+    new_shares.sort_by_key(|s| s.i);
+    Ok(new_shares)
 }
 
 /// 转换 Shamir 分片为加法分片 (Shamir -> Additive)
@@ -163,7 +168,7 @@ pub fn additive_portable_to_shamir_portable(
 /// 这种转换使得后续的 MPC 签名可以通过简单的加法同态来完成
 ///
 /// **流程**:
-/// 1. 确定参与计算的所有节点索引集合 $S$ (`all_indices`)
+/// 1. 确定参与计算的节点集合 $S$ 及其求值点 (`signers`，party id -> x)
 /// 2. 计算当前节点的拉格朗日系数 lambda_i
 /// 3. 将私钥分片 x_i 乘以 lambda_i 得到 w_i
 ///
@@ -172,8 +177,12 @@ pub fn additive_portable_to_shamir_portable(
 /// 每个人就可以独立在本地完成转换，无需交换数据。
 pub fn shamir_portable_to_additive_portable(
     mut share: PortableKeyShare,
-    all_indices: &[u64],
+    signers: &EvaluationPoints,
 ) -> Result<PortableKeyShare> {
+    if !share.is_shamir() {
+        return Err(anyhow!("Party {} 的分片已经是加法分片", share.i));
+    }
+
     // 1. Parse secret (Shamir share)
     let padded = pad_hex(strip_0x(&share.x_hex).to_string());
     let bytes = hex::decode(&padded)?;
@@ -187,18 +196,420 @@ pub fn shamir_portable_to_additive_portable(
     let secret = Option::<Scalar>::from(Scalar::from_repr(s_bytes)).context("Invalid scalar")?;
 
     // 2. Calculate Lagrange Coefficient
-    // Note: cggmp uses 0-based index i, so x = i + 1 for polynomial evaluation
-    let my_idx = share.i as u64 + 1;
-    let lambda = crate::math::calculate_lagrange_coefficient(my_idx, all_indices);
+    // 求值点由调用方显式给出，party id 可以不连续
+    let my_idx = signers.point(share.i)?;
+    let lambda = crate::math::calculate_lagrange_coefficient(my_idx, &signers.points());
 
     // 3. Convert to Additive Share: w_i = x_i * lambda_i
     let additive_secret = secret * lambda;
 
     // 4. Update share
     share.x_hex = hex::encode(additive_secret.to_bytes());
-    
+    share.kind = ShareKind::Additive {
+        signers: signers.ids(),
+    };
+    share.origin = Origin::Bridge;
+    // 旧的公钥分片 / Commitments 对应 Shamir 多项式，对加法分片不再成立
+    share.public_shares = None;
+    share.vss_commitments = None;
+    share.evaluation_points = Some(signers.clone());
+
     // 更新阈值信息：加法分片本质上是 n-of-n，所以阈值等于总人数
+    share.n = signers.len() as u16;
     share.t = share.n;
 
     Ok(share)
 }
+
+/// 检查一组 Shamir 分片属于同一把密钥，返回 (全局公钥 hex, 委员会求值点)
+fn check_shamir_shares(shares: &[PortableKeyShare]) -> Result<(String, EvaluationPoints)> {
+    let first = shares.first().context("No key shares provided")?;
+    let y = parse_k256_point(&first.y_hex)?;
+    let committee = first.committee_points()?;
+
+    for share in shares {
+        if !share.is_shamir() {
+            return Err(anyhow!("Party {} 的分片不是 Shamir 分片", share.i));
+        }
+        if parse_k256_point(&share.y_hex)? != y {
+            return Err(anyhow!("Party {} 的全局公钥与 Party {} 不一致", share.i, first.i));
+        }
+        if share.committee_points()? != committee {
+            return Err(anyhow!("Party {} 的委员会求值点与 Party {} 不一致", share.i, first.i));
+        }
+    }
+    if shares.len() < first.t as usize {
+        return Err(anyhow!("分片不足: 需要 {}, 实际 {}", first.t, shares.len()));
+    }
+
+    Ok((encode_k256_point(&y), committee))
+}
+
+/// 修改门限 (Change Threshold t -> t')
+///
+/// **功能**: 把同一组 n 个参与方的 t-of-n Shamir 分片重共享为 t'-of-n 分片，
+/// 全局公钥 Y (以及以太坊地址) 保持不变。
+///
+/// **原理**:
+/// 1. 所有 n 个参与方对集合 S = {全部参与方} 做 Lagrange 转换，得到加法分片 w_i，sum w_i = x。
+/// 2. 以新的阈值 t' 运行 `additive_portable_to_shamir_portable`，得到 t' - 1 次多项式上的新分片。
+/// 3. 重共享协议内部校验 C_0 == Y，这里再逐一确认输出分片的 `y_hex` 与输入一致。
+///
+/// **前提**: 输入必须是委员会 (`committee_points`) 中全部 n 个分片，且 n >= 旧阈值 t。
+/// t' 可以大于或小于 t，只要 1 <= t' <= n。新分片沿用原来的求值点。
+///
+/// **生产环境通信**:
+/// **涉及**。与 `additive_portable_to_shamir_portable` 相同，旧分片在本地转换为加法分片后
+/// 立即参与重共享，旧分片应在协议成功后销毁。
+pub fn reshare_with_new_threshold(
+    shares: Vec<PortableKeyShare>,
+    new_threshold: u16,
+    scheme: VssScheme,
+) -> Result<Vec<PortableKeyShare>> {
+    let (y_hex, committee) = check_shamir_shares(&shares)?;
+    let n = shares.len() as u16;
+
+    let mut ids: Vec<u16> = shares.iter().map(|s| s.i).collect();
+    ids.sort();
+    if ids != committee.ids() {
+        return Err(anyhow!(
+            "门限变更需要委员会 {:?} 的全部分片，实际 {:?}",
+            committee.ids(),
+            ids
+        ));
+    }
+    if new_threshold == 0 || new_threshold > n {
+        return Err(anyhow!("Invalid threshold {} for {} parties", new_threshold, n));
+    }
+
+    // 1. Shamir -> Additive (S = 全部参与方)
+    let additive_shares = shares
+        .into_iter()
+        .map(|s| shamir_portable_to_additive_portable(s, &committee))
+        .collect::<Result<Vec<_>>>()?;
+
+    // 2. Additive -> Shamir (t')
+    let new_shares =
+        additive_portable_to_shamir_portable(additive_shares, &committee, new_threshold, scheme)?;
+
+    // 3. 全局公钥不变
+    for share in &new_shares {
+        if encode_k256_point(&parse_k256_point(&share.y_hex)?) != y_hex {
+            return Err(anyhow!("Party {} 重共享后全局公钥发生变化", share.i));
+        }
+    }
+
+    Ok(new_shares)
+}
+
+/// 委员会轮换 (Committee Rotation)
+///
+/// **功能**: 把密钥从旧委员会转移到新委员会 `new_committee` (阈值 `new_threshold`)，全局公钥不变。
+/// 新成员可以加入，旧成员可以离开，party id 与求值点都不必连续 (例如 {0, 2, 3, 7})。
+///
+/// **原理**:
+/// 1. `old_shares` 中的持有者构成 Dealer 集合 D (|D| >= t_old)，各自对 D 做 Lagrange 转换得到加法分片。
+///    D 的求值点取自旧委员会的 `committee_points`。
+/// 2. 以 D 为 Dealer、`new_committee` 为 Receiver 运行 `resharing::reshare_to_committee`。
+/// 3. 只有新委员会成员得到分片；离开的成员在协议结束后不持有任何有效分片，
+///    因为新多项式与旧多项式无关，旧分片无法与新分片组合。
+///
+/// **生产环境通信**:
+/// **涉及**。新成员需要在线参与 Round 1 (公布加密公钥) 并接收子分片；
+/// 这里用 `round_based::sim::Simulation` 把 D ∪ R 放在同一进程中。
+pub fn rotate_committee(
+    old_shares: Vec<PortableKeyShare>,
+    new_committee: &EvaluationPoints,
+    new_threshold: u16,
+    scheme: VssScheme,
+) -> Result<Vec<PortableKeyShare>> {
+    let (y_hex, old_committee) = check_shamir_shares(&old_shares)?;
+
+    if new_threshold == 0 || new_threshold as usize > new_committee.len() {
+        return Err(anyhow!(
+            "Invalid threshold {} for {} parties",
+            new_threshold,
+            new_committee.len()
+        ));
+    }
+
+    // 1. 旧委员会: Shamir -> Additive (S = D)
+    let dealer_ids: Vec<u16> = old_shares.iter().map(|s| s.i).collect();
+    let dealer_points = old_committee.subset(&dealer_ids)?;
+    if dealer_points.len() != old_shares.len() {
+        return Err(anyhow!("旧委员会中存在重复的 party id"));
+    }
+    let mut dealer_shares = BTreeMap::new();
+    for share in old_shares {
+        let id = share.i;
+        dealer_shares.insert(id, shamir_portable_to_additive_portable(share, &dealer_points)?);
+    }
+
+    // 2. D -> R 重共享
+    let roles = ResharingRoles::new(dealer_ids, new_committee.clone());
+    run_resharing_simulation(&roles, dealer_shares, &y_hex, new_threshold, scheme)
+}
+
+/// 解析 secp256k1 点 (compressed / uncompressed hex，可带 0x 前缀)
+pub fn parse_k256_point(hex_str: &str) -> Result<ProjectivePoint> {
+    let bytes = hex::decode(pad_hex(strip_0x(hex_str).to_string()))?;
+    let pk = k256::PublicKey::from_sec1_bytes(&bytes)
+        .map_err(|e| anyhow!("Invalid secp256k1 point: {}", e))?;
+    Ok(pk.to_projective())
+}
+
+/// 解析 secp256k1 标量 (big-endian hex，可带 0x 前缀，不足 32 字节时左侧补零)
+pub fn parse_k256_scalar(hex_str: &str) -> Result<Scalar> {
+    let bytes = hex::decode(pad_hex(strip_0x(hex_str).to_string()))?;
+    if bytes.len() > 32 {
+        return Err(anyhow!("Scalar bytes too long"));
+    }
+    let mut s_bytes = k256::FieldBytes::default();
+    let offset = 32 - bytes.len();
+    s_bytes[offset..].copy_from_slice(&bytes);
+    Option::<Scalar>::from(Scalar::from_repr(s_bytes)).context("Invalid scalar")
+}
+
+/// 编码 secp256k1 点为 compressed hex (无 0x 前缀，cggmp24 的格式)
+pub fn encode_k256_point(point: &ProjectivePoint) -> String {
+    hex::encode(point.to_affine().to_encoded_point(true).as_bytes())
+}
+
+/// 推导 Feldman VSS Commitments (Derive VSS Commitments)
+///
+/// **功能**: 用分片中携带的公钥分片，在指数上插值得到 Commitments a_k·G。
+///
+/// **原理**:
+/// 公钥分片 X_j = f(x_j)·G 落在同一个 t-1 次多项式上，取任意 t 个即可唯一确定
+/// F_k = a_k·G (见 `math::interpolate_in_exponent`)。
+///
+/// **生产环境通信**: **不涉及**。公钥分片是 DKG / Refresh 结束时所有人都已知的公开数据。
+pub fn derive_vss_commitments(share: &PortableKeyShare) -> Result<Vec<String>> {
+    let public_shares = share
+        .public_shares
+        .as_ref()
+        .context("PortableKeyShare 未携带 public_shares")?;
+    let t = share.t as usize;
+    if t == 0 || public_shares.len() < t {
+        return Err(anyhow!(
+            "公钥分片不足以插值: 需要 {}, 实际 {}",
+            t,
+            public_shares.len()
+        ));
+    }
+
+    let mut points = Vec::with_capacity(t);
+    for ps in public_shares.iter().take(t) {
+        points.push((share.point_of(ps.i)?, parse_k256_point(&ps.point_hex)?));
+    }
+    let commitments =
+        crate::math::interpolate_in_exponent(&points).context("Duplicate party index")?;
+
+    Ok(commitments.iter().map(encode_k256_point).collect())
+}
+
+/// 从公开数据重建全局参数 (Global Params from Public Data)
+///
+/// **功能**: 只使用本方私钥分片 + 公开的公钥分片 / Commitments，得到 cggmp24 需要的
+/// (VSS Commitments, 按索引排序的 Public Shares)。
+///
+/// **与 `reconstruct_global_params` 的区别**:
+/// 后者需要所有人的私钥分片 (Trusted Dealer 模式)，本函数只需要公开数据。
+///
+/// **校验**:
+/// 1. 所有公钥分片都落在 Commitments 定义的多项式上。
+/// 2. F_0 等于全局公钥 Y。
+/// 3. 本方的 x_i·G 等于自己的公钥分片。
+pub fn global_params_from_public_data(
+    share: &PortableKeyShare,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let mut public_shares = share
+        .public_shares
+        .clone()
+        .context("PortableKeyShare 未携带 public_shares")?;
+    public_shares.sort_by_key(|ps| ps.i);
+
+    let commitments_hex = match &share.vss_commitments {
+        Some(c) if !c.is_empty() => c.clone(),
+        _ => derive_vss_commitments(share)?,
+    };
+    let commitments = commitments_hex
+        .iter()
+        .map(|c| parse_k256_point(c))
+        .collect::<Result<Vec<_>>>()?;
+    let commitments_hex = commitments.iter().map(encode_k256_point).collect();
+
+    // 1. 公钥分片一致性
+    for ps in &public_shares {
+        let x = share.point_of(ps.i)?;
+        if crate::math::evaluate_in_exponent(&commitments, x) != parse_k256_point(&ps.point_hex)? {
+            return Err(anyhow!("Party {} 的公钥分片与 Commitments 不一致", ps.i));
+        }
+    }
+
+    // 2. F_0 == Y
+    if commitments[0] != parse_k256_point(&share.y_hex)? {
+        return Err(anyhow!("Commitments 常数项与全局公钥不一致"));
+    }
+
+    // 3. x_i·G == X_i
+    let x_i = parse_k256_scalar(&share.x_hex)?;
+    if !crate::math::verify_share(share.evaluation_point()?, &x_i, &commitments) {
+        return Err(anyhow!("Party {} 的私钥分片与公钥分片不一致", share.i));
+    }
+
+    let public_shares_hex = public_shares
+        .iter()
+        .map(|ps| parse_k256_point(&ps.point_hex).map(|p| encode_k256_point(&p)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok((commitments_hex, public_shares_hex))
+}
+
+// ============================================================================
+// 刷新增量的重共享 (Refresh Delta Resharing)
+// ============================================================================
+
+/// 一个参与方对自己刷新增量的 Feldman 重共享
+///
+/// Synedrion `KeyRefresh` 输出的增量 d_j 是加法形式 (sum d_j = 0)。直接加到 t-of-n Shamir 分片上，
+/// 新分片不再落在同一个 t-1 次多项式上 (n > t 时 `global_params_from_public_data` 会拒绝)。
+/// 因此每个参与方把 d_j 当作秘密重新做一次 t-of-n Feldman 共享 h_j(x)，h_j(0) = d_j，
+/// 所有人收到的子分片之和就是 0 的一组 Shamir 分片。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RefreshDeltaDealing {
+    /// 分发者的 party id
+    pub dealer: u16,
+    /// 广播: H_{j,k} = h_{j,k}·G (compressed hex)，H_{j,0} = d_j·G
+    pub commitments: Vec<String>,
+    /// P2P: 接收方 party id -> h_j(x) (hex)
+    pub sub_shares: BTreeMap<u16, String>,
+}
+
+/// 分发本方的刷新增量 (Deal Refresh Delta)
+///
+/// **功能**: 由刷新前后的私钥分片得到本方的加法增量 d_i = x_i' - x_i，
+/// 并在委员会的求值点上生成 t-of-n Feldman 子分片。
+///
+/// **参数**:
+/// - `old`: 刷新前的 Shamir 分片，提供阈值 t 与私钥分片 x_i。
+/// - `refreshed_x_hex`: Synedrion 刷新后的私钥分片 x_i + d_i。
+/// - `committee`: 委员会的 party id -> 求值点映射。
+///
+/// **生产环境通信**:
+/// **涉及**。`commitments` 需要可靠广播，`sub_shares` 必须经加密的 P2P 信道逐个发给接收方
+/// (与 `bridge::resharing` 的 Round 2 相同)。
+pub fn deal_refresh_delta(
+    old: &PortableKeyShare,
+    refreshed_x_hex: &str,
+    committee: &EvaluationPoints,
+) -> Result<RefreshDeltaDealing> {
+    if !old.is_shamir() || old.t == 0 {
+        return Err(anyhow!("Party {} 的分片不是带阈值的 Shamir 分片", old.i));
+    }
+    let delta = parse_k256_scalar(refreshed_x_hex)? - parse_k256_scalar(&old.x_hex)?;
+    let dealing = crate::math::generate_polynomial_shares_at(delta, old.t, &committee.points());
+
+    Ok(RefreshDeltaDealing {
+        dealer: old.i,
+        commitments: dealing.commitments.iter().map(encode_k256_point).collect(),
+        sub_shares: committee
+            .ids()
+            .into_iter()
+            .zip(&dealing.shares)
+            .map(|(id, share)| (id, hex::encode(share.to_bytes())))
+            .collect(),
+    })
+}
+
+/// 应用重共享后的刷新增量 (Apply Refresh Deltas)
+///
+/// **功能**: 只用本方收到的子分片与公开的承诺，把刷新增量换算到 Shamir 域后加到本方分片上:
+/// x_i' = x_i + sum_j h_j(x_i)，X_k' = X_k + sum_j H_j(x_k)。
+///
+/// **校验**:
+/// 1. 委员会中每个参与方恰好提交一份 dealing，承诺数量等于阈值 t。
+/// 2. H_{j,0} 等于刷新协议公布的加法增量 D_j (`additive_deltas`)，把重共享绑定到这次刷新。
+/// 3. sum_j H_{j,0} 为零点，因此全局公钥不变。
+/// 4. 收到的每个子分片都通过 Feldman 校验。
+///
+/// **返回**: 新的 Shamir 分片，携带全部公钥分片；VSS Commitments 留空，由 `global_params_from_public_data` 重新插值。
+pub fn apply_refresh_deltas(
+    old: &PortableKeyShare,
+    additive_deltas: &BTreeMap<u16, ProjectivePoint>,
+    dealings: &[RefreshDeltaDealing],
+) -> Result<PortableKeyShare> {
+    let committee = old.committee_points()?;
+    let old_public_shares = old
+        .public_shares
+        .as_deref()
+        .context("PortableKeyShare 未携带 public_shares")?;
+    let by_dealer: BTreeMap<u16, &RefreshDeltaDealing> =
+        dealings.iter().map(|d| (d.dealer, d)).collect();
+    if by_dealer.len() != dealings.len() || by_dealer.keys().copied().ne(committee.ids()) {
+        return Err(anyhow!(
+            "刷新增量的分发者 {:?} 与委员会 {:?} 不一致",
+            dealings.iter().map(|d| d.dealer).collect::<Vec<_>>(),
+            committee.ids()
+        ));
+    }
+
+    let my_x = old.evaluation_point()?;
+    let mut my_delta = Scalar::ZERO;
+    let mut total = vec![ProjectivePoint::IDENTITY; old.t as usize];
+    for (&j, dealing) in &by_dealer {
+        let commitments = dealing
+            .commitments
+            .iter()
+            .map(|c| parse_k256_point(c))
+            .collect::<Result<Vec<_>>>()?;
+        if commitments.len() != old.t as usize {
+            return Err(anyhow!(
+                "Party {} 的承诺数量 {} 与阈值 {} 不一致",
+                j,
+                commitments.len(),
+                old.t
+            ));
+        }
+        let additive = additive_deltas
+            .get(&j)
+            .with_context(|| format!("Missing refresh delta for party {}", j))?;
+        if commitments[0] != *additive {
+            return Err(anyhow!("Party {} 的重共享常数项与刷新增量不一致", j));
+        }
+        let sub_share = dealing
+            .sub_shares
+            .get(&old.i)
+            .with_context(|| format!("Party {} 没有发给 Party {} 的子分片", j, old.i))?;
+        let sub_share = parse_k256_scalar(sub_share)?;
+        if !crate::math::verify_share(my_x, &sub_share, &commitments) {
+            return Err(anyhow!(
+                "Party {} 发给 Party {} 的子分片未通过 Feldman 校验",
+                j,
+                old.i
+            ));
+        }
+        my_delta += sub_share;
+        for (acc, c) in total.iter_mut().zip(&commitments) {
+            *acc += c;
+        }
+    }
+    if total[0] != ProjectivePoint::IDENTITY {
+        return Err(anyhow!("刷新增量之和不为零，全局公钥会改变"));
+    }
+
+    let mut public_shares = Vec::with_capacity(old_public_shares.len());
+    for ps in old_public_shares {
+        let delta = crate::math::evaluate_in_exponent(&total, old.point_of(ps.i)?);
+        public_shares.push(PublicShare {
+            i: ps.i,
+            point_hex: encode_k256_point(&(parse_k256_point(&ps.point_hex)? + delta)),
+        });
+    }
+
+    let mut updated = old.clone();
+    updated.x_hex = hex::encode((parse_k256_scalar(&old.x_hex)? + my_delta).to_bytes());
+    updated.public_shares = Some(public_shares);
+    updated.vss_commitments = None;
+    Ok(updated)
+}
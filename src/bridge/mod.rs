@@ -1,9 +1,21 @@
+//! 数据转换层 (Bridge)
+//!
+//! 所有库之间的转换都经过 [`PortableKeyShare`] 这一中间格式：
+//! `cggmp24::KeyShare` <-> `PortableKeyShare` <-> `synedrion::KeyShare`。
+//!
+//! `common`、`core` 与 `resharing` 始终可用；`cggmp` / `synedrion` 适配器分别由同名 feature 控制。
+
 pub mod common;
+#[cfg(feature = "cggmp24")]
 pub mod cggmp;
+#[cfg(feature = "synedrion")]
 pub mod synedrion;
 pub mod core; 
+pub mod resharing;
 
 pub use common::*;
+#[cfg(feature = "cggmp24")]
 pub use cggmp::*;
+#[cfg(feature = "synedrion")]
 pub use synedrion::*;
 pub use core::*;
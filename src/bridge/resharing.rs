@@ -0,0 +1,615 @@
+//! # 分布式重共享协议 (Distributed Additive -> Shamir Resharing)
+//!
+//! 基于 `round_based` 的交互协议 (Feldman 两轮 / Pedersen 三轮)，每个参与方只持有自己的加法分片 w_i，
+//! 协议结束后得到全局秘密 x = sum w_i 的 Shamir (t-of-n) 分片，任何一方都看不到其他人的秘密。
+//!
+//! ## 协议流程 (Protocol)
+//!
+//! 1. **本地**: P_i 以 w_i 为常数项生成 t-1 次多项式，在每个接收方的求值点上得到子分片 g_i(x_j)
+//!    与 Feldman Commitments C_ik = a_ik·G；同时生成一次性 ECDH 密钥 E_i。
+//! 2. **Round 1 (可靠广播)**: 广播 (C_i, E_i) 及本方身份私钥对它们的签名，接收方用登记表中的身份公钥验证。
+//! 3. **Round 2 (P2P)**: 用 ECDH(e_i, E_j) 派生的 ChaCha20-Poly1305 密钥加密 g_i(x_j) 并发送给 P_j。
+//! 4. **本地**: P_j 解密每个子分片并用 `math::verify_share` 验证 g_i(x_j)·G == sum_k C_ik·x_j^k，
+//!    然后聚合 x_j' = sum_i g_i(x_j)，C_k = sum_i C_ik。
+//!    最后校验 C_0 == Y，即所有加法分片之和确实是原来的全局私钥。
+//!
+//! ## VSS 方案 (VSS Scheme)
+//!
+//! - [`VssScheme::Feldman`]: C_ik = a_ik·G。承诺直接给出新的公钥分片，但会公开每个子多项式的 a_i0·G = w_i·G。
+//! - [`VssScheme::Pedersen`]: C_ik = a_ik·G + b_ik·H，信息论隐藏，不泄露 w_i·G。
+//!   由于无法从承诺推出公钥分片，协议多一轮 **Round 3 (广播)**: 每个参与方公开 x_j'·G，
+//!   所有人在指数上插值并校验这些公钥分片落在同一个 t-1 次多项式上且常数项为 Y。
+//!   Feldman 模式直接由聚合后的承诺得到公钥分片，没有 Round 3。
+//!
+//! ## 委员会轮换 (Committee Rotation)
+//!
+//! [`reshare_to_committee`] 是更一般的形式: 旧委员会中的 Dealer 集合 D (|D| >= t_old) 先在本地
+//! 对 D 做 Lagrange 转换得到加法分片，再把子分片只发给新委员会 R 的成员。
+//! D 与 R 可以部分重叠 (留任成员)，新加入的成员只接收，离开的成员只分发，之后不再持有任何分片。
+//! [`reshare_additive_share`] 就是 D = R 的特例。Receiver 的求值点由 [`EvaluationPoints`] 显式给出，不要求连续。
+//!
+//! ## 安全性 (Security)
+//! - 子分片只以密文形式离开本机。E_i 经身份密钥签名 ([`ResharingIdentity`])，中继无法替换成自己的公钥，
+//!   因此中继 / 窃听者无法得到任何子分片。
+//! - VSS 验证能发现发送了不一致子分片的参与方，错误信息中会指明作恶方。
+
+use super::common::{EvaluationPoints, Origin, PortableKeyShare, PublicShare};
+use super::core::{encode_k256_point, parse_k256_point, parse_k256_scalar};
+use anyhow::{anyhow, Context, Result};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use elliptic_curve::sec1::ToEncodedPoint;
+use elliptic_curve::{Field, PrimeField};
+use k256::ecdsa::signature::{Signer, Verifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use k256::{ProjectivePoint, Scalar};
+use rand_core::{CryptoRng, RngCore};
+use round_based::{Mpc, MpcExecution, ProtocolMessage};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 重共享协议使用的 VSS 方案
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VssScheme {
+    /// Feldman VSS: 两轮，承诺公开 a_k·G
+    #[default]
+    Feldman,
+    /// Pedersen VSS: 三轮，承诺信息论隐藏
+    Pedersen,
+}
+
+/// 协议消息
+#[derive(Clone, Debug, PartialEq, ProtocolMessage, Serialize, Deserialize)]
+pub enum Msg {
+    Commit(MsgCommit),
+    SubShare(MsgSubShare),
+    PublicShare(MsgPublicShare),
+}
+
+/// Round 1: VSS Commitments 与一次性加密公钥
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MsgCommit {
+    pub scheme: VssScheme,        // 所有参与方必须一致
+    pub commitments: Vec<String>, // C_ik (compressed hex)，非 Dealer 为空
+    pub enc_key: String,          // E_i = e_i·G (compressed hex)
+    pub signature: String,        // 身份私钥对以上字段的 ECDSA 签名 (r || s hex)
+}
+
+/// Round 2: Dealer -> Receiver 的加密子分片，其余方向为空 (round_based 的 P2P 轮要求两两都发消息)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MsgSubShare {
+    pub sealed: Option<SealedSubShare>,
+}
+
+/// 加密后的子分片 g_i(x_j) (Pedersen 模式下还包含盲化分片 r_i(x_j))
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SealedSubShare {
+    pub nonce: String,      // 12 字节 nonce (hex)
+    pub ciphertext: String, // ChaCha20-Poly1305 密文 (hex)
+}
+
+/// Round 3 (仅 Pedersen): Receiver 公布新的公钥分片 x_j'·G，其余参与方为空
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MsgPublicShare {
+    pub point: Option<String>, // compressed hex
+}
+
+/// 重共享的参与方角色 (Resharing Roles)
+///
+/// 协议参与方是 `dealers ∪ receivers`，按 party id 升序排列，round_based 的协议索引即其位置。
+/// Dealer 持有旧委员会 (对 `dealers` 做过 Lagrange 转换) 的加法分片；
+/// Receiver 是新委员会成员，协议结束后得到新的 Shamir 分片，其求值点由 `receivers` 给出。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResharingRoles {
+    pub dealers: BTreeSet<u16>,
+    pub receivers: EvaluationPoints,
+}
+
+impl ResharingRoles {
+    pub fn new(dealers: impl IntoIterator<Item = u16>, receivers: EvaluationPoints) -> Self {
+        Self {
+            dealers: dealers.into_iter().collect(),
+            receivers,
+        }
+    }
+
+    /// 所有协议参与方 (升序)
+    pub fn parties(&self) -> Vec<u16> {
+        let mut parties: BTreeSet<u16> = self.dealers.clone();
+        parties.extend(self.receivers.ids());
+        parties.into_iter().collect()
+    }
+
+    /// party id 在 round_based 协议中的索引
+    pub fn protocol_index(&self, id: u16) -> Option<u16> {
+        self.parties().iter().position(|&p| p == id).map(|k| k as u16)
+    }
+}
+
+/// 参与方身份 (Party Identity)
+///
+/// Round 1 的一次性加密公钥 E_i 用 `signing_key` 签名，其他参与方用 `registry` 中登记的身份公钥验证。
+/// 否则中继可以把 E_i 换成自己的公钥，解密发给 P_i 的子分片后再重新加密转发 (中间人)。
+#[derive(Clone)]
+pub struct ResharingIdentity {
+    pub signing_key: SigningKey,
+    /// 所有协议参与方的身份公钥 (party id -> 公钥)
+    pub registry: BTreeMap<u16, VerifyingKey>,
+}
+
+const KDF_DOMAIN: &[u8] = b"my-mpc-bridge/resharing/v1";
+const COMMIT_DOMAIN: &[u8] = b"my-mpc-bridge/resharing/commit/v1";
+
+/// Round 1 签名覆盖的内容: 发送方、全局公钥、VSS 方案、Commitments 与 E_i
+fn commit_message(sender: u16, y_hex: &str, commit: &MsgCommit) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(COMMIT_DOMAIN);
+    hasher.update(sender.to_be_bytes());
+    hasher.update((y_hex.len() as u32).to_be_bytes());
+    hasher.update(y_hex.as_bytes());
+    hasher.update([commit.scheme as u8]);
+    hasher.update((commit.commitments.len() as u32).to_be_bytes());
+    for c in &commit.commitments {
+        hasher.update((c.len() as u32).to_be_bytes());
+        hasher.update(c.as_bytes());
+    }
+    hasher.update(commit.enc_key.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// 用登记表中的身份公钥验证 Party `sender` 的 Round 1 消息
+fn verify_commit(
+    registry: &BTreeMap<u16, VerifyingKey>,
+    sender: u16,
+    y_hex: &str,
+    commit: &MsgCommit,
+) -> Result<()> {
+    let key = registry
+        .get(&sender)
+        .with_context(|| format!("Party {} 不在身份登记表中", sender))?;
+    let signature = hex::decode(&commit.signature)
+        .ok()
+        .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        .with_context(|| format!("Party {} 的 Round 1 签名格式错误", sender))?;
+    key.verify(&commit_message(sender, y_hex, commit), &signature)
+        .map_err(|_| anyhow!("Party {} 的加密公钥签名无效", sender))
+}
+
+/// 本方生成的子分片与承诺
+struct Dealing {
+    shares: Vec<Scalar>,
+    blindings: Option<Vec<Scalar>>,
+    commitments: Vec<String>,
+}
+
+impl Dealing {
+    /// 在 Receiver 的求值点 `points` 上生成子分片
+    fn new(scheme: VssScheme, secret_hex: &str, threshold: u16, points: &[u64]) -> Result<Self> {
+        let secret = parse_k256_scalar(secret_hex)?;
+        match scheme {
+            VssScheme::Feldman => {
+                let feldman = crate::math::generate_polynomial_shares_at(secret, threshold, points);
+                Ok(Self {
+                    shares: feldman.shares,
+                    blindings: None,
+                    commitments: feldman.commitments.iter().map(encode_k256_point).collect(),
+                })
+            }
+            VssScheme::Pedersen => {
+                let pedersen = crate::math::generate_pedersen_shares_at(secret, threshold, points);
+                Ok(Self {
+                    shares: pedersen.shares,
+                    blindings: Some(pedersen.blindings),
+                    commitments: pedersen.commitments.iter().map(encode_k256_point).collect(),
+                })
+            }
+        }
+    }
+
+    /// 发给 P_j 的明文: share (32 字节) [|| blinding (32 字节)]
+    fn plaintext_for(&self, j: usize) -> Vec<u8> {
+        let mut bytes = self.shares[j].to_bytes().to_vec();
+        if let Some(blindings) = &self.blindings {
+            bytes.extend_from_slice(&blindings[j].to_bytes());
+        }
+        bytes
+    }
+}
+
+/// 派生 P_sender -> P_receiver 方向的对称密钥
+fn derive_channel_key(shared_point: &ProjectivePoint, sender: u16, receiver: u16) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(KDF_DOMAIN);
+    hasher.update(shared_point.to_affine().to_encoded_point(true).as_bytes());
+    hasher.update(sender.to_be_bytes());
+    hasher.update(receiver.to_be_bytes());
+    hasher.finalize().into()
+}
+
+fn channel_aad(sender: u16, receiver: u16) -> Vec<u8> {
+    let mut aad = KDF_DOMAIN.to_vec();
+    aad.extend_from_slice(&sender.to_be_bytes());
+    aad.extend_from_slice(&receiver.to_be_bytes());
+    aad
+}
+
+fn encrypt_sub_share(
+    rng: &mut (impl RngCore + CryptoRng),
+    key: &[u8; 32],
+    sender: u16,
+    receiver: u16,
+    plaintext: &[u8],
+) -> Result<SealedSubShare> {
+    let mut nonce = [0u8; 12];
+    rng.fill_bytes(&mut nonce);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: &channel_aad(sender, receiver),
+            },
+        )
+        .map_err(|_| anyhow!("Failed to encrypt sub-share"))?;
+    Ok(SealedSubShare {
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+fn decrypt_sub_share(
+    key: &[u8; 32],
+    sender: u16,
+    receiver: u16,
+    msg: &SealedSubShare,
+) -> Result<Vec<u8>> {
+    let nonce = hex::decode(&msg.nonce)?;
+    if nonce.len() != 12 {
+        return Err(anyhow!("Invalid nonce length from party {}", sender));
+    }
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let plaintext = cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &hex::decode(&msg.ciphertext)?,
+                aad: &channel_aad(sender, receiver),
+            },
+        )
+        .map_err(|_| anyhow!("Failed to decrypt sub-share from party {}", sender))?;
+    Ok(plaintext)
+}
+
+/// 解析解密后的 32 字节标量 (big-endian)，拒绝长度错误与 >= q 的非规范编码
+fn scalar_from_bytes(bytes: &[u8]) -> Result<Scalar> {
+    let repr: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| anyhow!("Scalar must be 32 bytes, got {}", bytes.len()))?;
+    Option::<Scalar>::from(Scalar::from_repr(repr.into())).context("Non-canonical scalar")
+}
+
+/// 单个参与方执行重共享协议 (Run Resharing as Party i)
+///
+/// **功能**: P_i 以自己的加法分片 `my_share` 参与协议，得到新的 Shamir 分片。
+///
+/// **参数**:
+/// - `points`: 所有参与方 (即加法分片持有者) 的 party id -> 新求值点，必须包含 `my_share.i`。
+/// - `threshold`: 新 Shamir 分片的阈值 t。
+/// - `scheme`: VSS 方案，所有参与方必须一致。
+/// - `identity`: 本方身份私钥与所有参与方的身份公钥，用于认证 Round 1 的加密公钥。
+///
+/// **返回**: 携带新公钥分片与 VSS Commitments 的 `PortableKeyShare` (ShareKind::Shamir)。
+/// `vss_commitments` 始终是 Feldman 形式 (a_k·G)，可直接用于 cggmp24。
+pub async fn reshare_additive_share<M>(
+    party: M,
+    points: &EvaluationPoints,
+    my_share: &PortableKeyShare,
+    threshold: u16,
+    scheme: VssScheme,
+    identity: &ResharingIdentity,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<PortableKeyShare>
+where
+    M: Mpc<Msg = Msg>,
+{
+    let i = my_share.i;
+    let roles = ResharingRoles::new(points.ids(), points.clone());
+
+    reshare_to_committee(
+        party,
+        &roles,
+        i,
+        Some(my_share),
+        &my_share.y_hex,
+        threshold,
+        scheme,
+        identity,
+        rng,
+    )
+    .await?
+    .ok_or_else(|| anyhow!("Party {} 没有得到新分片", i))
+}
+
+/// 单个参与方执行委员会轮换 (Run Committee Resharing as Party `my_id`)
+///
+/// **功能**: Dealer 用自己的加法分片分发子分片，Receiver 聚合得到新委员会的 Shamir 分片。
+///
+/// **参数**:
+/// - `roles`: Dealer / Receiver 集合，所有参与方必须一致。
+/// - `my_id`: 本方的 party id (不是协议索引)。
+/// - `my_share`: Dealer 必须提供对 `roles.dealers` 做过 Lagrange 转换的加法分片，纯 Receiver 传 `None`。
+/// - `y_hex`: 全局公钥，纯 Receiver 用它校验 C_0 == Y。
+/// - `threshold`: 新委员会的阈值 t'，不能超过 Receiver 数量。
+/// - `identity`: 本方身份私钥与所有参与方的身份公钥，用于认证 Round 1 的加密公钥。
+///
+/// **返回**: Receiver 得到 `Some(新分片)`，离开的 Dealer 得到 `None`。
+#[allow(clippy::too_many_arguments)]
+pub async fn reshare_to_committee<M>(
+    party: M,
+    roles: &ResharingRoles,
+    my_id: u16,
+    my_share: Option<&PortableKeyShare>,
+    y_hex: &str,
+    threshold: u16,
+    scheme: VssScheme,
+    identity: &ResharingIdentity,
+    rng: &mut (impl RngCore + CryptoRng),
+) -> Result<Option<PortableKeyShare>>
+where
+    M: Mpc<Msg = Msg>,
+{
+    let parties = roles.parties();
+    let n = parties.len() as u16;
+    let i = roles
+        .protocol_index(my_id)
+        .ok_or_else(|| anyhow!("Party {} 不在本次重共享中", my_id))?;
+    let receivers = roles.receivers.ids();
+    let is_receiver = roles.receivers.contains(my_id);
+    let t = threshold as usize;
+    if threshold == 0 || t > receivers.len() {
+        return Err(anyhow!(
+            "Invalid threshold {} for {} receivers",
+            threshold,
+            receivers.len()
+        ));
+    }
+    let y = parse_k256_point(y_hex)?;
+
+    // 1. Dealer 在每个 Receiver 的求值点上生成子分片与 Commitments
+    let dealing = match (roles.dealers.contains(&my_id), my_share) {
+        (true, Some(share)) => {
+            if share.is_shamir() {
+                return Err(anyhow!("Party {} 的分片不是加法分片", share.i));
+            }
+            if share.i != my_id {
+                return Err(anyhow!("Party index mismatch: {} != {}", share.i, my_id));
+            }
+            Some(Dealing::new(scheme, &share.x_hex, threshold, &roles.receivers.points())?)
+        }
+        (true, None) => return Err(anyhow!("Dealer {} 缺少加法分片", my_id)),
+        (false, _) => None,
+    };
+
+    let enc_secret = Scalar::random(&mut *rng);
+    let enc_key = ProjectivePoint::GENERATOR * enc_secret;
+
+    // 2. 定义协议轮次
+    let mut mpc = party;
+    let round1 = mpc.add_round(round_based::round::reliable_broadcast::<MsgCommit>(i, n));
+    let round2 = mpc.add_round(round_based::round::p2p::<MsgSubShare>(i, n));
+    // Round 3 只在 Pedersen 模式下存在
+    let round3 = (scheme == VssScheme::Pedersen)
+        .then(|| mpc.add_round(round_based::round::reliable_broadcast::<MsgPublicShare>(i, n)));
+    let mut mpc = mpc.finish_setup();
+
+    // 3. Round 1: 广播 Commitments 与加密公钥，并用身份私钥签名
+    let mut my_commit = MsgCommit {
+        scheme,
+        commitments: dealing
+            .as_ref()
+            .map(|d| d.commitments.clone())
+            .unwrap_or_default(),
+        enc_key: encode_k256_point(&enc_key),
+        signature: String::new(),
+    };
+    let signature: Signature = identity
+        .signing_key
+        .sign(&commit_message(my_id, y_hex, &my_commit));
+    my_commit.signature = hex::encode(signature.to_bytes());
+    mpc.reliably_broadcast(my_commit.clone())
+        .await
+        .map_err(|e| anyhow!("Round 1 send failed: {:?}", e))?;
+    let commits = mpc
+        .complete(round1)
+        .await
+        .map_err(|e| anyhow!("Round 1 receive failed: {:?}", e))?
+        .into_vec_including_me(my_commit);
+
+    let mut all_commitments = Vec::with_capacity(n as usize);
+    let mut all_enc_keys = Vec::with_capacity(n as usize);
+    for (k, commit) in commits.iter().enumerate() {
+        let j = parties[k];
+        verify_commit(&identity.registry, j, y_hex, commit)?;
+        if commit.scheme != scheme {
+            return Err(anyhow!(
+                "Party {} 使用了不同的 VSS 方案: {:?} != {:?}",
+                j,
+                commit.scheme,
+                scheme
+            ));
+        }
+        let expected = if roles.dealers.contains(&j) { t } else { 0 };
+        if commit.commitments.len() != expected {
+            return Err(anyhow!(
+                "Party {} 的 Commitments 数量错误: 期望 {}, 实际 {}",
+                j,
+                expected,
+                commit.commitments.len()
+            ));
+        }
+        all_commitments.push(
+            commit
+                .commitments
+                .iter()
+                .map(|c| parse_k256_point(c))
+                .collect::<Result<Vec<_>>>()?,
+        );
+        all_enc_keys.push(parse_k256_point(&commit.enc_key)?);
+    }
+
+    // 4. Round 2: Dealer 加密并发送子分片，其余方向发送空消息
+    for (k, &j) in parties.iter().enumerate() {
+        if k as u16 == i {
+            continue;
+        }
+        let sealed = match (&dealing, receivers.iter().position(|&r| r == j)) {
+            (Some(dealing), Some(position)) => {
+                let key = derive_channel_key(&(all_enc_keys[k] * enc_secret), my_id, j);
+                Some(encrypt_sub_share(
+                    rng,
+                    &key,
+                    my_id,
+                    j,
+                    &dealing.plaintext_for(position),
+                )?)
+            }
+            _ => None,
+        };
+        mpc.send_p2p(k as u16, MsgSubShare { sealed })
+            .await
+            .map_err(|e| anyhow!("Round 2 send to party {} failed: {:?}", j, e))?;
+    }
+    let received = mpc
+        .complete(round2)
+        .await
+        .map_err(|e| anyhow!("Round 2 receive failed: {:?}", e))?;
+
+    // 5. Receiver 解密、验证并聚合
+    let mut new_secret = match (&dealing, receivers.iter().position(|&r| r == my_id)) {
+        (Some(dealing), Some(position)) => dealing.shares[position],
+        _ => Scalar::ZERO,
+    };
+    for (k, _msg_id, msg) in received.into_iter_indexed() {
+        let j = parties[k as usize];
+        let expected = is_receiver && roles.dealers.contains(&j);
+        let sealed = match (msg.sealed, expected) {
+            (Some(sealed), true) => sealed,
+            (None, false) => continue,
+            (None, true) => return Err(anyhow!("Party {} 没有发送子分片", j)),
+            (Some(_), false) => return Err(anyhow!("Party {} 发送了多余的子分片", j)),
+        };
+        let my_x = roles.receivers.point(my_id)?;
+        let key = derive_channel_key(&(all_enc_keys[k as usize] * enc_secret), j, my_id);
+        let plaintext = decrypt_sub_share(&key, j, my_id, &sealed)?;
+        let commitments = &all_commitments[k as usize];
+
+        let (sub_share, valid) = match scheme {
+            VssScheme::Feldman if plaintext.len() == 32 => {
+                let sub_share = scalar_from_bytes(&plaintext)?;
+                (sub_share, crate::math::verify_share(my_x, &sub_share, commitments))
+            }
+            VssScheme::Pedersen if plaintext.len() == 64 => {
+                let sub_share = scalar_from_bytes(&plaintext[..32])?;
+                let blinding = scalar_from_bytes(&plaintext[32..])?;
+                let valid =
+                    crate::math::verify_pedersen_share(my_x, &sub_share, &blinding, commitments);
+                (sub_share, valid)
+            }
+            _ => return Err(anyhow!("Party {} 发送的子分片长度错误", j)),
+        };
+        if !valid {
+            return Err(anyhow!(
+                "Party {} 发送的子分片与其 Commitments 不一致",
+                j
+            ));
+        }
+        new_secret += sub_share;
+    }
+
+    // 6. 得到新的 (Feldman) Commitments 并校验常数项
+    let aggregated = match scheme {
+        VssScheme::Feldman => {
+            let mut aggregated = vec![ProjectivePoint::IDENTITY; t];
+            for commitments in &all_commitments {
+                for (k, c) in commitments.iter().enumerate() {
+                    aggregated[k] += c;
+                }
+            }
+            aggregated
+        }
+        VssScheme::Pedersen => {
+            // Round 3: Receiver 广播新的公钥分片，并在指数上插值
+            let my_public = MsgPublicShare {
+                point: is_receiver
+                    .then(|| encode_k256_point(&(ProjectivePoint::GENERATOR * new_secret))),
+            };
+            mpc.reliably_broadcast(my_public.clone())
+                .await
+                .map_err(|e| anyhow!("Round 3 send failed: {:?}", e))?;
+            let round3 = round3.ok_or_else(|| anyhow!("Round 3 was not declared"))?;
+            let public_msgs = mpc
+                .complete(round3)
+                .await
+                .map_err(|e| anyhow!("Round 3 receive failed: {:?}", e))?
+                .into_vec_including_me(my_public);
+
+            let mut points = Vec::with_capacity(receivers.len());
+            for (k, msg) in public_msgs.iter().enumerate() {
+                let j = parties[k];
+                match (&msg.point, roles.receivers.contains(j)) {
+                    (Some(point), true) => {
+                        points.push((j, roles.receivers.point(j)?, parse_k256_point(point)?))
+                    }
+                    (None, false) => {}
+                    (None, true) => return Err(anyhow!("Party {} 没有公布公钥分片", j)),
+                    (Some(_), false) => return Err(anyhow!("Party {} 不是新委员会成员", j)),
+                }
+            }
+            let interpolation: Vec<_> = points[..t].iter().map(|&(_, x, p)| (x, p)).collect();
+            let aggregated = crate::math::interpolate_in_exponent(&interpolation)
+                .ok_or_else(|| anyhow!("Duplicate evaluation point"))?;
+            for (j, x, point) in &points[t..] {
+                if crate::math::evaluate_in_exponent(&aggregated, *x) != *point {
+                    return Err(anyhow!(
+                        "Party {} 公布的公钥分片不在 t-1 次多项式上",
+                        j
+                    ));
+                }
+            }
+            aggregated
+        }
+    };
+    if aggregated[0] != y {
+        return Err(anyhow!("加法分片之和与全局公钥不一致"));
+    }
+    if !is_receiver {
+        return Ok(None);
+    }
+
+    // 7. 输出新委员会的 Shamir 分片
+    let public_shares = roles
+        .receivers
+        .iter()
+        .map(|(j, x)| PublicShare {
+            i: j,
+            point_hex: encode_k256_point(&crate::math::evaluate_in_exponent(&aggregated, x)),
+        })
+        .collect();
+
+    let mut new_share = PortableKeyShare::new(
+        Origin::Bridge,
+        my_id,
+        threshold,
+        receivers.len() as u16,
+        hex::encode(new_secret.to_bytes()),
+        encode_k256_point(&y),
+    );
+    new_share.evaluation_points = Some(roles.receivers.clone());
+    new_share.public_shares = Some(public_shares);
+    new_share.vss_commitments = Some(aggregated.iter().map(encode_k256_point).collect());
+
+    Ok(Some(new_share))
+}
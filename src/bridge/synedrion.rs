@@ -1,5 +1,5 @@
 use super::common::{
-    ensure_0x, pad_hex, strip_0x, PortableKeyShare,
+    ensure_0x, pad_hex, strip_0x, EvaluationPoints, Origin, PortableKeyShare, PublicShare,
 };
 use anyhow::{anyhow, Context, Result};
 use elliptic_curve::sec1::{EncodedPoint, FromEncodedPoint, ToEncodedPoint};
@@ -9,7 +9,7 @@ use manul::protocol::PartyId;
 use num_bigint::BigInt;
 use num_traits::Num;
 use serde::Serialize;
-use synedrion::{AuxInfo, KeyShare as SynedrionKeyShare};
+use synedrion::{AuxInfo, KeyShare as SynedrionKeyShare, ThresholdKeyShare};
 
 // ============================================================================
 // Synedrion 适配器 (Synedrion Adapters)
@@ -47,7 +47,15 @@ where
     let x_point_json = serde_json::Value::String(ensure_0x(&x_point_hex));
 
     // 构造公钥分片列表 [[id, point_hex], ...]
-    let public_shares_list = serde_json::json!([[portable.i, x_point_json]]);
+    // 如果 portable 携带了所有人的公钥分片则全部导入，否则只包含自己
+    let public_shares_list = match &portable.public_shares {
+        Some(list) => serde_json::Value::Array(
+            list.iter()
+                .map(|ps| serde_json::json!([ps.i, ensure_0x(&ps.point_hex)]))
+                .collect(),
+        ),
+        None => serde_json::json!([[portable.i, x_point_json]]),
+    };
 
     let key_share_json = serde_json::json!({
         "owner": portable.i,
@@ -78,20 +86,175 @@ pub fn from_synedrion_to_portable<P: synedrion::SchemeParams, Id: PartyId + Into
         .to_string();
     let x_hex = pad_hex(raw_x_hex);
 
+    // 2. 提取所有参与方的公钥分片 (Synedrion 协议输出的公开数据)
+    // 没有 `public` 列表的旧数据沿用原来的路径: n = 0，不携带公钥分片，
+    // 之后只能走需要全部私钥分片的 `reconstruct_global_params`。
+    let Some(public_list) = v.get("public").and_then(|l| l.as_array()) else {
+        return Ok(PortableKeyShare::new(
+            Origin::Synedrion,
+            (*share.owner()).into(),
+            0,
+            0,
+            x_hex,
+            y_hex,
+        ));
+    };
+    let mut public_shares = Vec::with_capacity(public_list.len());
+    for item in public_list {
+        let pair = item.as_array().context("Invalid public share entry")?;
+        let id = pair
+            .first()
+            .and_then(|id| id.as_u64())
+            .context("Invalid public share id")?;
+        let point_hex = pair
+            .get(1)
+            .and_then(|p| p.as_str())
+            .context("Invalid public share point")?;
+        let id = u16::try_from(id)
+            .with_context(|| format!("Public share id {} does not fit in u16", id))?;
+        public_shares.push(PublicShare {
+            i: id,
+            point_hex: strip_0x(point_hex).to_string(),
+        });
+    }
+
     // Calculate n from share's public list
-    let n = v
-        .get("public")
-        .and_then(|l| l.as_array())
-        .map(|a| a.len())
-        .unwrap_or(0) as u16;
+    let n = public_shares.len() as u16;
 
-    Ok(PortableKeyShare {
-        i: (*share.owner()).into(),
-        t: 0,
+    let mut portable = PortableKeyShare::new(
+        Origin::Synedrion,
+        (*share.owner()).into(),
+        0,
         n,
         x_hex,
         y_hex,
-    })
+    );
+    portable.public_shares = Some(public_shares);
+
+    Ok(portable)
+}
+
+/// 导入到 Synedrion 门限格式
+///
+/// **功能**: 将 Shamir 分片 (`PortableKeyShare`) 转换为 `synedrion::ThresholdKeyShare`。
+/// 与 `from_portable_to_synedrion` 不同，门限分片保留阈值与每个参与方的求值点 (`share_ids`)，
+/// Synedrion 可以用 `ThresholdKeyShare::to_key_share` 为任意 t 个签名者自行完成 Lagrange 转换，
+/// 不需要针对每个签名者集合重新导入。
+///
+/// **前提**: `portable` 必须是 Shamir 分片，携带阈值 `t` 以及所有人的公钥分片。
+pub fn from_portable_to_synedrion_threshold<P: synedrion::SchemeParams>(
+    portable: &PortableKeyShare,
+) -> Result<ThresholdKeyShare<P, u16>> {
+    if !portable.is_shamir() || portable.t == 0 {
+        return Err(anyhow!(
+            "ThresholdKeyShare 需要带阈值的 Shamir 分片 (Party {}: {:?}, t = {})",
+            portable.i,
+            portable.kind,
+            portable.t
+        ));
+    }
+    let public_shares = portable
+        .public_shares
+        .as_ref()
+        .context("PortableKeyShare 未携带 public_shares")?;
+
+    // share_ids / public_shares 与 KeyShare.public 一样序列化为 [[id, value], ...]
+    let share_ids: Vec<serde_json::Value> = public_shares
+        .iter()
+        .map(|ps| {
+            Ok(serde_json::json!([ps.i, format!("0x{:064x}", portable.point_of(ps.i)?)]))
+        })
+        .collect::<Result<_>>()?;
+    let public_list: Vec<serde_json::Value> = public_shares
+        .iter()
+        .map(|ps| serde_json::json!([ps.i, ensure_0x(&ps.point_hex)]))
+        .collect();
+
+    let threshold_share_json = serde_json::json!({
+        "owner": portable.i,
+        "threshold": portable.t,
+        "secret_share": ensure_0x(&pad_hex(portable.x_hex.clone())),
+        "share_ids": share_ids,
+        "public_shares": public_list
+    });
+
+    serde_json::from_value(threshold_share_json)
+        .context("Failed to deserialize ThresholdKeyShare from JSON")
+}
+
+/// 从 Synedrion 门限格式导出
+///
+/// **功能**: 提取 `synedrion::ThresholdKeyShare` 的私钥分片、阈值、求值点与公钥分片。
+pub fn from_synedrion_threshold_to_portable<P, Id>(
+    share: &ThresholdKeyShare<P, Id>,
+    y_hex: String,
+) -> Result<PortableKeyShare>
+where
+    P: synedrion::SchemeParams,
+    Id: PartyId,
+{
+    let v = serde_json::to_value(share)?;
+    let owner = v
+        .get("owner")
+        .and_then(|id| id.as_u64())
+        .context("Missing owner from ThresholdKeyShare")?;
+    let owner = u16::try_from(owner)
+        .with_context(|| format!("ThresholdKeyShare owner {} does not fit in u16", owner))?;
+    let x_hex = pad_hex(
+        v.get("secret_share")
+            .and_then(|s| s.as_str())
+            .context("Missing secret_share from ThresholdKeyShare")?
+            .trim_start_matches("0x")
+            .to_string(),
+    );
+    let threshold = v
+        .get("threshold")
+        .and_then(|t| t.as_u64())
+        .context("Missing threshold from ThresholdKeyShare")?;
+    let threshold = u16::try_from(threshold)
+        .with_context(|| format!("Threshold {} does not fit in u16", threshold))?;
+
+    let pairs = |field: &str| -> Result<Vec<(u16, String)>> {
+        v.get(field)
+            .and_then(|l| l.as_array())
+            .with_context(|| format!("Missing {} from ThresholdKeyShare", field))?
+            .iter()
+            .map(|item| {
+                let id = item.get(0).and_then(|id| id.as_u64()).context("Invalid id")?;
+                let value = item.get(1).and_then(|p| p.as_str()).context("Invalid value")?;
+                let id = u16::try_from(id)
+                    .with_context(|| format!("{} id {} does not fit in u16", field, id))?;
+                Ok((id, strip_0x(value).to_string()))
+            })
+            .collect()
+    };
+
+    let mut points = std::collections::BTreeMap::new();
+    for (id, x) in pairs("share_ids")? {
+        // 与 secret 一样按大整数解析，兼容全零与任意长度的 hex
+        let x = BigInt::from_str_radix(&x, 16)
+            .with_context(|| format!("Invalid share id for party {}", id))?;
+        let x = u64::try_from(&x)
+            .map_err(|_| anyhow!("Share id {:#x} of party {} does not fit in u64", x, id))?;
+        points.insert(id, x);
+    }
+    let public_shares = pairs("public_shares")?
+        .into_iter()
+        .map(|(i, point_hex)| PublicShare { i, point_hex })
+        .collect::<Vec<_>>();
+
+    let mut portable = PortableKeyShare::new(
+        Origin::Synedrion,
+        owner,
+        threshold,
+        public_shares.len() as u16,
+        x_hex,
+        y_hex,
+    );
+    portable.evaluation_points = Some(EvaluationPoints::new(points).map_err(|e| anyhow!(e))?);
+    portable.public_shares = Some(public_shares);
+
+    Ok(portable)
 }
 
 // ============================================================================
@@ -244,6 +407,19 @@ where
     serde_json::from_str(&share_json).context("Failed to convert KeyShare type")
 }
 
+/// 仅转换 ThresholdKeyShare 的 ID 类型
+pub fn convert_synedrion_threshold_share<P, FromId, ToId>(
+    share: &ThresholdKeyShare<P, FromId>,
+) -> Result<ThresholdKeyShare<P, ToId>>
+where
+    P: synedrion::SchemeParams,
+    FromId: PartyId + 'static,
+    ToId: PartyId + 'static,
+{
+    let share_json = serde_json::to_string(share)?;
+    serde_json::from_str(&share_json).context("Failed to convert ThresholdKeyShare type")
+}
+
 /// 从 KeyShareChange 中提取公钥增量 (Delta)
 pub fn extract_refresh_delta<P, Id>(
     change: &synedrion::KeyShareChange<P, Id>,
@@ -279,3 +455,37 @@ where
         Ok(None)
     }
 }
+
+/// 根据 KeyShareChange 计算刷新后的公钥分片列表
+///
+/// **功能**: 对每个参与方 j，X_j' = X_j + ΔX_j，其中 ΔX_j 由 `extract_refresh_delta` 提取。
+///
+/// **生产环境通信**: **不涉及**。KeyShareChange 中的公钥增量是 Refresh 协议的公开输出，
+/// 每个参与方在本地即可得到完整的新公钥分片列表，无需接触他人的私钥分片。
+///
+/// **注意**: 结果只对加法 (n-of-n) 分片成立。t-of-n Shamir 分片的增量需要先用
+/// `deal_refresh_delta` / `apply_refresh_deltas` 换算到 Shamir 域。
+pub fn refreshed_public_shares<P, Id>(
+    old_public_shares: &[PublicShare],
+    change: &synedrion::KeyShareChange<P, Id>,
+    party_id: impl Fn(u16) -> Id,
+) -> Result<Vec<PublicShare>>
+where
+    P: synedrion::SchemeParams,
+    Id: PartyId,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
+{
+    let mut new_public_shares = Vec::with_capacity(old_public_shares.len());
+    for ps in old_public_shares {
+        let old_point = parse_point::<P>(&ps.point_hex)?;
+        let delta = extract_refresh_delta(change, party_id(ps.i))?
+            .with_context(|| format!("Missing refresh delta for party {}", ps.i))?;
+        let new_point = old_point + delta;
+        new_public_shares.push(PublicShare {
+            i: ps.i,
+            point_hex: hex::encode(new_point.to_encoded_point(true).as_bytes()),
+        });
+    }
+    Ok(new_public_shares)
+}
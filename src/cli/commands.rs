@@ -0,0 +1,357 @@
+//! 子命令实现 (Command Handlers)
+//!
+//! 每个子命令返回一个 `serde_json::Value`，由 `main` 统一输出到 stdout。
+//! 协议过程中的进度日志全部输出到 stderr，不会污染 JSON 结果。
+
+use super::{Backend, BridgeArgs, BroadcastArgs, CommitteeArgs, Config, RefreshArgs, SignArgs};
+use anyhow::{anyhow, Context};
+use cggmp24::security_level::SecurityLevel128;
+use cggmp24::supported_curves::Secp256k1;
+use cggmp24::{ExecutionId, KeyShare};
+use ethers::types::{Address, TransactionRequest, U256};
+use my_mpc_bridge::bridge::{self, strip_0x};
+use my_mpc_bridge::eth_utils;
+use my_mpc_bridge::selection;
+use my_mpc_bridge::simulation::{self, truncate_hex, FastSecp256k1, SimpleVerifier};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+type CggmpKeyShare = KeyShare<Secp256k1, SecurityLevel128>;
+type SynedrionParams = FastSecp256k1;
+type RefreshedShares = BTreeMap<
+    SimpleVerifier,
+    (
+        synedrion::KeyShare<SynedrionParams, SimpleVerifier>,
+        synedrion::AuxInfo<SynedrionParams, SimpleVerifier>,
+    ),
+>;
+
+// ============================================================================
+// 辅助函数 (Helpers)
+// ============================================================================
+
+/// 加载已有的 DKG 数据 (只读)
+///
+/// 数据不存在时报错，不会运行 DKG，也不会修改 `data/`。只有 `dkg` 子命令会生成新的密钥。
+async fn load_cggmp_shares(
+    config: &Config,
+    args: &CommitteeArgs,
+) -> anyhow::Result<Vec<CggmpKeyShare>> {
+    simulation::load_dkg(config.parties(args))
+        .await
+        .context("先运行 `mpc-bridge dkg` 生成密钥分片")
+}
+
+/// 分片中保存的签名阈值 (n-of-n 密钥没有 `vss_setup`，阈值为 n)
+fn key_threshold(shares: &[CggmpKeyShare]) -> anyhow::Result<u16> {
+    let first = shares.first().context("No key shares loaded")?;
+    match first.core.vss_setup.as_ref() {
+        Some(setup) => Ok(setup.min_signers),
+        None => Ok(first.core.public_shares.len() as u16),
+    }
+}
+
+/// 返回 (压缩公钥 hex, 以太坊地址)
+fn key_info(shares: &[CggmpKeyShare]) -> anyhow::Result<(String, Address)> {
+    let first = shares.first().context("No key shares loaded")?;
+    let pubkey_bytes = first.shared_public_key.to_bytes(true);
+    let address = eth_utils::compute_eth_address_from_pubkey(&pubkey_bytes);
+    Ok((hex::encode(&pubkey_bytes), address))
+}
+
+/// 委员会的 party id -> 求值点映射
+///
+/// `mpc-bridge dkg` 生成的委员会是连续的 party id 0..n，这里用分片的 `vss_setup.I` 校验这一点。
+fn committee_points(shares: &[CggmpKeyShare]) -> anyhow::Result<bridge::EvaluationPoints> {
+    let first = shares.first().context("No key shares loaded")?;
+    let committee = bridge::EvaluationPoints::contiguous(first.core.public_shares.len() as u16);
+    bridge::from_cggmp_to_portable(first, Some(&committee))?;
+    Ok(committee)
+}
+
+fn load_refreshed_shares(path: &Path) -> anyhow::Result<RefreshedShares> {
+    let content = fs::read_to_string(path).with_context(|| {
+        format!(
+            "Failed to read {} (先运行 `mpc-bridge refresh`)",
+            path.display()
+        )
+    })?;
+    serde_json::from_str(&content).context("Failed to parse refreshed Synedrion shares")
+}
+
+fn parse_message_hash(hex_str: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(strip_0x(hex_str))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| anyhow!("Message hash must be 32 bytes, got {}", b.len()))
+}
+
+/// 加载 cggmp24 DKG 数据，运行 Synedrion AuxGen，并将 cggmp24 分片转换为 Synedrion 分片
+async fn bridge_to_synedrion(
+    config: &Config,
+    args: &CommitteeArgs,
+) -> anyhow::Result<(
+    Vec<CggmpKeyShare>,
+    Vec<(
+        synedrion::KeyShare<SynedrionParams, u16>,
+        synedrion::AuxInfo<SynedrionParams, u16>,
+    )>,
+)> {
+    let cggmp_shares = load_cggmp_shares(config, args).await?;
+    let party_ids: BTreeSet<u16> = cggmp_shares.iter().map(|s| s.core.i).collect();
+    let aux_map = simulation::run_synedrion_aux_gen::<SynedrionParams>(party_ids).await?;
+    let committee = committee_points(&cggmp_shares)?;
+    let synedrion_data =
+        simulation::cggmp_shares_to_synedrion(&cggmp_shares, &committee, &aux_map)?;
+    Ok((cggmp_shares, synedrion_data))
+}
+
+// ============================================================================
+// 子命令 (Commands)
+// ============================================================================
+
+/// 运行 DKG，已有数据时直接加载 (阈值不一致时报错，不会覆盖已有密钥)
+pub async fn dkg(config: &Config, args: &CommitteeArgs) -> anyhow::Result<Value> {
+    let execution_id = config.execution_id(args);
+    let shares = simulation::run_dkg(
+        config.parties(args),
+        config.threshold(args),
+        ExecutionId::new(execution_id.as_bytes()),
+    )
+    .await?;
+    let (shared_public_key, address) = key_info(&shares)?;
+    Ok(json!({
+        "parties": shares.iter().map(|s| s.core.i).collect::<Vec<_>>(),
+        "threshold": key_threshold(&shares)?,
+        "shared_public_key": shared_public_key,
+        "address": address,
+    }))
+}
+
+pub async fn aux_gen(config: &Config, args: &CommitteeArgs) -> anyhow::Result<Value> {
+    let party_ids: BTreeSet<u16> = (0..config.parties(args)).collect();
+    let aux_map = simulation::run_synedrion_aux_gen::<SynedrionParams>(party_ids).await?;
+
+    let mut parties = Vec::new();
+    for (id, aux) in &aux_map {
+        let paillier_n = bridge::get_aux_n_hex(aux, *id).unwrap_or_else(|_| "N/A".to_string());
+        parties.push(json!({ "party": id, "paillier_n": truncate_hex(&paillier_n) }));
+    }
+    Ok(json!({ "parties": parties }))
+}
+
+pub async fn refresh(config: &Config, args: &RefreshArgs) -> anyhow::Result<Value> {
+    let cache_path = config.refresh_cache(args.cache.as_ref());
+    let (cggmp_shares, synedrion_data) = bridge_to_synedrion(config, &args.committee).await?;
+    let (shared_public_key, address) = key_info(&cggmp_shares)?;
+
+    let updated_shares = simulation::run_refresh_workflow(
+        synedrion_data,
+        &committee_points(&cggmp_shares)?,
+        &cache_path.to_string_lossy(),
+        args.force,
+    )
+    .await?;
+
+    Ok(json!({
+        "cache": cache_path,
+        "parties": updated_shares.keys().map(|v| v.0).collect::<Vec<_>>(),
+        "shared_public_key": shared_public_key,
+        "address": address,
+    }))
+}
+
+pub async fn cggmp_to_synedrion(config: &Config, args: &BridgeArgs) -> anyhow::Result<Value> {
+    let (cggmp_shares, synedrion_data) = bridge_to_synedrion(config, &args.committee).await?;
+    let (shared_public_key, address) = key_info(&cggmp_shares)?;
+
+    let by_owner: BTreeMap<u16, _> = synedrion_data
+        .into_iter()
+        .map(|(share, aux)| (*share.owner(), (share, aux)))
+        .collect();
+    fs::write(&args.out, serde_json::to_string_pretty(&by_owner)?)?;
+
+    Ok(json!({
+        "out": args.out,
+        "parties": by_owner.keys().collect::<Vec<_>>(),
+        "shared_public_key": shared_public_key,
+        "address": address,
+    }))
+}
+
+pub async fn synedrion_to_cggmp(config: &Config, args: &BridgeArgs) -> anyhow::Result<Value> {
+    let input = config.refresh_cache(args.input.as_ref());
+    let refreshed = load_refreshed_shares(&input)?;
+    let templates = load_cggmp_shares(config, &args.committee).await?;
+    let (shared_public_key, address) = key_info(&templates)?;
+
+    let updated = simulation::synedrion_shares_to_cggmp(
+        &templates,
+        &refreshed,
+        &shared_public_key,
+        key_threshold(&templates)?,
+        &committee_points(&templates)?,
+    )?;
+    fs::write(&args.out, serde_json::to_string_pretty(&updated)?)?;
+
+    Ok(json!({
+        "input": input,
+        "out": args.out,
+        "parties": updated.iter().map(|s| s.core.i).collect::<Vec<_>>(),
+        "shared_public_key": shared_public_key,
+        "address": address,
+    }))
+}
+
+pub async fn sign(config: &Config, args: &SignArgs) -> anyhow::Result<Value> {
+    // cggmp24 后端指定了 --key-shares 时，委员会、阈值与签名者都以该文件中的分片为准，不需要 data/ 中的 DKG 数据
+    let shares = match (args.backend, &args.key_shares) {
+        (Backend::Cggmp, Some(path)) => {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str::<Vec<CggmpKeyShare>>(&content)
+                .context("Failed to parse cggmp24 key shares")?
+        }
+        _ => load_cggmp_shares(config, &args.committee).await?,
+    };
+    let threshold = key_threshold(&shares)?;
+    let (shared_public_key, address) = key_info(&shares)?;
+    let committee = committee_points(&shares)?;
+    let committee_ids = committee.ids();
+
+    // 1. 确定待签名的哈希: 直接给定，或者构造一笔转账交易
+    let chain_id = config.chain_id(args.chain_id);
+    let (message_hash, tx_request): ([u8; 32], Option<TransactionRequest>) =
+        match (&args.message_hash, &args.to) {
+            (Some(hash_hex), _) => (parse_message_hash(hash_hex)?, None),
+            (None, Some(to)) => {
+                let rpc_url = config.rpc_url(args.rpc_url.as_ref());
+                let to_address: Address = to.parse().context("Invalid recipient address")?;
+                let nonce = match args.nonce {
+                    Some(nonce) => nonce,
+                    None => eth_utils::get_nonce(&rpc_url, address)
+                        .await
+                        .map_err(|e| anyhow!(e))?,
+                };
+                let gas_price = match args.gas_price {
+                    Some(gas_price) => U256::from(gas_price),
+                    None => eth_utils::get_gas_price(&rpc_url)
+                        .await
+                        .map_err(|e| anyhow!(e))?,
+                };
+                let value_wei = args.value_wei.context("--value-wei is required")?;
+                let tx = eth_utils::create_tx_request(
+                    to_address, value_wei, nonce, chain_id, gas_price,
+                );
+                (tx.sighash().into(), Some(tx))
+            }
+            (None, None) => return Err(anyhow!("需要 --message-hash 或 --to/--value-wei")),
+        };
+
+    // 2. 确定签名者: 显式指定，或按策略从在线参与方中选择 (以消息哈希为种子)
+    let signers: Vec<u16> = match args.signers.clone().or_else(|| config.signers.clone()) {
+        Some(signers) => signers,
+        None => {
+            let available: BTreeSet<u16> = match &args.available {
+                Some(ids) => ids.iter().copied().collect(),
+                None => committee_ids.iter().copied().collect(),
+            };
+            selection::select_signers(
+                &committee_ids,
+                &available,
+                threshold,
+                &config.signer_policy(args),
+                &message_hash,
+            )?
+        }
+    };
+    let mut seen = BTreeSet::new();
+    for &id in &signers {
+        if !committee_ids.contains(&id) {
+            return Err(anyhow!("签名者 {} 不在委员会 {:?} 中", id, committee_ids));
+        }
+        if !seen.insert(id) {
+            return Err(anyhow!("签名者 {} 重复", id));
+        }
+    }
+    if signers.len() < threshold as usize {
+        return Err(anyhow!(
+            "签名者不足: 需要 {}, 实际 {}",
+            threshold,
+            signers.len()
+        ));
+    }
+
+    // 3. 运行 MPC 签名
+    let (r, s, v) = match args.backend {
+        Backend::Cggmp => {
+            // core.i 是分片在委员会中的位置，对应 committee_ids 中的 party id
+            let signing_shares: Vec<CggmpKeyShare> = shares
+                .into_iter()
+                .filter(|s| {
+                    committee_ids
+                        .get(s.core.i as usize)
+                        .is_some_and(|id| signers.contains(id))
+                })
+                .collect();
+            if signing_shares.len() != signers.len() {
+                return Err(anyhow!(
+                    "分片文件缺少部分签名者的分片: 需要 {:?}",
+                    signers
+                ));
+            }
+            simulation::run_cggmp_signing(&signing_shares, message_hash).await?
+        }
+        Backend::Synedrion => {
+            let cache_path = config.refresh_cache(args.cache.as_ref());
+            let refreshed = load_refreshed_shares(&cache_path)?;
+            let signer_keys: Vec<SimpleVerifier> =
+                signers.iter().map(|&id| SimpleVerifier(id)).collect();
+            let threshold_shares = simulation::synedrion_threshold_shares::<SynedrionParams>(
+                &refreshed,
+                &committee,
+                threshold,
+                &shared_public_key,
+            )?;
+            let signing_subset =
+                simulation::threshold_signing_subset(&threshold_shares, &signer_keys)?;
+            simulation::run_synedrion_signing_simulation::<SynedrionParams>(
+                &signing_subset,
+                message_hash,
+            )
+            .await?
+        }
+    };
+
+    let raw_tx = tx_request.map(|tx| eth_utils::encode_signed_tx(&tx, r, s, v, chain_id));
+
+    Ok(json!({
+        "backend": format!("{:?}", args.backend).to_lowercase(),
+        "signers": signers,
+        "address": address,
+        "message_hash": hex::encode(message_hash),
+        "r": hex::encode(r),
+        "s": hex::encode(s),
+        "v": v,
+        "raw_tx": raw_tx,
+    }))
+}
+
+pub async fn address(config: &Config, args: &CommitteeArgs) -> anyhow::Result<Value> {
+    let shares = load_cggmp_shares(config, args).await?;
+    let (shared_public_key, address) = key_info(&shares)?;
+    Ok(json!({
+        "shared_public_key": shared_public_key,
+        "address": address,
+    }))
+}
+
+pub async fn broadcast(config: &Config, args: &BroadcastArgs) -> anyhow::Result<Value> {
+    let rpc_url = config.rpc_url(args.rpc_url.as_ref());
+    let tx_hash = eth_utils::broadcast_tx(&rpc_url, &args.raw_tx)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    Ok(json!({ "tx_hash": tx_hash }))
+}
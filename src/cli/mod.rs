@@ -0,0 +1,231 @@
+//! 命令行参数与配置文件 (CLI Arguments & Config)
+//!
+//! 所有参数既可以通过命令行 flag 指定，也可以写在 `--config` 指向的 JSON 文件中。
+//! 优先级: flag > 配置文件 > 默认值。
+
+pub mod commands;
+
+use anyhow::Context;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use my_mpc_bridge::selection::SignerPolicy;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PARTIES: u16 = 5;
+pub const DEFAULT_THRESHOLD: u16 = 3;
+pub const DEFAULT_EXECUTION_ID: &str = "demo-mixed-mpc-session";
+pub const DEFAULT_RPC_URL: &str = "https://ethereum-sepolia-rpc.publicnode.com";
+pub const DEFAULT_CHAIN_ID: u64 = 11155111; // Sepolia
+pub const DEFAULT_REFRESH_CACHE: &str = "data/refreshed_synedrion_shares.json";
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "mpc-bridge",
+    version,
+    about = "cggmp24 <-> Synedrion MPC 密钥桥接工具，所有结果以 JSON 输出到 stdout"
+)]
+pub struct Cli {
+    /// JSON 配置文件路径
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// 运行 (或加载已有的) cggmp24 DKG
+    Dkg(CommitteeArgs),
+    /// 运行 Synedrion AuxGen 生成 Paillier 辅助信息
+    AuxGen(CommitteeArgs),
+    /// cggmp24 -> Synedrion -> KeyRefresh，并缓存刷新后的分片
+    Refresh(RefreshArgs),
+    /// 在两种分片格式之间转换
+    #[command(subcommand)]
+    Bridge(BridgeCommand),
+    /// 使用 cggmp24 或 Synedrion 签名消息哈希或以太坊交易
+    Sign(SignArgs),
+    /// 输出共享公钥与以太坊地址
+    Address(CommitteeArgs),
+    /// 广播已签名的原始交易
+    Broadcast(BroadcastArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BridgeCommand {
+    /// cggmp24 KeyShare -> Synedrion KeyShare + AuxInfo
+    CggmpToSynedrion(BridgeArgs),
+    /// 刷新后的 Synedrion KeyShare -> cggmp24 KeyShare
+    SynedrionToCggmp(BridgeArgs),
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct CommitteeArgs {
+    /// 参与方总数 n
+    #[arg(long)]
+    pub parties: Option<u16>,
+    /// 签名阈值 t (min_signers)
+    #[arg(long)]
+    pub threshold: Option<u16>,
+    /// DKG 使用的 ExecutionId
+    #[arg(long)]
+    pub execution_id: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct RefreshArgs {
+    #[command(flatten)]
+    pub committee: CommitteeArgs,
+    /// 刷新结果缓存文件
+    #[arg(long)]
+    pub cache: Option<PathBuf>,
+    /// 忽略缓存，强制重新运行 Key Refresh
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct BridgeArgs {
+    #[command(flatten)]
+    pub committee: CommitteeArgs,
+    /// 输入文件 (synedrion-to-cggmp 时默认为刷新缓存)
+    #[arg(long)]
+    pub input: Option<PathBuf>,
+    /// 输出文件
+    #[arg(long)]
+    pub out: PathBuf,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Cggmp,
+    Synedrion,
+}
+
+#[derive(Args, Debug)]
+pub struct SignArgs {
+    #[command(flatten)]
+    pub committee: CommitteeArgs,
+    /// 签名后端
+    #[arg(long, value_enum, default_value = "cggmp")]
+    pub backend: Backend,
+    /// 参与签名的 party id 列表 (逗号分隔)，指定后跳过签名者选择策略
+    #[arg(long, value_delimiter = ',')]
+    pub signers: Option<Vec<u16>>,
+    /// 当前在线的 party id (逗号分隔)，默认整个委员会
+    #[arg(long, value_delimiter = ',')]
+    pub available: Option<Vec<u16>>,
+    /// 优先入选的 party id (逗号分隔，按顺序)，覆盖配置文件中的 `signer_policy.preferred`
+    #[arg(long, value_delimiter = ',')]
+    pub prefer: Option<Vec<u16>>,
+    /// 排除的 party id (逗号分隔)，与配置文件中的 `signer_policy.exclude` 合并
+    #[arg(long, value_delimiter = ',')]
+    pub exclude: Option<Vec<u16>>,
+    /// cggmp24 KeyShare 文件 (例如 `bridge synedrion-to-cggmp` 的输出)，默认使用 DKG 数据
+    #[arg(long)]
+    pub key_shares: Option<PathBuf>,
+    /// Synedrion 刷新缓存文件
+    #[arg(long)]
+    pub cache: Option<PathBuf>,
+    /// 直接签名 32 字节消息哈希 (hex)
+    #[arg(long, conflicts_with = "to")]
+    pub message_hash: Option<String>,
+    /// 构造并签名转账交易: 收款地址
+    #[arg(long, requires = "value_wei")]
+    pub to: Option<String>,
+    /// 构造并签名转账交易: 金额 (wei)
+    #[arg(long)]
+    pub value_wei: Option<u64>,
+    /// 交易 nonce，默认从 RPC 查询
+    #[arg(long)]
+    pub nonce: Option<u64>,
+    /// gas price (wei)，默认从 RPC 查询
+    #[arg(long)]
+    pub gas_price: Option<u64>,
+    #[arg(long)]
+    pub chain_id: Option<u64>,
+    #[arg(long)]
+    pub rpc_url: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct BroadcastArgs {
+    /// RLP 编码的已签名交易 (0x 前缀 hex)
+    #[arg(long)]
+    pub raw_tx: String,
+    #[arg(long)]
+    pub rpc_url: Option<String>,
+}
+
+/// 配置文件格式 (JSON)，所有字段可选
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub parties: Option<u16>,
+    pub threshold: Option<u16>,
+    pub execution_id: Option<String>,
+    pub rpc_url: Option<String>,
+    pub chain_id: Option<u64>,
+    pub refresh_cache: Option<PathBuf>,
+    pub signers: Option<Vec<u16>>,
+    pub signer_policy: Option<SignerPolicy>,
+}
+
+impl Config {
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        match path {
+            Some(path) => {
+                let content = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read config {}", path.display()))?;
+                serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to parse config {}", path.display()))
+            }
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn parties(&self, args: &CommitteeArgs) -> u16 {
+        args.parties.or(self.parties).unwrap_or(DEFAULT_PARTIES)
+    }
+
+    pub fn threshold(&self, args: &CommitteeArgs) -> u16 {
+        args.threshold.or(self.threshold).unwrap_or(DEFAULT_THRESHOLD)
+    }
+
+    pub fn execution_id(&self, args: &CommitteeArgs) -> String {
+        args.execution_id
+            .clone()
+            .or_else(|| self.execution_id.clone())
+            .unwrap_or_else(|| DEFAULT_EXECUTION_ID.to_string())
+    }
+
+    pub fn rpc_url(&self, flag: Option<&String>) -> String {
+        flag.cloned()
+            .or_else(|| self.rpc_url.clone())
+            .unwrap_or_else(|| DEFAULT_RPC_URL.to_string())
+    }
+
+    pub fn chain_id(&self, flag: Option<u64>) -> u64 {
+        flag.or(self.chain_id).unwrap_or(DEFAULT_CHAIN_ID)
+    }
+
+    /// 配置文件中的策略，叠加 `--prefer` / `--exclude`
+    pub fn signer_policy(&self, args: &SignArgs) -> SignerPolicy {
+        let mut policy = self.signer_policy.clone().unwrap_or_default();
+        if let Some(prefer) = &args.prefer {
+            policy.preferred = prefer.clone();
+        }
+        if let Some(exclude) = &args.exclude {
+            policy.exclude.extend(exclude);
+        }
+        policy
+    }
+
+    pub fn refresh_cache(&self, flag: Option<&PathBuf>) -> PathBuf {
+        flag.cloned()
+            .or_else(|| self.refresh_cache.clone())
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_REFRESH_CACHE))
+    }
+}
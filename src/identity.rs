@@ -0,0 +1,261 @@
+//! 节点身份与消息认证 (Node Identity)
+//!
+//! `SimpleSigner` / `SimpleVerifier` 不做任何签名，任何参与方都可以伪造他人的 Synedrion 消息。
+//! 本模块用每个节点的 secp256k1 ECDSA 身份密钥实现 `manul::session::SessionParameters`:
+//! - [`NodeSigner`]: 持有本节点的私钥，对 manul 消息的摘要签名。
+//! - [`NodeVerifier`]: party id + 压缩公钥，同时作为 manul 中的 party id 使用。
+//! - [`NodeSessionParams`]: 把两者与 SHA-256 组合成会话参数。
+//!
+//! **密钥文件**:
+//! - 节点私钥 (每个节点一份，只保存在本机): `{"id": 0, "secret_key": "<32 字节 hex>"}`
+//! - 公钥登记表 (所有节点共享): `{"0": "<33 字节压缩公钥 hex>", "1": "...", ...}`
+
+use anyhow::{anyhow, Context};
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::digest::Digest;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use synedrion::signature::{
+    self, DigestVerifier, Error as SignatureError, Keypair, RandomizedDigestSigner,
+};
+
+/// 节点身份私钥 (secp256k1 ECDSA)
+#[derive(Clone)]
+pub struct NodeSigner {
+    id: u16,
+    signing_key: SigningKey,
+    verifier: NodeVerifier,
+}
+
+/// 节点公钥，同时作为 manul 会话中的 party id
+///
+/// 排序与比较先按 `id`，因此 `BTreeMap<NodeVerifier, _>` 的顺序与 party id 一致。
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NodeVerifier {
+    pub id: u16,
+    /// SEC1 压缩公钥
+    #[serde(with = "crate::bridge::hex_bytes")]
+    public_key: Vec<u8>,
+}
+
+/// 64 字节 (r || s) 的 ECDSA 签名
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeSignature(#[serde(with = "crate::bridge::hex_bytes")] Vec<u8>);
+
+#[derive(Serialize, Deserialize)]
+struct NodeKeyFile {
+    id: u16,
+    secret_key: String,
+}
+
+impl NodeSigner {
+    /// 生成新的身份密钥
+    pub fn generate(id: u16) -> Self {
+        Self::from_signing_key(id, SigningKey::random(&mut rand_core::OsRng))
+    }
+
+    /// 从 32 字节私钥 (hex) 构造
+    pub fn from_secret_hex(id: u16, secret_hex: &str) -> anyhow::Result<Self> {
+        let bytes = hex::decode(crate::bridge::strip_0x(secret_hex))?;
+        let signing_key =
+            SigningKey::from_slice(&bytes).map_err(|e| anyhow!("Invalid node secret key: {}", e))?;
+        Ok(Self::from_signing_key(id, signing_key))
+    }
+
+    fn from_signing_key(id: u16, signing_key: SigningKey) -> Self {
+        let public_key = signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec();
+        Self {
+            id,
+            signing_key,
+            verifier: NodeVerifier { id, public_key },
+        }
+    }
+
+    /// 读取节点私钥文件
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read node key {}", path.display()))?;
+        let file: NodeKeyFile = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse node key {}", path.display()))?;
+        Self::from_secret_hex(file.id, &file.secret_key)
+    }
+
+    /// 读取节点私钥文件，不存在时生成并保存
+    pub fn load_or_generate(id: u16, path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if path.exists() {
+            let signer = Self::load(path)?;
+            if signer.id != id {
+                return Err(anyhow!(
+                    "{} 属于 Party {}，而不是 Party {}",
+                    path.display(),
+                    signer.id,
+                    id
+                ));
+            }
+            return Ok(signer);
+        }
+        let signer = Self::generate(id);
+        signer.save(path)?;
+        Ok(signer)
+    }
+
+    /// 保存节点私钥文件 (只应保存在本节点)
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = NodeKeyFile {
+            id: self.id,
+            secret_key: hex::encode(self.signing_key.to_bytes()),
+        };
+        fs::write(path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// 身份私钥，用于签名 `network::secure` 的 X25519 公钥声明
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.signing_key
+    }
+}
+
+impl fmt::Debug for NodeSigner {
+    // 不输出私钥
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeSigner")
+            .field("verifier", &self.verifier)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NodeVerifier {
+    /// 从压缩或非压缩公钥 (hex) 构造
+    pub fn from_public_hex(id: u16, public_hex: &str) -> anyhow::Result<Self> {
+        let bytes = hex::decode(crate::bridge::strip_0x(public_hex))?;
+        let key = VerifyingKey::from_sec1_bytes(&bytes)
+            .map_err(|e| anyhow!("Invalid public key for Party {}: {}", id, e))?;
+        Ok(Self {
+            id,
+            public_key: key.to_encoded_point(true).as_bytes().to_vec(),
+        })
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(&self.public_key)
+    }
+
+    /// 身份公钥，用于 `NetworkNode::enable_encryption` 的登记表
+    pub fn public_key(&self) -> anyhow::Result<VerifyingKey> {
+        VerifyingKey::from_sec1_bytes(&self.public_key)
+            .map_err(|e| anyhow!("Invalid public key for Party {}: {}", self.id, e))
+    }
+
+    fn verifying_key(&self) -> Result<VerifyingKey, SignatureError> {
+        VerifyingKey::from_sec1_bytes(&self.public_key).map_err(|_| SignatureError::new())
+    }
+
+    fn verify_prehash(&self, prehash: &[u8], signature: &NodeSignature) -> Result<(), SignatureError> {
+        let signature = Signature::from_slice(&signature.0).map_err(|_| SignatureError::new())?;
+        self.verifying_key()?
+            .verify_prehash(prehash, &signature)
+            .map_err(|_| SignatureError::new())
+    }
+}
+
+impl fmt::Debug for NodeVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Party {} ({})", self.id, crate::simulation::truncate_hex(&self.public_key_hex()))
+    }
+}
+
+impl From<NodeVerifier> for u16 {
+    fn from(v: NodeVerifier) -> Self {
+        v.id
+    }
+}
+
+impl Keypair for NodeSigner {
+    type VerifyingKey = NodeVerifier;
+    fn verifying_key(&self) -> Self::VerifyingKey {
+        self.verifier.clone()
+    }
+}
+
+impl<D: Digest> RandomizedDigestSigner<D, NodeSignature> for NodeSigner {
+    fn try_sign_digest_with_rng(
+        &self,
+        _rng: &mut (impl signature::rand_core::CryptoRng + signature::rand_core::RngCore),
+        digest: D,
+    ) -> Result<NodeSignature, SignatureError> {
+        // RFC 6979 确定性 nonce，不需要外部随机数
+        let signature: Signature = self
+            .signing_key
+            .sign_prehash(&digest.finalize())
+            .map_err(|_| SignatureError::new())?;
+        Ok(NodeSignature(signature.to_bytes().to_vec()))
+    }
+}
+
+impl<D: Digest> DigestVerifier<D, NodeSignature> for NodeVerifier {
+    fn verify_digest(&self, digest: D, signature: &NodeSignature) -> Result<(), SignatureError> {
+        self.verify_prehash(&digest.finalize(), signature)
+    }
+}
+
+/// 使用节点身份密钥认证消息的 manul 会话参数
+///
+/// `WireFormat` 沿用 manul 的 `BinaryFormat` (bincode)；消息认证只依赖签名，与编码格式无关。
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeSessionParams;
+
+impl manul::session::SessionParameters for NodeSessionParams {
+    type Signer = NodeSigner;
+    type Verifier = NodeVerifier;
+    type Signature = NodeSignature;
+    type Digest = sha2::Sha256;
+    type WireFormat = manul::dev::BinaryFormat;
+}
+
+/// 读取公钥登记表 (`{"<party id>": "<公钥 hex>"}`)
+pub fn load_verifiers(path: impl AsRef<Path>) -> anyhow::Result<BTreeMap<u16, NodeVerifier>> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read verifier registry {}", path.display()))?;
+    let registry: BTreeMap<u16, String> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse verifier registry {}", path.display()))?;
+    registry
+        .into_iter()
+        .map(|(id, public_hex)| Ok((id, NodeVerifier::from_public_hex(id, &public_hex)?)))
+        .collect()
+}
+
+/// 保存公钥登记表
+pub fn save_verifiers<'a>(
+    path: impl AsRef<Path>,
+    verifiers: impl IntoIterator<Item = &'a NodeVerifier>,
+) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let registry: BTreeMap<u16, String> = verifiers
+        .into_iter()
+        .map(|v| (v.id, v.public_key_hex()))
+        .collect();
+    fs::write(path, serde_json::to_string_pretty(&registry)?)?;
+    Ok(())
+}
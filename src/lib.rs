@@ -0,0 +1,66 @@
+//! # my-mpc-bridge
+//!
+//! 在不同 MPC 门限签名库 (`cggmp24` 与 `synedrion`) 之间迁移 secp256k1 密钥分片的桥接库。
+//!
+//! ## 模块 (Modules)
+//!
+//! - [`bridge`]: 数据转换层。
+//!   - [`bridge::common`]: 通用中间格式 [`bridge::PortableKeyShare`] 及 Hex 工具函数。
+//!   - [`bridge::cggmp`]: `cggmp24::KeyShare` <-> `PortableKeyShare`。
+//!   - [`bridge::synedrion`]: `synedrion::KeyShare` <-> `PortableKeyShare`。
+//!   - [`bridge::core`]: Shamir 分片与加法分片之间的转换 (Lagrange / Resharing)、门限变更与委员会轮换。
+//! - [`math`]: Shamir 秘密共享与拉格朗日插值等数学原语。
+//! - [`eth_utils`]: 以太坊地址计算、交易构造、RLP 编码与 RPC 广播。
+//! - [`simulation`]: 在单进程内驱动 DKG / AuxGen / Refresh / Signing 协议的模拟器。
+//! - [`identity`]: 节点 secp256k1 身份密钥、公钥登记表与经签名认证的 manul 会话参数 (`NodeSessionParams`)。
+//! - [`selection`]: 按在线状态、优先级/权重与排除列表确定性地选择签名者集合。
+//! - [`network`]: TCP 传输层、消息中继与 P2P 端到端加密，每个参与方作为独立进程运行 `round_based` 协议与 manul 会话。
+//!
+//! ## Cargo Features
+//!
+//! | feature     | 启用内容                                                                       |
+//! |-------------|--------------------------------------------------------------------------------|
+//! | `cggmp24`   | `bridge::cggmp`, `simulation::cggmp`, `network::cggmp`                         |
+//! | `synedrion` | `bridge::synedrion`, `simulation::synedrion`, `network::synedrion`, `identity` |
+//! | `ethereum`  | `eth_utils`                                                                    |
+//! | `network`   | `network` (TCP 传输层、中继与端到端加密)                                       |
+//! | `relay`     | `mpc-relay` (依赖 `network`)                                                   |
+//!
+//! 默认全部启用。`bridge::common` (`PortableKeyShare`)、`bridge::core` 与 `math` 始终可用。
+//!
+//! ## 示例 (Example)
+//!
+//! 完整的 Sepolia 演示流程见 `examples/sepolia_walkthrough.rs`，门限变更与委员会轮换见
+//! `examples/threshold_change.rs` 与 `examples/committee_rotation.rs`，两种 Key Refresh 路径的对比见
+//! `examples/refresh_comparison.rs`，从 Synedrion 建钥迁移到 cggmp24 见 `examples/synedrion_first.rs`，
+//! 用 Synedrion KeyResharing 变更委员会见 `examples/synedrion_resharing.rs`，
+//! 预签名池与批量签名见 `examples/presignature_pool.rs` 与 `examples/batch_signing.rs`，
+//! 节点身份认证见 `examples/node_identity.rs`，多进程运行见 `examples/network_cggmp.rs`、`examples/network_synedrion.rs` 与 `examples/network_relay.rs`，
+//! P2P 消息端到端加密见 `examples/e2e_encryption.rs`：
+//!
+//! ```text
+//! cargo run --example sepolia_walkthrough
+//! cargo run --example threshold_change
+//! cargo run --example committee_rotation
+//! cargo run --example refresh_comparison
+//! cargo run --example synedrion_first
+//! cargo run --example synedrion_resharing
+//! cargo run --example presignature_pool
+//! cargo run --example batch_signing
+//! cargo run --example node_identity
+//! cargo run --example network_cggmp
+//! cargo run --example network_synedrion
+//! cargo run --example network_relay
+//! cargo run --example e2e_encryption
+//! ```
+
+pub mod bridge;
+#[cfg(feature = "ethereum")]
+pub mod eth_utils;
+#[cfg(feature = "synedrion")]
+pub mod identity;
+pub mod math;
+#[cfg(feature = "network")]
+pub mod network;
+pub mod selection;
+pub mod simulation;
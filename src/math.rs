@@ -11,6 +11,17 @@
 //!    - 用于分布式密钥重构 (Resharing) 或密钥刷新 (Refresh)。
 //!    - 通过构造随机多项式，将一个秘密值（如加法分片）拆分为多个子分片发送给其他参与方。
 //!
+//! 3. **Feldman VSS (`generate_polynomial_shares` / `verify_share`)**:
+//!    - 分片生成时同时返回多项式系数的承诺 $C_k = a_k \cdot G$。
+//!    - 接收方用 `verify_share` 检查自己收到的分片是否落在承诺的多项式上，从而发现作恶的分发者。
+//!
+//! 4. **Pedersen VSS (`generate_pedersen_shares` / `verify_pedersen_share`)**:
+//!    - 承诺 $C_k = a_k \cdot G + b_k \cdot H$ 是信息论隐藏的，不泄露 $a_0 \cdot G$。
+//!    - 第二个生成元 $H$ 由 `pedersen_generator` 以 nothing-up-my-sleeve 的方式哈希得到。
+//!
+//! 5. **指数上的插值 (`interpolate_in_exponent` / `evaluate_in_exponent`)**:
+//!    - 仅凭公钥分片 $x_i \cdot G$ 重建 VSS Commitments，并验证公钥分片的一致性。
+//!
 //! ## 安全性 (Security)
 //!
 //! - 所有计算均在有限域 (Finite Field) 上进行，使用 `k256::Scalar` 类型。
@@ -23,7 +34,7 @@
 
 
 use elliptic_curve::Field;
-use k256::Scalar;
+use k256::{ProjectivePoint, Scalar};
 use rand_core::OsRng;
 
 /// 计算拉格朗日插值系数 (Lagrange Coefficient) $\lambda_i$
@@ -89,12 +100,27 @@ pub fn calculate_lagrange_coefficient(party_index: u64, all_indices: &[u64]) ->
 /// - `n`: 总参与方数量 (生成的份额总数)。
 ///
 /// ### 返回值 (Returns)
-/// 返回一个包含 $n$ 个 Scalar 的向量，第 $j$ 个元素对应 $x=j+1$ 处的函数值。
+/// 返回 [`FeldmanShares`]：
+/// - `shares`: $n$ 个 Scalar，第 $j$ 个元素对应 $x=j+1$ 处的函数值。
+/// - `commitments`: $t$ 个 Feldman 承诺 $C_k = a_k \cdot G$，其中 $C_0 = s \cdot G$。
 pub fn generate_polynomial_shares(
     secret: Scalar,
     threshold: u16,
     n: u16,
-) -> Vec<Scalar> {
+) -> FeldmanShares {
+    let points: Vec<u64> = (1..=n as u64).collect();
+    generate_polynomial_shares_at(secret, threshold, &points)
+}
+
+/// 在指定求值点上生成 Feldman VSS 分片
+///
+/// 与 [`generate_polynomial_shares`] 相同，但第 $j$ 个分片对应 $x = \text{points}[j]$，
+/// 用于参与方索引不连续 (例如委员会轮换后) 的场景。求值点必须非零且互不相同。
+pub fn generate_polynomial_shares_at(
+    secret: Scalar,
+    threshold: u16,
+    points: &[u64],
+) -> FeldmanShares {
     // 1. 确定多项式阶数 (Degree)
     // Degree = t - 1
     let degree = (threshold as usize).saturating_sub(1);
@@ -109,10 +135,10 @@ pub fn generate_polynomial_shares(
     }
 
     // 3. 计算每个点的份额 (Evaluation)
-    // Evaluate f(x) for x = 1, 2, ..., n
-    let mut shares = Vec::with_capacity(n as usize);
-    for j in 1..=n {
-        let x = Scalar::from(j as u64);
+    // Evaluate f(x) for every x in points
+    let mut shares = Vec::with_capacity(points.len());
+    for &point in points {
+        let x = Scalar::from(point);
         let mut y = Scalar::ZERO;
         
         // Horner's Method or simple iteration for polynomial evaluation
@@ -125,5 +151,368 @@ pub fn generate_polynomial_shares(
         shares.push(y);
     }
 
-    shares
-}
\ No newline at end of file
+    // 4. 计算 Feldman 承诺 (Commitments)
+    let commitments = coeffs
+        .iter()
+        .map(|a| ProjectivePoint::GENERATOR * a)
+        .collect();
+
+    FeldmanShares {
+        shares,
+        commitments,
+    }
+}
+
+/// Feldman VSS 的分片与承诺
+#[derive(Clone, Debug)]
+pub struct FeldmanShares {
+    /// $f(1), \dots, f(n)$ (或 `generate_polynomial_shares_at` 指定的求值点)
+    pub shares: Vec<Scalar>,
+    /// $a_0 \cdot G, \dots, a_{t-1} \cdot G$
+    pub commitments: Vec<ProjectivePoint>,
+}
+
+/// 验证 Feldman VSS 分片 (Verify Share)
+///
+/// ### 原理 (Theory)
+/// 若分片 $y = f(x)$ 确实来自承诺的多项式，则
+/// $$ y \cdot G = \sum_k C_k \cdot x^k $$
+/// 分发者无法在不改变承诺的前提下给不同的接收方发送来自不同多项式的分片。
+///
+/// ### 参数 (Parameters)
+/// - `index`: 接收方的求值点 $x$ (1-based)。
+/// - `share`: 收到的分片 $y$。
+/// - `commitments`: 分发者广播的承诺 $C_0, \dots, C_{t-1}$。
+pub fn verify_share(index: u64, share: &Scalar, commitments: &[ProjectivePoint]) -> bool {
+    ProjectivePoint::GENERATOR * share == evaluate_in_exponent(commitments, index)
+}
+
+/// 指数上的拉格朗日插值 (Interpolation in the Exponent)
+///
+/// ### 原理 (Theory)
+/// 给定 $m$ 个点 $(x_i, Y_i)$，其中 $Y_i = f(x_i) \cdot G$，$f$ 是 $m-1$ 次多项式，
+/// 求出系数承诺 $F_k = a_k \cdot G$，使得 $Y_i = \sum_k F_k \cdot x_i^k$。
+/// 计算方式与标量域上的插值相同，只是把 $y_i$ 换成了曲线点 (点乘标量)。
+///
+/// ### 用途 (Usage)
+/// 只凭各方的**公钥分片**就能重建 Feldman VSS Commitments，无需任何私钥分片。
+/// 常数项 $F_0 = f(0) \cdot G$ 即全局公钥。
+///
+/// ### 返回值 (Returns)
+/// 返回 $m$ 个系数承诺；若存在重复的 $x_i$ (无法求逆) 则返回 `None`。
+pub fn interpolate_in_exponent(points: &[(u64, ProjectivePoint)]) -> Option<Vec<ProjectivePoint>> {
+    let m = points.len();
+    let mut coeffs = vec![ProjectivePoint::IDENTITY; m];
+
+    for (i, &(xi, yi)) in points.iter().enumerate() {
+        let xi = Scalar::from(xi);
+        let mut denom = Scalar::ONE;
+
+        // 拉格朗日基多项式的分子 prod_{j != i} (x - x_j)，按升幂存储
+        let mut basis = vec![Scalar::ZERO; m];
+        basis[0] = Scalar::ONE;
+        for (j, &(xj, _)) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let xj = Scalar::from(xj);
+            denom *= xi - xj;
+            for k in (1..m).rev() {
+                basis[k] = basis[k - 1] - xj * basis[k];
+            }
+            basis[0] = -xj * basis[0];
+        }
+        let inv_denom = Option::<Scalar>::from(denom.invert())?;
+
+        for k in 0..m {
+            coeffs[k] += yi * (basis[k] * inv_denom);
+        }
+    }
+
+    Some(coeffs)
+}
+
+/// 在指数上对多项式求值: $\sum_k F_k \cdot x^k$ (Horner's Method)
+pub fn evaluate_in_exponent(commitments: &[ProjectivePoint], x: u64) -> ProjectivePoint {
+    let x = Scalar::from(x);
+    commitments
+        .iter()
+        .rev()
+        .fold(ProjectivePoint::IDENTITY, |acc, c| acc * x + c)
+}
+
+/// Pedersen VSS 的第二个生成元 $H$ 的域分隔标签
+const PEDERSEN_H_DOMAIN: &[u8] = b"my-mpc-bridge/pedersen-vss/secp256k1/H";
+
+/// Pedersen VSS 的第二个生成元 $H$ (Nothing-up-my-sleeve Generator)
+///
+/// ### 原理 (Theory)
+/// Pedersen 承诺要求没有人知道 $\log_G H$。这里用 "try-and-increment" 的方式把公开的域分隔标签
+/// 哈希到曲线上：依次计算 $x = \mathrm{SHA256}(\text{domain} \| \text{counter})$，
+/// 取第一个能解压为合法点 (`0x02 || x`) 的结果。整个过程完全确定且可复现，
+/// 因此任何人都可以验证 $H$ 不是由某个已知私钥生成的。
+pub fn pedersen_generator() -> ProjectivePoint {
+    use k256::elliptic_curve::sec1::FromEncodedPoint;
+    use k256::{AffinePoint, EncodedPoint};
+    use sha2::{Digest, Sha256};
+    use std::sync::OnceLock;
+
+    static H: OnceLock<ProjectivePoint> = OnceLock::new();
+    *H.get_or_init(|| {
+        for counter in 0u32.. {
+            let mut hasher = Sha256::new();
+            hasher.update(PEDERSEN_H_DOMAIN);
+            hasher.update(counter.to_be_bytes());
+            let x = hasher.finalize();
+
+            let mut compressed = [0u8; 33];
+            compressed[0] = 0x02;
+            compressed[1..].copy_from_slice(&x);
+            let Ok(encoded) = EncodedPoint::from_bytes(compressed) else {
+                continue;
+            };
+            let point = Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&encoded));
+            if let Some(point) = point {
+                return ProjectivePoint::from(point);
+            }
+        }
+        unreachable!("a valid x-coordinate is found with overwhelming probability")
+    })
+}
+
+/// Pedersen VSS 的分片、盲化分片与承诺
+#[derive(Clone, Debug)]
+pub struct PedersenShares {
+    /// 每个求值点上的 $f(x)$：`generate_pedersen_shares` 为 $f(1), \dots, f(n)$，
+    /// `generate_pedersen_shares_at` 按 `points` 的顺序
+    pub shares: Vec<Scalar>,
+    /// 同一组求值点上盲化多项式的取值 $r(x)$
+    pub blindings: Vec<Scalar>,
+    /// $C_k = a_k \cdot G + b_k \cdot H$
+    pub commitments: Vec<ProjectivePoint>,
+}
+
+/// 生成 Pedersen VSS 分片 (Generate Pedersen Shares)
+///
+/// ### 原理 (Theory)
+/// 与 Feldman VSS 相比，额外选取一个随机盲化多项式 $r(x) = b_0 + b_1 x + \dots$，
+/// 公开的承诺为 $C_k = a_k \cdot G + b_k \cdot H$。
+/// 由于 $b_k$ 均匀随机，承诺在信息论意义上不泄露任何 $a_k \cdot G$ (包括 $a_0 \cdot G = s \cdot G$)。
+///
+/// ### 返回值 (Returns)
+/// 第 $j$ 个分片 / 盲化分片对应 $x=j+1$ 处的函数值。
+pub fn generate_pedersen_shares(secret: Scalar, threshold: u16, n: u16) -> PedersenShares {
+    let points: Vec<u64> = (1..=n as u64).collect();
+    generate_pedersen_shares_at(secret, threshold, &points)
+}
+
+/// 在指定求值点上生成 Pedersen VSS 分片 (参见 [`generate_polynomial_shares_at`])
+pub fn generate_pedersen_shares_at(
+    secret: Scalar,
+    threshold: u16,
+    points: &[u64],
+) -> PedersenShares {
+    let degree = (threshold as usize).saturating_sub(1);
+    let h = pedersen_generator();
+
+    let mut coeffs = Vec::with_capacity(degree + 1);
+    let mut blinding_coeffs = Vec::with_capacity(degree + 1);
+    coeffs.push(secret);
+    blinding_coeffs.push(Scalar::random(&mut OsRng));
+    for _ in 0..degree {
+        coeffs.push(Scalar::random(&mut OsRng));
+        blinding_coeffs.push(Scalar::random(&mut OsRng));
+    }
+
+    let evaluate = |poly: &[Scalar], x: Scalar| {
+        poly.iter()
+            .rev()
+            .fold(Scalar::ZERO, |acc, coeff| acc * x + coeff)
+    };
+
+    let mut shares = Vec::with_capacity(points.len());
+    let mut blindings = Vec::with_capacity(points.len());
+    for &point in points {
+        let x = Scalar::from(point);
+        shares.push(evaluate(&coeffs, x));
+        blindings.push(evaluate(&blinding_coeffs, x));
+    }
+
+    let commitments = coeffs
+        .iter()
+        .zip(&blinding_coeffs)
+        .map(|(a, b)| ProjectivePoint::GENERATOR * a + h * b)
+        .collect();
+
+    PedersenShares {
+        shares,
+        blindings,
+        commitments,
+    }
+}
+
+/// 验证 Pedersen VSS 分片 (Verify Pedersen Share)
+///
+/// 检查 $y \cdot G + y' \cdot H = \sum_k C_k \cdot x^k$，其中 $y'$ 是盲化分片。
+pub fn verify_pedersen_share(
+    index: u64,
+    share: &Scalar,
+    blinding: &Scalar,
+    commitments: &[ProjectivePoint],
+) -> bool {
+    ProjectivePoint::GENERATOR * share + pedersen_generator() * blinding
+        == evaluate_in_exponent(commitments, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 用 `points` 中前 t 个分片在 x = 0 处插值恢复秘密
+    fn recover(points: &[u64], shares: &[Scalar]) -> Scalar {
+        points
+            .iter()
+            .zip(shares)
+            .map(|(&x, y)| *y * calculate_lagrange_coefficient(x, points))
+            .sum()
+    }
+
+    #[test]
+    fn shares_at_sparse_points_recover_secret() {
+        let secret = Scalar::random(&mut OsRng);
+        let points = [1, 3, 4, 8, 10];
+        let feldman = generate_polynomial_shares_at(secret, 3, &points);
+
+        assert_eq!(feldman.shares.len(), points.len());
+        assert_eq!(feldman.commitments.len(), 3);
+        assert_eq!(feldman.commitments[0], ProjectivePoint::GENERATOR * secret);
+        // 任意 3 个分片都能恢复秘密，2 个不能
+        assert_eq!(recover(&points[..3], &feldman.shares[..3]), secret);
+        assert_eq!(recover(&points[2..], &feldman.shares[2..]), secret);
+        assert_ne!(recover(&points[..2], &feldman.shares[..2]), secret);
+    }
+
+    #[test]
+    fn contiguous_shares_match_points_one_to_n() {
+        let feldman = generate_polynomial_shares(Scalar::ONE, 2, 4);
+        for (j, share) in feldman.shares.iter().enumerate() {
+            assert!(verify_share(j as u64 + 1, share, &feldman.commitments));
+        }
+    }
+
+    #[test]
+    fn verify_share_rejects_wrong_share_or_point() {
+        let points = [2, 5, 7];
+        let feldman = generate_polynomial_shares_at(Scalar::random(&mut OsRng), 2, &points);
+
+        for (&x, share) in points.iter().zip(&feldman.shares) {
+            assert!(verify_share(x, share, &feldman.commitments));
+        }
+        let tampered = feldman.shares[0] + Scalar::ONE;
+        assert!(!verify_share(points[0], &tampered, &feldman.commitments));
+        assert!(!verify_share(points[1], &feldman.shares[0], &feldman.commitments));
+    }
+
+    #[test]
+    fn interpolate_in_exponent_recovers_commitments() {
+        let points = [1, 4, 6, 9];
+        let feldman = generate_polynomial_shares_at(Scalar::random(&mut OsRng), 3, &points);
+        let public: Vec<(u64, ProjectivePoint)> = points
+            .iter()
+            .zip(&feldman.shares)
+            .map(|(&x, y)| (x, ProjectivePoint::GENERATOR * y))
+            .collect();
+
+        // 任意 t 个公钥分片都给出同一组承诺
+        let from_first = interpolate_in_exponent(&public[..3]).expect("distinct points");
+        let from_last = interpolate_in_exponent(&public[1..]).expect("distinct points");
+        assert_eq!(from_first, feldman.commitments);
+        assert_eq!(from_last, feldman.commitments);
+        // 第 t+1 个公钥分片落在插值出的多项式上
+        assert_eq!(evaluate_in_exponent(&from_first, points[3]), public[3].1);
+    }
+
+    #[test]
+    fn interpolate_in_exponent_rejects_duplicate_points() {
+        let y = ProjectivePoint::GENERATOR;
+        assert!(interpolate_in_exponent(&[(3, y), (3, y * Scalar::from(2u64))]).is_none());
+    }
+
+    #[test]
+    fn pedersen_generator_is_deterministic_and_independent_of_g() {
+        let h = pedersen_generator();
+        assert_eq!(h, pedersen_generator());
+        assert_ne!(h, ProjectivePoint::GENERATOR);
+        assert_ne!(h, ProjectivePoint::IDENTITY);
+    }
+
+    #[test]
+    fn pedersen_shares_verify_and_hide_secret() {
+        let secret = Scalar::random(&mut OsRng);
+        let pedersen = generate_pedersen_shares(secret, 3, 5);
+
+        assert_eq!(pedersen.shares.len(), 5);
+        assert_eq!(pedersen.blindings.len(), 5);
+        assert_eq!(pedersen.commitments.len(), 3);
+        // C_0 = s·G + b_0·H，不等于 s·G
+        assert_ne!(pedersen.commitments[0], ProjectivePoint::GENERATOR * secret);
+        for (j, (share, blinding)) in pedersen.shares.iter().zip(&pedersen.blindings).enumerate() {
+            assert!(verify_pedersen_share(
+                j as u64 + 1,
+                share,
+                blinding,
+                &pedersen.commitments
+            ));
+        }
+        let points: Vec<u64> = (1..=5).collect();
+        assert_eq!(recover(&points[..3], &pedersen.shares[..3]), secret);
+    }
+
+    #[test]
+    fn verify_pedersen_share_rejects_tampered_share_or_blinding() {
+        let pedersen = generate_pedersen_shares(Scalar::random(&mut OsRng), 2, 3);
+        let (share, blinding) = (pedersen.shares[1], pedersen.blindings[1]);
+        let commitments = &pedersen.commitments;
+
+        assert!(verify_pedersen_share(2, &share, &blinding, commitments));
+        assert!(!verify_pedersen_share(
+            2,
+            &(share + Scalar::ONE),
+            &blinding,
+            commitments
+        ));
+        assert!(!verify_pedersen_share(
+            2,
+            &share,
+            &(blinding + Scalar::ONE),
+            commitments
+        ));
+        assert!(!verify_pedersen_share(3, &share, &blinding, commitments));
+    }
+
+    #[test]
+    fn pedersen_shares_at_sparse_points() {
+        let secret = Scalar::random(&mut OsRng);
+        let points = [1, 3, 4, 8, 10];
+        let pedersen = generate_pedersen_shares_at(secret, 3, &points);
+
+        assert_eq!(pedersen.shares.len(), points.len());
+        for ((&x, share), blinding) in points.iter().zip(&pedersen.shares).zip(&pedersen.blindings)
+        {
+            assert!(verify_pedersen_share(
+                x,
+                share,
+                blinding,
+                &pedersen.commitments
+            ));
+        }
+        // 分片对应 points 中的求值点，而不是 1..=n
+        assert!(!verify_pedersen_share(
+            2,
+            &pedersen.shares[1],
+            &pedersen.blindings[1],
+            &pedersen.commitments
+        ));
+        assert_eq!(recover(&points[2..], &pedersen.shares[2..]), secret);
+    }
+}
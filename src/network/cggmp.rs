@@ -0,0 +1,142 @@
+//! 网络化的 cggmp24 协议 (Networked cggmp24)
+//!
+//! 与 `simulation::cggmp` 中的函数对应，但每个函数只运行**本方**的一份协议，消息经
+//! [`NetworkNode`] 发往其他进程。所有参与方必须以相同的 `session_id` 调用同一个函数。
+//!
+//! **索引约定**: keygen / aux_info_gen 的协议索引是 party id 在 `node.parties()` 中的位置，
+//! 因此 `core.i` 即该位置；签名时 `participants` 由签名者 party id 映射到这些位置。
+
+use super::delivery::round_based_delivery;
+use super::node::NetworkNode;
+use crate::simulation::calc_recovery_id_from_pubkey;
+
+use anyhow::{anyhow, Context};
+use cggmp24::key_share::{AuxInfo, IncompleteKeyShare};
+use cggmp24::security_level::SecurityLevel128;
+use cggmp24::supported_curves::Secp256k1;
+use cggmp24::{ExecutionId, KeyShare, PregeneratedPrimes};
+use rand_core::OsRng;
+use round_based::MpcParty;
+use sha2::Sha256;
+
+/// 本方在 `node.parties()` 中的位置 (keygen 协议索引)
+fn committee_index(node: &NetworkNode, id: u16) -> anyhow::Result<u16> {
+    node.parties()
+        .iter()
+        .position(|&p| p == id)
+        .map(|i| i as u16)
+        .ok_or_else(|| anyhow!("Party {} 不在委员会 {:?} 中", id, node.parties()))
+}
+
+/// 运行本方的 cggmp24 keygen (全体参与方)
+pub async fn run_cggmp_keygen_party(
+    node: &NetworkNode,
+    session_id: &str,
+    threshold: u16,
+) -> anyhow::Result<IncompleteKeyShare<Secp256k1>> {
+    let i = committee_index(node, node.id())?;
+    let n = node.parties().len() as u16;
+    let link = node.session(session_id, node.parties())?;
+    eprintln!("      [NET] Party {} keygen ({}-of-{})...", node.id(), threshold, n);
+    cggmp24::keygen::<Secp256k1>(ExecutionId::new(session_id.as_bytes()), i, n)
+        .set_threshold(threshold)
+        .start(&mut OsRng, MpcParty::connected(round_based_delivery(link)))
+        .await
+        .map_err(|e| anyhow!("Party {} keygen failed: {:?}", node.id(), e))
+}
+
+/// 运行本方的 cggmp24 aux_info_gen (全体参与方)
+pub async fn run_cggmp_aux_gen_party(
+    node: &NetworkNode,
+    session_id: &str,
+    primes: PregeneratedPrimes<SecurityLevel128>,
+) -> anyhow::Result<AuxInfo<SecurityLevel128>> {
+    let i = committee_index(node, node.id())?;
+    let n = node.parties().len() as u16;
+    let link = node.session(session_id, node.parties())?;
+    eprintln!("      [NET] Party {} aux_info_gen...", node.id());
+    cggmp24::aux_info_gen(ExecutionId::new(session_id.as_bytes()), i, n, primes)
+        .start(&mut OsRng, MpcParty::connected(round_based_delivery(link)))
+        .await
+        .map_err(|e| anyhow!("Party {} aux_info_gen failed: {:?}", node.id(), e))
+}
+
+/// 依次运行 keygen 与 aux_info_gen，合并为完整的 KeyShare
+///
+/// 两个阶段分别使用会话 `{session_prefix}/keygen` 与 `{session_prefix}/aux`。
+pub async fn run_cggmp_dkg_party(
+    node: &NetworkNode,
+    session_prefix: &str,
+    threshold: u16,
+    primes: PregeneratedPrimes<SecurityLevel128>,
+) -> anyhow::Result<KeyShare<Secp256k1, SecurityLevel128>> {
+    let core = run_cggmp_keygen_party(node, &format!("{}/keygen", session_prefix), threshold).await?;
+    let aux = run_cggmp_aux_gen_party(node, &format!("{}/aux", session_prefix), primes).await?;
+    KeyShare::from_parts((core, aux))
+        .map_err(|e| anyhow!("Party {} combine failed: {:?}", node.id(), e))
+}
+
+/// 运行本方的 cggmp24 签名
+///
+/// **参数**:
+/// - `signers`: 签名者 party id，所有签名者必须一致 (例如都由 `selection::select_signers` 算出)。
+/// - `share`: 本方的 KeyShare，`core.i` 必须等于本方在 `node.parties()` 中的位置。
+///
+/// **返回**: (r, s, v)，所有签名者得到相同的签名。
+pub async fn run_cggmp_signing_party(
+    node: &NetworkNode,
+    session_id: &str,
+    share: &KeyShare<Secp256k1, SecurityLevel128>,
+    signers: &[u16],
+    message_hash_bytes: [u8; 32],
+) -> anyhow::Result<([u8; 32], [u8; 32], u8)> {
+    let link = node.session(session_id, signers)?;
+    let participants = link
+        .participants()
+        .iter()
+        .map(|&id| committee_index(node, id))
+        .collect::<anyhow::Result<Vec<u16>>>()?;
+    let my_index = committee_index(node, node.id())?;
+    if share.core.i != my_index {
+        return Err(anyhow!(
+            "Party {} 的 KeyShare 索引 {} 与委员会位置 {} 不一致",
+            node.id(),
+            share.core.i,
+            my_index
+        ));
+    }
+    // 第一个参数是本方在签名者中的索引，participants 中是 keygen 时的索引
+    let i = participants
+        .iter()
+        .position(|&p| p == my_index)
+        .context("Signer index")? as u16;
+    eprintln!(
+        "      [NET] Party {} signing with participants {:?}...",
+        node.id(),
+        link.participants()
+    );
+
+    type E = Secp256k1;
+    let message_scalar =
+        cggmp24::generic_ec::Scalar::<E>::from_be_bytes_mod_order(&message_hash_bytes);
+    let prehashed_message = cggmp24::signing::PrehashedDataToSign::from_scalar(message_scalar);
+    let signature = cggmp24::signing(ExecutionId::new(session_id.as_bytes()), i, &participants, share)
+        .set_digest::<Sha256>()
+        .sign(
+            &mut OsRng,
+            MpcParty::connected(round_based_delivery(link)),
+            &prehashed_message,
+        )
+        .await
+        .map_err(|e| anyhow!("Party {} signing failed: {:?}", node.id(), e))?;
+
+    let r: [u8; 32] = signature.r.to_be_bytes().as_ref().try_into()?;
+    let s: [u8; 32] = signature.s.to_be_bytes().as_ref().try_into()?;
+    let v = calc_recovery_id_from_pubkey(
+        &r,
+        &s,
+        &message_hash_bytes,
+        &share.shared_public_key.to_bytes(false),
+    )?;
+    Ok((r, s, v))
+}
@@ -0,0 +1,96 @@
+//! 消息封装与分帧 (Envelope & Framing)
+//!
+//! 每条协议消息装在一个 [`Envelope`] 中，按 `4 字节大端长度 || JSON` 的格式写入字节流。
+//! 协议消息本身 (cggmp24 / manul) 先序列化为字节放在 `payload` 中，传输层与中继都不解析其内容。
+//!
+//! 直连时每帧是一个 `Envelope`；经中继时，参与方发出 [`Routed`] (附带接收方列表)，
+//! 中继按列表转发其中的 `Envelope`。直连的第一帧是 [`Hello`]；连接中继时先完成
+//! `network::relay` 中经身份密钥签名的挑战应答握手。
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::io;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// 单帧最大长度。cggmp24 AuxGen 的 Paillier 证明序列化后约为数 MB，这里留足余量。
+pub const MAX_FRAME_LEN: usize = 64 * 1024 * 1024;
+
+/// 一条在参与方之间传递的协议消息
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    /// 会话 id，同一连接上的多个协议会话按它分发
+    pub session: String,
+    /// 发送方 party id
+    pub sender: u16,
+    /// 接收方 party id，`None` 表示广播
+    pub recipient: Option<u16>,
+    /// 协议是否要求可靠广播 (只对广播有意义，原样转交给协议层)
+    #[serde(default)]
+    pub reliable: bool,
+    /// 序列化后的协议消息
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub payload: Vec<u8>,
+}
+
+impl Envelope {
+    pub fn is_broadcast(&self) -> bool {
+        self.recipient.is_none()
+    }
+}
+
+/// 直连时发送的第一帧，声明本方 party id
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hello {
+    pub party: u16,
+}
+
+/// 发给中继的帧: 一条消息及其接收方列表 (广播时为会话中除发送方外的所有参与方)
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Routed {
+    pub to: Vec<u16>,
+    pub envelope: Envelope,
+}
+
+/// 写入一帧
+pub async fn write_frame<W, T>(writer: &mut W, value: &T) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let bytes = serde_json::to_vec(value).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if bytes.len() > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("frame too large: {} bytes", bytes.len()),
+        ));
+    }
+    writer.write_all(&(bytes.len() as u32).to_be_bytes()).await?;
+    writer.write_all(&bytes).await?;
+    writer.flush().await
+}
+
+/// 读取一帧；对端正常关闭连接时返回 `Ok(None)`
+pub async fn read_frame<R, T>(reader: &mut R) -> io::Result<Option<T>>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut len_bytes = [0u8; 4];
+    match reader.read_exact(&mut len_bytes).await {
+        Ok(_) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame too large: {} bytes", len),
+        ));
+    }
+    let mut bytes = vec![0u8; len];
+    reader.read_exact(&mut bytes).await?;
+    serde_json::from_slice(&bytes)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
@@ -0,0 +1,35 @@
+//! 网络传输层 (Network)
+//!
+//! `simulation` 把所有参与方放在同一进程中；本模块让每个参与方作为独立进程运行，
+//! 协议消息经 TCP 在进程之间传递。
+//!
+//! - [`frame`]: 消息封装 [`Envelope`] 与长度前缀分帧。
+//! - [`node`]: [`NetworkNode`] 建立连接并按会话 id 分发消息，[`SessionLink`] 是单个协议会话的通道。
+//! - [`relay`]: 中继服务 [`Relay`]，参与方无法直连时按会话 id 与 party id 转发消息。
+//! - [`secure`]: 经身份密钥认证的 X25519 密钥交换与 P2P 消息端到端加密 (XChaCha20-Poly1305)。
+//! - [`delivery`]: 把 `SessionLink` 适配成 `round_based` 的 delivery。
+//! - [`cggmp`]: 每个进程只运行本方的 cggmp24 keygen / aux_info_gen / signing。
+//! - [`synedrion`]: 在网络上驱动本方的 manul 会话，替代进程内的 `manul::dev::tokio::run_async`。
+//!
+//! 完整的多进程演示见 `examples/network_cggmp.rs` 与 `examples/network_synedrion.rs`，
+//! 经中继运行见 `examples/network_relay.rs`，端到端加密见 `examples/e2e_encryption.rs`。
+
+pub mod frame;
+pub mod node;
+pub mod relay;
+pub mod secure;
+pub mod delivery;
+#[cfg(feature = "cggmp24")]
+pub mod cggmp;
+#[cfg(feature = "synedrion")]
+pub mod synedrion;
+
+pub use frame::*;
+pub use node::*;
+pub use relay::*;
+pub use secure::*;
+pub use delivery::*;
+#[cfg(feature = "cggmp24")]
+pub use cggmp::*;
+#[cfg(feature = "synedrion")]
+pub use synedrion::*;
@@ -0,0 +1,580 @@
+//! 网络节点 (Network Node)
+//!
+//! [`NetworkNode`] 代表本进程中的一个参与方，负责与其他参与方建立连接，并按会话 id 分发收到的消息。
+//! 每次协议运行 (keygen / aux_info_gen / signing ...) 通过 [`NetworkNode::session`] 打开一个
+//! [`SessionLink`]，多个会话复用同一组连接。
+//!
+//! **连接方式**:
+//! - [`NetworkNode::connect_mesh`]: 参与方之间建立 TCP 全连接。每方监听自己的地址，
+//!   主动连接 id 更小的参与方，接受 id 更大的参与方的连接。
+//! - [`NetworkNode::connect_relay`]: 只连接一个中继 (`network::relay`)，适用于参与方之间无法直连 (NAT) 的场景。
+//!
+//! **加密**: 连接后、打开任何协议会话之前调用 [`NetworkNode::enable_encryption`]，此后打开的会话中
+//! 所有 P2P 消息都经 `network::secure` 端到端加密，广播消息带有身份私钥的签名；中继与窃听者看不到
+//! P2P 内容，篡改、伪造或重放会被接收方发现。
+//!
+//! **注意**: 直连的握手只声明 party id，不做身份认证；连接中继时用身份私钥回应中继的挑战，
+//! 中继只接受登记的参与方。未启用加密时消息的机密性与完整性需要由上层保证。
+
+use super::frame::{read_frame, write_frame, Envelope, Hello, Routed};
+use super::relay::{RelayChallenge, RelayHello};
+use super::secure::{KeyAnnouncement, KeyExchange, SecureChannels, SessionOpener, SessionSealer};
+
+use anyhow::{anyhow, Context};
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// 等待其他参与方上线的最长时间
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(200);
+/// 交换 X25519 公钥声明所用的会话 id
+const KEY_EXCHANGE_SESSION: &str = "__e2e/key-exchange";
+
+/// 按会话 id 分发消息。会话尚未打开时先缓存，打开后一次性交付。
+#[derive(Default)]
+struct Router {
+    sessions: HashMap<String, mpsc::UnboundedSender<Envelope>>,
+    pending: HashMap<String, Vec<Envelope>>,
+    live_connections: usize,
+}
+
+impl Router {
+    fn deliver(&mut self, envelope: Envelope) {
+        match self.sessions.get(&envelope.session) {
+            Some(tx) => {
+                // 会话已结束 (接收端已丢弃) 时直接丢弃迟到的消息
+                let _ = tx.send(envelope);
+            }
+            None => self
+                .pending
+                .entry(envelope.session.clone())
+                .or_default()
+                .push(envelope),
+        }
+    }
+
+    fn open(&mut self, session: &str) -> mpsc::UnboundedReceiver<Envelope> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        for envelope in self.pending.remove(session).unwrap_or_default() {
+            let _ = tx.send(envelope);
+        }
+        // 所有连接都已断开时不再登记会话: tx 随即被丢弃，接收端读完缓存后结束
+        if self.live_connections > 0 {
+            self.sessions.insert(session.to_string(), tx);
+        }
+        rx
+    }
+
+    fn connection_closed(&mut self) {
+        self.live_connections = self.live_connections.saturating_sub(1);
+        if self.live_connections == 0 {
+            // 丢弃所有发送端，正在等待消息的会话读完缓存后结束，而不是永远挂起
+            self.sessions.clear();
+        }
+    }
+}
+
+/// 本进程中的一个参与方
+pub struct NetworkNode {
+    my_id: u16,
+    parties: Vec<u16>,
+    outbound: mpsc::UnboundedSender<Routed>,
+    router: Arc<Mutex<Router>>,
+    tasks: Vec<JoinHandle<()>>,
+    secure: Option<Arc<SecureChannels>>,
+}
+
+impl NetworkNode {
+    /// 与 `peers` 中的所有参与方建立 TCP 全连接
+    ///
+    /// **参数**:
+    /// - `my_id`: 本方 party id，必须出现在 `peers` 中。
+    /// - `peers`: 所有参与方 (包括本方) 的 party id -> 监听地址，所有参与方必须一致。
+    ///
+    /// **返回**: 所有连接建立完成后的节点；超过 [`CONNECT_TIMEOUT`] 仍有参与方未连上时报错。
+    pub async fn connect_mesh(
+        my_id: u16,
+        peers: &BTreeMap<u16, SocketAddr>,
+    ) -> anyhow::Result<Self> {
+        let my_addr = *peers
+            .get(&my_id)
+            .ok_or_else(|| anyhow!("Party {} 不在参与方列表 {:?} 中", my_id, peers.keys()))?;
+        let listener = TcpListener::bind(my_addr)
+            .await
+            .with_context(|| format!("Party {} failed to listen on {}", my_id, my_addr))?;
+        let deadline = Instant::now() + CONNECT_TIMEOUT;
+
+        let mut connections = BTreeMap::new();
+        // 1. 主动连接 id 更小的参与方
+        for (&peer, &addr) in peers.range(..my_id) {
+            let mut stream = dial(addr, deadline)
+                .await
+                .with_context(|| format!("Party {} failed to connect to Party {} ({})", my_id, peer, addr))?;
+            write_frame(&mut stream, &Hello { party: my_id }).await?;
+            connections.insert(peer, stream);
+        }
+        // 2. 接受 id 更大的参与方的连接
+        while connections.len() < peers.len() - 1 {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let (mut stream, addr) = tokio::time::timeout(remaining, listener.accept())
+                .await
+                .map_err(|_| {
+                    anyhow!(
+                        "Party {} 等待连接超时: 已连接 {:?}，还缺 {} 个参与方",
+                        my_id,
+                        connections.keys().collect::<Vec<_>>(),
+                        peers.len() - 1 - connections.len()
+                    )
+                })??;
+            let hello: Hello = read_frame(&mut stream)
+                .await?
+                .ok_or_else(|| anyhow!("{} 在握手前断开", addr))?;
+            if hello.party <= my_id || !peers.contains_key(&hello.party) {
+                eprintln!("      [NET] 拒绝来自 {} 的连接: 非法 party id {}", addr, hello.party);
+                continue;
+            }
+            if connections.contains_key(&hello.party) {
+                eprintln!("      [NET] 拒绝来自 {} 的重复连接 (Party {})", addr, hello.party);
+                continue;
+            }
+            connections.insert(hello.party, stream);
+        }
+        eprintln!(
+            "      [NET] Party {} 已连接全部 {} 个参与方",
+            my_id,
+            connections.len()
+        );
+
+        let router = Arc::new(Mutex::new(Router {
+            live_connections: connections.len(),
+            ..Router::default()
+        }));
+        let mut tasks = Vec::new();
+        let mut writers = BTreeMap::new();
+        for (peer, stream) in connections {
+            stream.set_nodelay(true)?;
+            let (read_half, write_half) = stream.into_split();
+            let (tx, rx) = mpsc::unbounded_channel();
+            writers.insert(peer, tx);
+            tasks.push(tokio::spawn(write_loop(write_half, rx)));
+            tokio::spawn(read_loop(read_half, Some(peer), router.clone()));
+        }
+
+        // 3. 出站消息按接收方列表分发到各连接
+        let (outbound, mut outbound_rx) = mpsc::unbounded_channel::<Routed>();
+        tasks.push(tokio::spawn(async move {
+            while let Some(routed) = outbound_rx.recv().await {
+                for peer in &routed.to {
+                    match writers.get(peer) {
+                        Some(tx) => {
+                            let _ = tx.send(routed.envelope.clone());
+                        }
+                        None => eprintln!("      [NET] 丢弃发往未知参与方 {} 的消息", peer),
+                    }
+                }
+            }
+        }));
+
+        Ok(Self {
+            my_id,
+            parties: peers.keys().copied().collect(),
+            outbound,
+            router,
+            tasks,
+            secure: None,
+        })
+    }
+
+    /// 通过中继连接其他参与方
+    ///
+    /// **参数**:
+    /// - `parties`: 所有参与方 (包括本方) 的 party id，所有参与方必须一致。
+    /// - `relay_addr`: 中继地址。中继按 `Envelope` 头部的会话 id 与接收方转发，不解析 `payload`。
+    /// - `identity`: 本节点的身份私钥，用于回应中继的握手挑战；对应的公钥必须在中继的登记表中。
+    ///
+    /// 中继暂时不可达时在 [`CONNECT_TIMEOUT`] 内重试。
+    pub async fn connect_relay(
+        my_id: u16,
+        parties: &[u16],
+        relay_addr: SocketAddr,
+        identity: &SigningKey,
+    ) -> anyhow::Result<Self> {
+        let mut parties = parties.to_vec();
+        parties.sort();
+        parties.dedup();
+        if !parties.contains(&my_id) {
+            return Err(anyhow!("Party {} 不在参与方列表 {:?} 中", my_id, parties));
+        }
+        let mut stream = dial(relay_addr, Instant::now() + CONNECT_TIMEOUT)
+            .await
+            .with_context(|| format!("Party {} failed to connect to relay {}", my_id, relay_addr))?;
+        stream.set_nodelay(true)?;
+        let challenge: RelayChallenge = read_frame(&mut stream)
+            .await?
+            .ok_or_else(|| anyhow!("中继 {} 在握手前断开", relay_addr))?;
+        write_frame(&mut stream, &RelayHello::sign(my_id, &challenge, identity)).await?;
+        eprintln!("      [NET] Party {} 已连接中继 {}", my_id, relay_addr);
+
+        let router = Arc::new(Mutex::new(Router {
+            live_connections: 1,
+            ..Router::default()
+        }));
+        let (read_half, write_half) = stream.into_split();
+        // 中继负责校验发送方，这里不再按连接检查
+        tokio::spawn(read_loop(read_half, None, router.clone()));
+        let (outbound, outbound_rx) = mpsc::unbounded_channel::<Routed>();
+        let tasks = vec![tokio::spawn(write_loop(write_half, outbound_rx))];
+
+        Ok(Self {
+            my_id,
+            parties,
+            outbound,
+            router,
+            tasks,
+            secure: None,
+        })
+    }
+
+    /// 与所有参与方交换经身份密钥签名的 X25519 公钥，之后打开的会话对 P2P 消息端到端加密
+    ///
+    /// **参数**:
+    /// - `identity`: 本节点的身份私钥 (例如 `NodeSigner::signing_key`)。
+    /// - `registry`: 所有参与方的 party id -> 身份公钥，必须包含本方且与 `identity` 一致。
+    ///
+    /// **注意**: 所有参与方都必须调用，且必须在打开任何协议会话之前调用: 已经打开过会话时报错
+    /// (这些会话不会加密，也不会验证广播签名)。超过 [`CONNECT_TIMEOUT`] 仍未收齐声明时报错。
+    pub async fn enable_encryption(
+        &mut self,
+        identity: &SigningKey,
+        registry: &BTreeMap<u16, VerifyingKey>,
+    ) -> anyhow::Result<()> {
+        if self.secure.is_some() {
+            return Err(anyhow!("Party {} 已经启用加密", self.my_id));
+        }
+        if registry.get(&self.my_id) != Some(identity.verifying_key()) {
+            return Err(anyhow!(
+                "Party {} 的身份私钥与登记表中的公钥不一致",
+                self.my_id
+            ));
+        }
+        if let Some(id) = self.parties.iter().find(|id| !registry.contains_key(id)) {
+            return Err(anyhow!("Party {} 不在身份公钥登记表中", id));
+        }
+        let opened: Vec<String> = self
+            .router
+            .lock()
+            .map_err(|_| anyhow!("Router lock poisoned"))?
+            .sessions
+            .keys()
+            .filter(|session| session.as_str() != KEY_EXCHANGE_SESSION)
+            .cloned()
+            .collect();
+        if !opened.is_empty() {
+            return Err(anyhow!(
+                "Party {} 在启用加密前已打开会话 {:?}，这些会话不受保护；必须先启用加密再打开会话",
+                self.my_id,
+                opened
+            ));
+        }
+
+        let exchange = KeyExchange::new(self.my_id);
+        let mut link = self.session(KEY_EXCHANGE_SESSION, &self.parties)?;
+        let announcement = serde_json::to_vec(&exchange.announcement(identity))?;
+        link.send(None, true, announcement)?;
+        let mut announcements = BTreeMap::new();
+        let receive_all = async {
+            while announcements.len() < self.parties.len() - 1 {
+                let envelope = link
+                    .recv()
+                    .await
+                    .ok_or_else(|| anyhow!("Party {} 交换密钥时连接已断开", self.my_id))??;
+                let announcement: KeyAnnouncement = serde_json::from_slice(&envelope.payload)
+                    .with_context(|| format!("Party {} 的公钥声明无法解析", envelope.sender))?;
+                if announcement.party != envelope.sender {
+                    return Err(anyhow!(
+                        "Party {} 发送了 Party {} 的公钥声明",
+                        envelope.sender,
+                        announcement.party
+                    ));
+                }
+                announcements.insert(envelope.sender, announcement);
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+        tokio::time::timeout(CONNECT_TIMEOUT, receive_all)
+            .await
+            .map_err(|_| anyhow!("Party {} 等待公钥声明超时", self.my_id))??;
+
+        let channels = exchange.finish(identity, announcements.into_values(), registry)?;
+        self.secure = Some(Arc::new(channels));
+        eprintln!(
+            "      [NET] Party {} 已与 {} 个参与方建立加密通道",
+            self.my_id,
+            self.parties.len() - 1
+        );
+        Ok(())
+    }
+
+    pub fn id(&self) -> u16 {
+        self.my_id
+    }
+
+    /// 所有参与方的 party id (升序)
+    pub fn parties(&self) -> &[u16] {
+        &self.parties
+    }
+
+    /// 打开一个协议会话
+    ///
+    /// **参数**:
+    /// - `session_id`: 所有参与方必须使用相同的 id，且每次协议运行都不同。
+    /// - `participants`: 本次会话的参与方 (例如签名者集合)，必须包含本方。
+    ///
+    /// 在打开之前已经到达的消息会被缓存并立即交付。已启用加密时，会话中的 P2P 消息自动加解密，
+    /// 广播自动签名与验证。
+    pub fn session(&self, session_id: &str, participants: &[u16]) -> anyhow::Result<SessionLink> {
+        let mut participants = participants.to_vec();
+        participants.sort();
+        participants.dedup();
+        if !participants.contains(&self.my_id) {
+            return Err(anyhow!(
+                "Party {} 不在会话 {} 的参与方 {:?} 中",
+                self.my_id,
+                session_id,
+                participants
+            ));
+        }
+        if let Some(id) = participants.iter().find(|id| !self.parties.contains(id)) {
+            return Err(anyhow!("会话 {} 的参与方 {} 未连接", session_id, id));
+        }
+        let inbound = self
+            .router
+            .lock()
+            .map_err(|_| anyhow!("Router lock poisoned"))?
+            .open(session_id);
+        let (sealer, opener) = match &self.secure {
+            Some(secure) => {
+                let (sealer, opener) = secure.session(session_id);
+                (Some(sealer), Some(opener))
+            }
+            None => (None, None),
+        };
+        Ok(SessionLink {
+            sender: SessionSender {
+                session: session_id.to_string(),
+                my_id: self.my_id,
+                participants: participants.clone(),
+                outbound: self.outbound.clone(),
+                sealer,
+            },
+            receiver: SessionReceiver {
+                session: session_id.to_string(),
+                participants,
+                inbound,
+                opener,
+            },
+        })
+    }
+
+    /// 等待已发出的消息全部写入连接后关闭节点
+    ///
+    /// 进程退出前必须调用，否则最后一轮消息可能还在发送队列中。调用前应先丢弃所有 `SessionLink`。
+    pub async fn shutdown(self) {
+        drop(self.outbound);
+        for task in self.tasks {
+            let _ = task.await;
+        }
+    }
+}
+
+/// 一个协议会话的双向通道
+pub struct SessionLink {
+    sender: SessionSender,
+    receiver: SessionReceiver,
+}
+
+impl SessionLink {
+    pub fn session_id(&self) -> &str {
+        &self.sender.session
+    }
+
+    pub fn my_id(&self) -> u16 {
+        self.sender.my_id
+    }
+
+    /// 本次会话的参与方 (升序)，位置即 round_based 的协议索引
+    pub fn participants(&self) -> &[u16] {
+        &self.receiver.participants
+    }
+
+    pub fn send(&self, recipient: Option<u16>, reliable: bool, payload: Vec<u8>) -> io::Result<()> {
+        self.sender.send(recipient, reliable, payload)
+    }
+
+    pub async fn recv(&mut self) -> Option<io::Result<Envelope>> {
+        self.receiver.recv().await
+    }
+
+    pub fn split(self) -> (SessionSender, SessionReceiver) {
+        (self.sender, self.receiver)
+    }
+}
+
+/// 会话的发送端
+#[derive(Clone)]
+pub struct SessionSender {
+    session: String,
+    my_id: u16,
+    participants: Vec<u16>,
+    outbound: mpsc::UnboundedSender<Routed>,
+    sealer: Option<SessionSealer>,
+}
+
+impl SessionSender {
+    /// 发送一条消息，`recipient` 为 `None` 时广播给会话中的其他参与方
+    ///
+    /// 已启用加密时 P2P 消息的 `payload` 在发送前加密，广播在发送前用身份私钥签名。
+    pub fn send(&self, recipient: Option<u16>, reliable: bool, payload: Vec<u8>) -> io::Result<()> {
+        let to = match recipient {
+            Some(id) if self.participants.contains(&id) => vec![id],
+            Some(id) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Party {} is not in session {}", id, self.session),
+                ))
+            }
+            None => self
+                .participants
+                .iter()
+                .copied()
+                .filter(|&id| id != self.my_id)
+                .collect(),
+        };
+        let payload = match (recipient, &self.sealer) {
+            (Some(id), Some(sealer)) => sealer
+                .seal(id, reliable, &payload)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, format!("{:#}", e)))?,
+            (None, Some(sealer)) => sealer.sign_broadcast(reliable, &payload),
+            (_, None) => payload,
+        };
+        self.outbound
+            .send(Routed {
+                to,
+                envelope: Envelope {
+                    session: self.session.clone(),
+                    sender: self.my_id,
+                    recipient,
+                    reliable,
+                    payload,
+                },
+            })
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "network node is shut down"))
+    }
+}
+
+/// 会话的接收端
+pub struct SessionReceiver {
+    session: String,
+    participants: Vec<u16>,
+    inbound: mpsc::UnboundedReceiver<Envelope>,
+    opener: Option<SessionOpener>,
+}
+
+impl SessionReceiver {
+    pub fn participants(&self) -> &[u16] {
+        &self.participants
+    }
+
+    /// 接收下一条消息；所有连接断开且缓存读完后返回 `None`
+    ///
+    /// 来自会话参与方之外的消息会被丢弃。已启用加密时返回解密后的 P2P 消息与验证过签名的广播；
+    /// 解密或验证失败 (被篡改、重放、伪造或未加密) 时返回 `InvalidData` 错误，由协议层决定是否中止。
+    pub async fn recv(&mut self) -> Option<io::Result<Envelope>> {
+        loop {
+            let mut envelope = self.inbound.recv().await?;
+            if self.participants.contains(&envelope.sender) {
+                if let Some(opener) = self.opener.as_mut() {
+                    let opened = if envelope.is_broadcast() {
+                        opener.open_broadcast(&envelope)
+                    } else {
+                        opener.open(&envelope)
+                    };
+                    match opened {
+                        Ok(plaintext) => envelope.payload = plaintext,
+                        Err(e) => {
+                            return Some(Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                format!("会话 {}: {:#}", self.session, e),
+                            )))
+                        }
+                    }
+                }
+                return Some(Ok(envelope));
+            }
+            eprintln!(
+                "      [NET] 会话 {} 丢弃来自非参与方 {} 的消息",
+                self.session, envelope.sender
+            );
+        }
+    }
+}
+
+async fn dial(addr: SocketAddr, deadline: Instant) -> io::Result<TcpStream> {
+    loop {
+        match TcpStream::connect(addr).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) if Instant::now() >= deadline => return Err(e),
+            Err(_) => tokio::time::sleep(CONNECT_RETRY_INTERVAL).await,
+        }
+    }
+}
+
+async fn write_loop<T: Serialize>(mut writer: OwnedWriteHalf, mut rx: mpsc::UnboundedReceiver<T>) {
+    use tokio::io::AsyncWriteExt;
+
+    while let Some(frame) = rx.recv().await {
+        if let Err(e) = write_frame(&mut writer, &frame).await {
+            eprintln!("      [NET] 发送失败: {}", e);
+            return;
+        }
+    }
+    let _ = writer.shutdown().await;
+}
+
+/// `peer`: 直连时为对端 party id；经中继时为 `None`
+async fn read_loop(mut reader: OwnedReadHalf, peer: Option<u16>, router: Arc<Mutex<Router>>) {
+    loop {
+        match read_frame::<_, Envelope>(&mut reader).await {
+            Ok(Some(envelope)) => {
+                // 连接握手已确定对端 party id，冒充其他参与方的消息直接断开
+                if peer.is_some_and(|peer| envelope.sender != peer) {
+                    eprintln!(
+                        "      [NET] Party {:?} 的连接上出现发送方 {}，断开连接",
+                        peer, envelope.sender
+                    );
+                    break;
+                }
+                if let Ok(mut router) = router.lock() {
+                    router.deliver(envelope);
+                }
+            }
+            Ok(None) => break,
+            Err(e) => {
+                eprintln!("      [NET] 读取 Party {:?} 的消息失败: {}", peer, e);
+                break;
+            }
+        }
+    }
+    if let Ok(mut router) = router.lock() {
+        router.connection_closed();
+    }
+}
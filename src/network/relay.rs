@@ -0,0 +1,457 @@
+//! 消息中继 (Relay)
+//!
+//! 参与方位于 NAT 之后、无法互相直连时，所有参与方只连接中继，由中继转发协议消息。
+//! `round_based` (cggmp24) 与 manul (Synedrion) 的会话都经 [`NetworkNode::connect_relay`] 使用同一个中继。
+//!
+//! **握手**: 中继只接受身份公钥登记表中的参与方。连接建立后中继发送一次性随机挑战 [`RelayChallenge`]，
+//! 参与方回复 [`RelayHello`]: party id 与身份私钥对 `域标签 || 挑战 || party id` 的签名。
+//! 签名无效、party id 未登记或同一 party id 已经连接时断开。
+//!
+//! **路由**: 握手后每帧是一个 [`Routed`]。中继按 `envelope.session` 统计会话，按 `to` 中的 party id
+//! 把 `Envelope` 转发到对应连接；接收方尚未连接时先排队，连接后一次性交付。发往未登记 party id 的
+//! 消息直接丢弃，因此排队占用的内存只与登记的参与方数量有关。写入连接失败时，尚未送达的消息重新排队。
+//! 某个接收方的队列已满时中继不丢弃已排队的消息，而是拒绝新消息并断开发送方。
+//!
+//! **中继看到的内容**: 只有 `Envelope` 头部 (会话 id、发送方、接收方、长度)。`payload` 原样转发，
+//! 中继从不解析它。参与方启用 `NetworkNode::enable_encryption` 后 P2P 消息的 `payload` 是密文，
+//! 中继篡改或重放它会被接收方发现 (见 `network::secure`)。
+//!
+//! [`NetworkNode::connect_relay`]: super::NetworkNode::connect_relay
+
+use super::frame::{read_frame, write_frame, Envelope, Routed};
+
+use anyhow::{anyhow, Context};
+use k256::ecdsa::signature::{Signer, Verifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::tcp::OwnedWriteHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// 每个未连接的参与方最多排队的消息数，队列已满时拒绝新消息并断开发送方
+pub const MAX_QUEUED_PER_PARTY: usize = 4096;
+/// 每个未连接的参与方最多排队的 payload 总字节数，队列已满时拒绝新消息并断开发送方
+pub const MAX_QUEUED_BYTES_PER_PARTY: usize = 256 * 1024 * 1024;
+const HELLO_TIMEOUT: Duration = Duration::from_secs(10);
+const HELLO_DOMAIN: &[u8] = b"my-mpc-bridge/relay/hello/v1";
+const CHALLENGE_LEN: usize = 32;
+
+/// 中继接受连接后发送的第一帧: 一次性随机挑战
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RelayChallenge {
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub nonce: Vec<u8>,
+}
+
+/// 参与方对挑战的回复: party id 与身份私钥的签名
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RelayHello {
+    pub party: u16,
+    /// 64 字节 (r || s) 的 ECDSA 签名
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl RelayHello {
+    fn signed_message(nonce: &[u8], party: u16) -> Vec<u8> {
+        [HELLO_DOMAIN, nonce, &party.to_be_bytes()].concat()
+    }
+
+    /// 用本节点身份私钥回复中继的挑战
+    pub fn sign(party: u16, challenge: &RelayChallenge, identity: &SigningKey) -> Self {
+        let signature: Signature = identity.sign(&Self::signed_message(&challenge.nonce, party));
+        Self {
+            party,
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// 用登记表中 `self.party` 的身份公钥验证对 `challenge` 的签名
+    pub fn verify(&self, challenge: &RelayChallenge, identity: &VerifyingKey) -> anyhow::Result<()> {
+        let signature = Signature::from_slice(&self.signature)
+            .map_err(|e| anyhow!("Party {} 的握手签名格式错误: {}", self.party, e))?;
+        identity
+            .verify(&Self::signed_message(&challenge.nonce, self.party), &signature)
+            .map_err(|_| anyhow!("Party {} 的握手签名无效", self.party))
+    }
+}
+
+/// 读取身份公钥登记表 (`{"<party id>": "<公钥 hex>"}`，与 `identity::save_verifiers` 的格式相同)
+pub fn load_registry(path: impl AsRef<Path>) -> anyhow::Result<BTreeMap<u16, VerifyingKey>> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read verifier registry {}", path.display()))?;
+    let registry: BTreeMap<u16, String> = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse verifier registry {}", path.display()))?;
+    registry
+        .into_iter()
+        .map(|(id, public_hex)| {
+            let bytes = hex::decode(crate::bridge::strip_0x(&public_hex))?;
+            let key = VerifyingKey::from_sec1_bytes(&bytes)
+                .map_err(|e| anyhow!("Invalid public key for Party {}: {}", id, e))?;
+            Ok((id, key))
+        })
+        .collect()
+}
+
+/// 单个会话经过中继的消息统计
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionStats {
+    pub broadcast: u64,
+    pub p2p: u64,
+    /// 转发的 payload 总字节数 (每个接收方各计一次)
+    pub payload_bytes: u64,
+    /// 在该会话中发送过消息的参与方
+    pub senders: BTreeSet<u16>,
+}
+
+/// 一个未连接参与方的待交付消息
+#[derive(Default)]
+struct PartyQueue {
+    envelopes: VecDeque<Envelope>,
+    bytes: usize,
+}
+
+impl PartyQueue {
+    fn push_back(&mut self, envelope: Envelope) {
+        self.bytes += envelope.payload.len();
+        self.envelopes.push_back(envelope);
+    }
+
+    fn push_front(&mut self, envelope: Envelope) {
+        self.bytes += envelope.payload.len();
+        self.envelopes.push_front(envelope);
+    }
+
+    /// 放入 `envelope` 后是否仍在条数与字节数上限之内
+    fn has_room_for(&self, envelope: &Envelope) -> bool {
+        self.envelopes.len() < MAX_QUEUED_PER_PARTY
+            && self.bytes + envelope.payload.len() <= MAX_QUEUED_BYTES_PER_PARTY
+    }
+}
+
+#[derive(Default)]
+struct RelayState {
+    /// party id -> 身份公钥，只接受并转发给其中的参与方
+    registry: BTreeMap<u16, VerifyingKey>,
+    /// party id -> (连接序号, 发送队列)
+    connections: HashMap<u16, (u64, mpsc::UnboundedSender<Envelope>)>,
+    queued: HashMap<u16, PartyQueue>,
+    stats: BTreeMap<String, SessionStats>,
+    next_connection: u64,
+    #[cfg(any(test, feature = "relay-testing"))]
+    interceptor: Option<Box<dyn FnMut(&mut Envelope) + Send>>,
+}
+
+impl RelayState {
+    /// 检查 `recipients` 的队列能否再放入 `envelope`；任一队列已满时整条消息都不转发
+    fn check_capacity(
+        &self,
+        recipients: &BTreeSet<u16>,
+        envelope: &Envelope,
+    ) -> anyhow::Result<()> {
+        for to in recipients {
+            let room = match self.queued.get(to) {
+                Some(queue) => queue.has_room_for(envelope),
+                None => PartyQueue::default().has_room_for(envelope),
+            };
+            if !room {
+                return Err(anyhow!(
+                    "Party {} 的待交付队列已满，拒绝 Party {} 的消息",
+                    to,
+                    envelope.sender
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 转发给已登记的 `to`；调用方负责过滤未登记的 party id
+    fn forward(&mut self, to: u16, envelope: Envelope) {
+        if let Some((_, tx)) = self.connections.get(&to) {
+            if let Err(mpsc::error::SendError(envelope)) = tx.send(envelope) {
+                self.enqueue(to, envelope);
+            }
+            return;
+        }
+        self.enqueue(to, envelope);
+    }
+
+    /// 交给测试用的拦截器检查或修改
+    #[cfg(any(test, feature = "relay-testing"))]
+    fn intercepted(&mut self, mut envelope: Envelope) -> Envelope {
+        if let Some(interceptor) = self.interceptor.as_mut() {
+            interceptor(&mut envelope);
+        }
+        envelope
+    }
+
+    /// 放入 `to` 的队列；调用方已用 `check_capacity` 检查过上限
+    fn enqueue(&mut self, to: u16, envelope: Envelope) {
+        self.queued.entry(to).or_default().push_back(envelope);
+    }
+
+    /// 把写入失败、尚未送达的消息放回队首 (保持原有顺序，排在之后到达的消息前面)
+    ///
+    /// 这些消息此前已被接受，放回时不受上限约束；队列超出上限期间新消息会被拒绝。
+    fn requeue(&mut self, to: u16, undelivered: Vec<Envelope>) {
+        if undelivered.is_empty() {
+            return;
+        }
+        eprintln!(
+            "      [RELAY] {} 条消息未送达 Party {}，重新排队",
+            undelivered.len(),
+            to
+        );
+        let queue = self.queued.entry(to).or_default();
+        for envelope in undelivered.into_iter().rev() {
+            queue.push_front(envelope);
+        }
+    }
+
+    /// 注销连接 (只注销 `connection` 本身，不影响之后的重连)
+    fn unregister(&mut self, party: u16, connection: u64) {
+        if self
+            .connections
+            .get(&party)
+            .is_some_and(|(c, _)| *c == connection)
+        {
+            self.connections.remove(&party);
+        }
+    }
+}
+
+/// 运行中的中继；丢弃时停止接受新连接
+pub struct Relay {
+    local_addr: SocketAddr,
+    state: Arc<Mutex<RelayState>>,
+    task: JoinHandle<()>,
+}
+
+impl Relay {
+    /// 监听 `addr` 并开始转发 (端口为 0 时由系统分配，见 [`Relay::local_addr`])
+    ///
+    /// **参数**:
+    /// - `registry`: 允许连接的参与方 party id -> 身份公钥。握手时用它验证参与方的签名，
+    ///   发往登记表之外的 party id 的消息不会排队。
+    pub async fn bind(
+        addr: SocketAddr,
+        registry: BTreeMap<u16, VerifyingKey>,
+    ) -> anyhow::Result<Self> {
+        if registry.is_empty() {
+            return Err(anyhow!("Relay registry is empty"));
+        }
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Relay failed to listen on {}", addr))?;
+        let local_addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(RelayState {
+            registry,
+            ..RelayState::default()
+        }));
+        let task = tokio::spawn(accept_loop(listener, state.clone()));
+        Ok(Self {
+            local_addr,
+            state,
+            task,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// 各会话的转发统计 (按会话 id 排序)
+    pub fn stats(&self) -> BTreeMap<String, SessionStats> {
+        self.state
+            .lock()
+            .map(|state| state.stats.clone())
+            .unwrap_or_default()
+    }
+
+    /// 在转发前检查或修改每条消息，用于模拟不可信的中继 (例如验证篡改会被端到端加密发现)
+    ///
+    /// 只在测试或启用 `relay-testing` feature 时可用，生产环境的中继不能改写消息。
+    #[cfg(any(test, feature = "relay-testing"))]
+    pub fn intercept(&self, interceptor: impl FnMut(&mut Envelope) + Send + 'static) {
+        if let Ok(mut state) = self.state.lock() {
+            state.interceptor = Some(Box::new(interceptor));
+        }
+    }
+
+    /// 当前在线的参与方
+    pub fn connected_parties(&self) -> BTreeSet<u16> {
+        self.state
+            .lock()
+            .map(|state| state.connections.keys().copied().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for Relay {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn accept_loop(listener: TcpListener, state: Arc<Mutex<RelayState>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, addr)) => {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handle_client(stream, state).await {
+                        eprintln!("      [RELAY] {} 断开: {:#}", addr, e);
+                    }
+                });
+            }
+            Err(e) => eprintln!("      [RELAY] accept 失败: {}", e),
+        }
+    }
+}
+
+/// 发送挑战并验证参与方的签名，返回通过认证的 party id
+async fn handshake(stream: &mut TcpStream, state: &Mutex<RelayState>) -> anyhow::Result<u16> {
+    let mut nonce = [0u8; CHALLENGE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let challenge = RelayChallenge {
+        nonce: nonce.to_vec(),
+    };
+    write_frame(stream, &challenge).await?;
+    let hello: RelayHello = tokio::time::timeout(HELLO_TIMEOUT, read_frame(stream))
+        .await
+        .map_err(|_| anyhow!("握手超时"))??
+        .ok_or_else(|| anyhow!("在握手前断开"))?;
+    let identity = state
+        .lock()
+        .map_err(|_| anyhow!("Relay lock poisoned"))?
+        .registry
+        .get(&hello.party)
+        .copied()
+        .ok_or_else(|| anyhow!("Party {} 不在身份公钥登记表中", hello.party))?;
+    hello.verify(&challenge, &identity)?;
+    Ok(hello.party)
+}
+
+async fn handle_client(mut stream: TcpStream, state: Arc<Mutex<RelayState>>) -> anyhow::Result<()> {
+    let party = handshake(&mut stream, &state).await?;
+    stream.set_nodelay(true)?;
+    let (mut reader, writer) = stream.into_split();
+
+    // 1. 登记连接，交付排队中的消息
+    let (tx, rx) = mpsc::unbounded_channel::<Envelope>();
+    let connection = {
+        let mut state = state.lock().map_err(|_| anyhow!("Relay lock poisoned"))?;
+        if state.connections.contains_key(&party) {
+            return Err(anyhow!("Party {} 已经连接", party));
+        }
+        let queue = state.queued.remove(&party).unwrap_or_default();
+        for envelope in queue.envelopes {
+            let _ = tx.send(envelope);
+        }
+        state.next_connection += 1;
+        let connection = state.next_connection;
+        state.connections.insert(party, (connection, tx));
+        connection
+    };
+    eprintln!("      [RELAY] Party {} 已连接", party);
+    let mut writer_task = tokio::spawn(write_loop(writer, rx));
+
+    // 2. 按接收方列表转发，直到连接断开或写入失败
+    let forward = async {
+        while let Some(routed) = read_frame::<_, Routed>(&mut reader).await? {
+            let Routed { to, envelope } = routed;
+            if envelope.sender != party {
+                return Err(anyhow!(
+                    "Party {} 试图以 Party {} 的身份发送消息",
+                    party,
+                    envelope.sender
+                ));
+            }
+            if let Some(recipient) = envelope.recipient {
+                if to != [recipient] {
+                    return Err(anyhow!(
+                        "P2P 消息的接收方 {} 与转发列表 {:?} 不一致",
+                        recipient,
+                        to
+                    ));
+                }
+            }
+            let mut relay = state.lock().map_err(|_| anyhow!("Relay lock poisoned"))?;
+            let (recipients, unknown): (BTreeSet<u16>, BTreeSet<u16>) = to
+                .into_iter()
+                .filter(|&id| id != party)
+                .partition(|id| relay.registry.contains_key(id));
+            if !unknown.is_empty() {
+                eprintln!(
+                    "      [RELAY] 丢弃 Party {} 发往未登记参与方 {:?} 的消息",
+                    party, unknown
+                );
+            }
+            #[cfg(any(test, feature = "relay-testing"))]
+            let envelope = relay.intercepted(envelope);
+            // 队列已满时断开发送方，而不是丢弃接收方尚未收到的消息
+            relay.check_capacity(&recipients, &envelope)?;
+            let stats = relay.stats.entry(envelope.session.clone()).or_default();
+            if envelope.is_broadcast() {
+                stats.broadcast += 1;
+            } else {
+                stats.p2p += 1;
+            }
+            stats.payload_bytes += (envelope.payload.len() * recipients.len()) as u64;
+            stats.senders.insert(party);
+            for recipient in recipients {
+                relay.forward(recipient, envelope.clone());
+            }
+        }
+        Ok(())
+    };
+    // 写入任务只会因写入失败而先结束 (发送端在注销前一直被持有)
+    let (result, undelivered) = tokio::select! {
+        result = forward => (result, None),
+        joined = &mut writer_task => (
+            Err(anyhow!("写入 Party {} 的连接失败", party)),
+            Some(joined.unwrap_or_default()),
+        ),
+    };
+
+    // 3. 注销连接，之后发往本方的消息进入队列；未送达的消息放回队首
+    if let Ok(mut state) = state.lock() {
+        state.unregister(party, connection);
+    }
+    let undelivered = match undelivered {
+        Some(undelivered) => undelivered,
+        None => writer_task.await.unwrap_or_default(),
+    };
+    if let Ok(mut state) = state.lock() {
+        state.requeue(party, undelivered);
+    }
+    eprintln!("      [RELAY] Party {} 已断开", party);
+    result
+}
+
+/// 把消息写入连接；写入失败时返回尚未送达的消息 (失败的那条与发送队列中剩余的)
+async fn write_loop(
+    mut writer: OwnedWriteHalf,
+    mut rx: mpsc::UnboundedReceiver<Envelope>,
+) -> Vec<Envelope> {
+    while let Some(envelope) = rx.recv().await {
+        if write_frame(&mut writer, &envelope).await.is_err() {
+            let mut undelivered = vec![envelope];
+            rx.close();
+            while let Ok(envelope) = rx.try_recv() {
+                undelivered.push(envelope);
+            }
+            return undelivered;
+        }
+    }
+    Vec::new()
+}
@@ -0,0 +1,692 @@
+//! P2P 消息端到端加密 (End-to-End Encryption)
+//!
+//! 经中继运行时，中继能看到每个 `Envelope` 的 `payload`，其中包括重共享子份额等必须保密的 P2P 消息。
+//! 本模块在参与方之间建立两两独立的加密通道，中继或网络上的窃听者只能看到密文。
+//!
+//! **密钥交换**:
+//! 1. 每个节点生成一次性的 X25519 密钥 [`KeyExchange`]，用节点身份私钥 (secp256k1 ECDSA) 对
+//!    `party id || X25519 公钥` 签名，得到 [`KeyAnnouncement`] 并广播。
+//! 2. 收到的声明用公钥登记表中对应 party id 的身份公钥验证，中继无法替换其中的 X25519 公钥。
+//! 3. 与每个对端做 X25519 DH，再用 HKDF-SHA256 为两个方向各派生一个 32 字节密钥，得到 [`SecureChannels`]。
+//!
+//! **消息加密**: 每条 P2P 消息用 XChaCha20-Poly1305 加密，nonce 随机生成 (24 字节)。
+//! AAD 绑定会话 id、发送方、接收方、`reliable` 与会话内序号，因此密文被篡改、被转发给其他接收方、
+//! 被挪到其他会话或在同一会话中重放都会解密失败。
+//!
+//! **广播签名**: 广播消息 (包括可靠广播) 本身就发给所有参与方，不加密；发送方用身份私钥对
+//! 会话 id、发送方、`reliable`、会话内序号与内容签名，`payload` 变为 `序号 (8 字节大端) || 签名 (64 字节) || 原内容`。
+//! 接收方用登记表中的身份公钥验证，中继无法篡改、伪造、挪到其他会话或重放广播。
+//!
+//! **不保护的内容**: `Envelope` 头部 (会话 id、发送方、接收方、长度) 与广播内容仍是明文。
+//! X25519 密钥只保存在内存中，节点重启后需要重新交换。
+
+use super::frame::Envelope;
+
+use anyhow::{anyhow, Context};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use hkdf::Hkdf;
+use k256::ecdsa::signature::{Signer, Verifier};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+const ANNOUNCE_DOMAIN: &[u8] = b"my-mpc-bridge/e2e/announce/v1";
+const KDF_SALT: &[u8] = b"my-mpc-bridge/e2e/kdf/v1";
+const AAD_DOMAIN: &[u8] = b"my-mpc-bridge/e2e/p2p/v1";
+const BROADCAST_DOMAIN: &[u8] = b"my-mpc-bridge/e2e/broadcast/v1";
+const NONCE_LEN: usize = 24;
+const SIGNATURE_LEN: usize = 64;
+
+/// 经身份私钥签名的 X25519 公钥声明
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KeyAnnouncement {
+    pub party: u16,
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub x25519_public: Vec<u8>,
+    /// 64 字节 (r || s) 的 ECDSA 签名
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl KeyAnnouncement {
+    fn signed_message(party: u16, x25519_public: &[u8]) -> Vec<u8> {
+        [ANNOUNCE_DOMAIN, &party.to_be_bytes(), x25519_public].concat()
+    }
+
+    /// 用 `identity` (登记表中 `self.party` 的身份公钥) 验证签名，返回 X25519 公钥
+    pub fn verify(&self, identity: &VerifyingKey) -> anyhow::Result<PublicKey> {
+        let x25519_public: [u8; 32] = self
+            .x25519_public
+            .as_slice()
+            .try_into()
+            .map_err(|_| anyhow!("Party {} 的 X25519 公钥长度错误", self.party))?;
+        let signature = Signature::from_slice(&self.signature)
+            .map_err(|e| anyhow!("Party {} 的声明签名格式错误: {}", self.party, e))?;
+        identity
+            .verify(
+                &Self::signed_message(self.party, &x25519_public),
+                &signature,
+            )
+            .map_err(|_| anyhow!("Party {} 的 X25519 公钥声明签名无效", self.party))?;
+        Ok(PublicKey::from(x25519_public))
+    }
+}
+
+/// 本节点的一次性 X25519 密钥
+pub struct KeyExchange {
+    my_id: u16,
+    secret: StaticSecret,
+    public: PublicKey,
+}
+
+impl KeyExchange {
+    pub fn new(my_id: u16) -> Self {
+        let secret = StaticSecret::random_from_rng(OsRng);
+        let public = PublicKey::from(&secret);
+        Self {
+            my_id,
+            secret,
+            public,
+        }
+    }
+
+    /// 用本节点身份私钥签名的 X25519 公钥声明
+    pub fn announcement(&self, identity: &SigningKey) -> KeyAnnouncement {
+        let signature: Signature = identity.sign(&KeyAnnouncement::signed_message(
+            self.my_id,
+            self.public.as_bytes(),
+        ));
+        KeyAnnouncement {
+            party: self.my_id,
+            x25519_public: self.public.as_bytes().to_vec(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// 验证其他参与方的声明并派生两两之间的通道密钥
+    ///
+    /// **参数**:
+    /// - `identity`: 本节点的身份私钥，之后用于对广播消息签名。
+    /// - `announcements`: 除本方外每个参与方各一条声明。
+    /// - `registry`: party id -> 身份公钥，必须包含所有声明的 party id。
+    ///
+    /// **返回**: 与每个声明方的加密通道；声明重复、签名无效或 DH 结果为低阶点时报错。
+    pub fn finish(
+        self,
+        identity: &SigningKey,
+        announcements: impl IntoIterator<Item = KeyAnnouncement>,
+        registry: &BTreeMap<u16, VerifyingKey>,
+    ) -> anyhow::Result<SecureChannels> {
+        let mut send_keys = BTreeMap::new();
+        let mut recv_keys = BTreeMap::new();
+        let mut identities = BTreeMap::new();
+        for announcement in announcements {
+            let peer = announcement.party;
+            if peer == self.my_id || send_keys.contains_key(&peer) {
+                return Err(anyhow!("重复的 X25519 公钥声明 (Party {})", peer));
+            }
+            let peer_identity = registry
+                .get(&peer)
+                .ok_or_else(|| anyhow!("Party {} 不在身份公钥登记表中", peer))?;
+            let peer_public = announcement.verify(peer_identity)?;
+            let shared = self.secret.diffie_hellman(&peer_public);
+            if !shared.was_contributory() {
+                return Err(anyhow!("Party {} 的 X25519 公钥是低阶点", peer));
+            }
+            let hkdf = Hkdf::<Sha256>::new(Some(KDF_SALT), shared.as_bytes());
+            send_keys.insert(
+                peer,
+                derive_key(&hkdf, (self.my_id, &self.public), (peer, &peer_public))?,
+            );
+            recv_keys.insert(
+                peer,
+                derive_key(&hkdf, (peer, &peer_public), (self.my_id, &self.public))?,
+            );
+            identities.insert(peer, *peer_identity);
+        }
+        Ok(SecureChannels {
+            my_id: self.my_id,
+            identity: identity.clone(),
+            identities,
+            send_keys,
+            recv_keys,
+        })
+    }
+}
+
+/// 方向 `sender -> recipient` 的密钥，`info` 绑定双方 party id 与 X25519 公钥
+fn derive_key(
+    hkdf: &Hkdf<Sha256>,
+    (sender, sender_public): (u16, &PublicKey),
+    (recipient, recipient_public): (u16, &PublicKey),
+) -> anyhow::Result<[u8; 32]> {
+    let info = [
+        &sender.to_be_bytes()[..],
+        &recipient.to_be_bytes(),
+        sender_public.as_bytes(),
+        recipient_public.as_bytes(),
+    ]
+    .concat();
+    let mut key = [0u8; 32];
+    hkdf.expand(&info, &mut key)
+        .map_err(|e| anyhow!("HKDF expand failed: {}", e))?;
+    Ok(key)
+}
+
+/// 加密后放在 `Envelope.payload` 中的 P2P 消息
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SealedPayload {
+    /// 同一会话中 `sender -> recipient` 方向的序号，从 1 开始递增
+    pub seq: u64,
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub nonce: Vec<u8>,
+    #[serde(with = "crate::bridge::hex_bytes")]
+    pub ciphertext: Vec<u8>,
+}
+
+/// 本节点与其他参与方之间的加密通道
+pub struct SecureChannels {
+    my_id: u16,
+    /// 本节点身份私钥，对广播签名
+    identity: SigningKey,
+    /// 对端 party id -> 身份公钥，验证广播签名
+    identities: BTreeMap<u16, VerifyingKey>,
+    send_keys: BTreeMap<u16, [u8; 32]>,
+    recv_keys: BTreeMap<u16, [u8; 32]>,
+}
+
+impl SecureChannels {
+    pub fn my_id(&self) -> u16 {
+        self.my_id
+    }
+
+    /// 已建立加密通道的参与方
+    pub fn peers(&self) -> impl Iterator<Item = u16> + '_ {
+        self.send_keys.keys().copied()
+    }
+
+    /// 为一个协议会话创建加密端与解密端
+    pub fn session(self: &Arc<Self>, session_id: &str) -> (SessionSealer, SessionOpener) {
+        (
+            SessionSealer {
+                channels: self.clone(),
+                session: session_id.to_string(),
+                next_seq: Arc::new(Mutex::new(BTreeMap::new())),
+                next_broadcast_seq: Arc::new(AtomicU64::new(0)),
+            },
+            SessionOpener {
+                channels: self.clone(),
+                session: session_id.to_string(),
+                last_seq: BTreeMap::new(),
+                last_broadcast_seq: BTreeMap::new(),
+            },
+        )
+    }
+}
+
+fn channel_aad(session: &str, sender: u16, recipient: u16, reliable: bool, seq: u64) -> Vec<u8> {
+    [
+        AAD_DOMAIN,
+        &(session.len() as u64).to_be_bytes(),
+        session.as_bytes(),
+        &sender.to_be_bytes(),
+        &recipient.to_be_bytes(),
+        &[reliable as u8],
+        &seq.to_be_bytes(),
+    ]
+    .concat()
+}
+
+fn broadcast_message(session: &str, sender: u16, reliable: bool, seq: u64, payload: &[u8]) -> Vec<u8> {
+    [
+        BROADCAST_DOMAIN,
+        &(session.len() as u64).to_be_bytes(),
+        session.as_bytes(),
+        &sender.to_be_bytes(),
+        &[reliable as u8],
+        &seq.to_be_bytes(),
+        payload,
+    ]
+    .concat()
+}
+
+/// 一个会话的加密端，可在多个发送端之间共享序号
+#[derive(Clone)]
+pub struct SessionSealer {
+    channels: Arc<SecureChannels>,
+    session: String,
+    next_seq: Arc<Mutex<BTreeMap<u16, u64>>>,
+    next_broadcast_seq: Arc<AtomicU64>,
+}
+
+impl SessionSealer {
+    /// 加密发往 `recipient` 的一条 P2P 消息，返回新的 `payload`
+    pub fn seal(
+        &self,
+        recipient: u16,
+        reliable: bool,
+        plaintext: &[u8],
+    ) -> anyhow::Result<Vec<u8>> {
+        let key = self
+            .channels
+            .send_keys
+            .get(&recipient)
+            .ok_or_else(|| anyhow!("没有与 Party {} 的加密通道", recipient))?;
+        let seq = {
+            let mut next_seq = self
+                .next_seq
+                .lock()
+                .map_err(|_| anyhow!("Sealer lock poisoned"))?;
+            let seq = next_seq.entry(recipient).or_insert(0);
+            *seq += 1;
+            *seq
+        };
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let aad = channel_aad(&self.session, self.channels.my_id, recipient, reliable, seq);
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(key))
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| anyhow!("Encryption failed"))?;
+        Ok(serde_json::to_vec(&SealedPayload {
+            seq,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })?)
+    }
+
+    /// 用身份私钥对一条广播签名，返回新的 `payload` (`序号 || 签名 || 原内容`)
+    pub fn sign_broadcast(&self, reliable: bool, payload: &[u8]) -> Vec<u8> {
+        let seq = self.next_broadcast_seq.fetch_add(1, Ordering::SeqCst) + 1;
+        let signature: Signature = self.channels.identity.sign(&broadcast_message(
+            &self.session,
+            self.channels.my_id,
+            reliable,
+            seq,
+            payload,
+        ));
+        [&seq.to_be_bytes()[..], &signature.to_bytes(), payload].concat()
+    }
+}
+
+/// 一个会话的解密端，拒绝重放的消息
+pub struct SessionOpener {
+    channels: Arc<SecureChannels>,
+    session: String,
+    /// 每个发送方已接受的最大序号
+    last_seq: BTreeMap<u16, u64>,
+    /// 每个发送方已接受的最大广播序号
+    last_broadcast_seq: BTreeMap<u16, u64>,
+}
+
+impl SessionOpener {
+    /// 解密发给本方的 P2P 消息，返回明文
+    ///
+    /// **注意**: 同一方向的消息经同一条连接按顺序到达，因此序号必须严格递增；
+    /// 密文或头部被篡改、序号未递增时报错，且不更新已接受的序号。
+    pub fn open(&mut self, envelope: &Envelope) -> anyhow::Result<Vec<u8>> {
+        let my_id = self.channels.my_id;
+        if envelope.recipient != Some(my_id) {
+            return Err(anyhow!("不是发给 Party {} 的 P2P 消息", my_id));
+        }
+        if envelope.session != self.session {
+            return Err(anyhow!(
+                "会话 {} 收到属于会话 {} 的消息",
+                self.session,
+                envelope.session
+            ));
+        }
+        let key = self
+            .channels
+            .recv_keys
+            .get(&envelope.sender)
+            .ok_or_else(|| anyhow!("没有与 Party {} 的加密通道", envelope.sender))?;
+        let sealed: SealedPayload = serde_json::from_slice(&envelope.payload)
+            .with_context(|| format!("来自 Party {} 的 P2P 消息未加密", envelope.sender))?;
+        if sealed.nonce.len() != NONCE_LEN {
+            return Err(anyhow!(
+                "来自 Party {} 的消息 nonce 长度错误",
+                envelope.sender
+            ));
+        }
+        let last_seq = self.last_seq.get(&envelope.sender).copied().unwrap_or(0);
+        if sealed.seq <= last_seq {
+            return Err(anyhow!(
+                "来自 Party {} 的消息序号 {} 未递增 (已接受 {})，可能是重放",
+                envelope.sender,
+                sealed.seq,
+                last_seq
+            ));
+        }
+        let aad = channel_aad(
+            &envelope.session,
+            envelope.sender,
+            my_id,
+            envelope.reliable,
+            sealed.seq,
+        );
+        let plaintext = XChaCha20Poly1305::new(Key::from_slice(key))
+            .decrypt(
+                XNonce::from_slice(&sealed.nonce),
+                Payload {
+                    msg: &sealed.ciphertext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                anyhow!(
+                    "来自 Party {} 的 P2P 消息认证失败 (被篡改或密钥不匹配)",
+                    envelope.sender
+                )
+            })?;
+        self.last_seq.insert(envelope.sender, sealed.seq);
+        Ok(plaintext)
+    }
+
+    /// 验证广播的签名与序号，返回原内容
+    ///
+    /// **注意**: 与 P2P 消息一样，同一发送方的广播序号必须严格递增；签名无效或序号未递增时报错，
+    /// 且不更新已接受的序号。
+    pub fn open_broadcast(&mut self, envelope: &Envelope) -> anyhow::Result<Vec<u8>> {
+        if !envelope.is_broadcast() {
+            return Err(anyhow!("不是广播消息"));
+        }
+        if envelope.session != self.session {
+            return Err(anyhow!(
+                "会话 {} 收到属于会话 {} 的消息",
+                self.session,
+                envelope.session
+            ));
+        }
+        let identity = self
+            .channels
+            .identities
+            .get(&envelope.sender)
+            .ok_or_else(|| anyhow!("没有 Party {} 的身份公钥", envelope.sender))?;
+        if envelope.payload.len() < 8 + SIGNATURE_LEN {
+            return Err(anyhow!("来自 Party {} 的广播未签名", envelope.sender));
+        }
+        let (seq_bytes, rest) = envelope.payload.split_at(8);
+        let (signature_bytes, payload) = rest.split_at(SIGNATURE_LEN);
+        let seq = u64::from_be_bytes(seq_bytes.try_into()?);
+        let last_seq = self
+            .last_broadcast_seq
+            .get(&envelope.sender)
+            .copied()
+            .unwrap_or(0);
+        if seq <= last_seq {
+            return Err(anyhow!(
+                "来自 Party {} 的广播序号 {} 未递增 (已接受 {})，可能是重放",
+                envelope.sender,
+                seq,
+                last_seq
+            ));
+        }
+        let signature = Signature::from_slice(signature_bytes)
+            .map_err(|_| anyhow!("来自 Party {} 的广播签名格式错误", envelope.sender))?;
+        identity
+            .verify(
+                &broadcast_message(
+                    &envelope.session,
+                    envelope.sender,
+                    envelope.reliable,
+                    seq,
+                    payload,
+                ),
+                &signature,
+            )
+            .map_err(|_| {
+                anyhow!(
+                    "来自 Party {} 的广播签名无效 (被篡改或伪造)",
+                    envelope.sender
+                )
+            })?;
+        self.last_broadcast_seq.insert(envelope.sender, seq);
+        Ok(payload.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identities(n: u16) -> (Vec<SigningKey>, BTreeMap<u16, VerifyingKey>) {
+        let keys: Vec<SigningKey> = (0..n).map(|_| SigningKey::random(&mut OsRng)).collect();
+        let registry = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| (i as u16, *key.verifying_key()))
+            .collect();
+        (keys, registry)
+    }
+
+    /// 所有参与方两两交换声明后的加密通道
+    fn channels(
+        keys: &[SigningKey],
+        registry: &BTreeMap<u16, VerifyingKey>,
+    ) -> Vec<Arc<SecureChannels>> {
+        let exchanges: Vec<KeyExchange> = (0..keys.len() as u16).map(KeyExchange::new).collect();
+        let announcements: Vec<KeyAnnouncement> = exchanges
+            .iter()
+            .zip(keys)
+            .map(|(exchange, key)| exchange.announcement(key))
+            .collect();
+        exchanges
+            .into_iter()
+            .zip(keys)
+            .map(|(exchange, key)| {
+                let others: Vec<KeyAnnouncement> = announcements
+                    .iter()
+                    .filter(|a| a.party != exchange.my_id)
+                    .cloned()
+                    .collect();
+                Arc::new(exchange.finish(key, others, registry).unwrap())
+            })
+            .collect()
+    }
+
+    fn envelope(session: &str, sender: u16, recipient: Option<u16>, payload: Vec<u8>) -> Envelope {
+        Envelope {
+            session: session.to_string(),
+            sender,
+            recipient,
+            reliable: false,
+            payload,
+        }
+    }
+
+    #[test]
+    fn announcement_verifies_only_with_registered_key() {
+        let (keys, registry) = identities(2);
+        let exchange = KeyExchange::new(0);
+        let announcement = exchange.announcement(&keys[0]);
+        let public = announcement.verify(&registry[&0]).unwrap();
+        assert_eq!(public.as_bytes(), exchange.public.as_bytes());
+        assert!(announcement.verify(&registry[&1]).is_err());
+
+        let mut substituted = announcement.clone();
+        substituted.x25519_public = KeyExchange::new(0).public.as_bytes().to_vec();
+        assert!(substituted.verify(&registry[&0]).is_err());
+
+        let mut relabeled = announcement.clone();
+        relabeled.party = 1;
+        assert!(relabeled.verify(&registry[&0]).is_err());
+
+        let mut truncated = announcement;
+        truncated.signature.pop();
+        assert!(truncated.verify(&registry[&0]).is_err());
+    }
+
+    #[test]
+    fn finish_rejects_unregistered_and_duplicate_announcements() {
+        let (keys, registry) = identities(2);
+        let attacker = SigningKey::random(&mut OsRng);
+        let forged = KeyExchange::new(1).announcement(&attacker);
+        assert!(KeyExchange::new(0)
+            .finish(&keys[0], [forged], &registry)
+            .is_err());
+
+        let announcement = KeyExchange::new(1).announcement(&keys[1]);
+        assert!(KeyExchange::new(0)
+            .finish(&keys[0], [announcement.clone(), announcement], &registry)
+            .is_err());
+
+        let unknown = KeyExchange::new(7).announcement(&keys[1]);
+        assert!(KeyExchange::new(0)
+            .finish(&keys[0], [unknown], &registry)
+            .is_err());
+    }
+
+    #[test]
+    fn open_decrypts_and_rejects_replay() {
+        let (keys, registry) = identities(2);
+        let channels = channels(&keys, &registry);
+        let (sealer, _) = channels[0].session("s");
+        let (_, mut opener) = channels[1].session("s");
+
+        let first = envelope("s", 0, Some(1), sealer.seal(1, false, b"one").unwrap());
+        let second = envelope("s", 0, Some(1), sealer.seal(1, false, b"two").unwrap());
+        assert_eq!(opener.open(&first).unwrap(), b"one");
+        assert!(opener.open(&first).is_err());
+        assert_eq!(opener.open(&second).unwrap(), b"two");
+        assert!(opener.open(&first).is_err());
+        assert!(opener.open(&second).is_err());
+    }
+
+    #[test]
+    fn open_rejects_wrong_session() {
+        let (keys, registry) = identities(2);
+        let channels = channels(&keys, &registry);
+        let (sealer, _) = channels[0].session("a");
+        let (_, mut opener_b) = channels[1].session("b");
+        let sealed = sealer.seal(1, false, b"secret").unwrap();
+
+        // 头部仍标记为会话 a
+        assert!(opener_b
+            .open(&envelope("a", 0, Some(1), sealed.clone()))
+            .is_err());
+        // 头部改成会话 b，AAD 不匹配
+        assert!(opener_b
+            .open(&envelope("b", 0, Some(1), sealed.clone()))
+            .is_err());
+        // 未通过认证的消息不影响原会话
+        let (_, mut opener_a) = channels[1].session("a");
+        assert_eq!(
+            opener_a.open(&envelope("a", 0, Some(1), sealed)).unwrap(),
+            b"secret"
+        );
+    }
+
+    #[test]
+    fn open_rejects_wrong_recipient() {
+        let (keys, registry) = identities(3);
+        let channels = channels(&keys, &registry);
+        let (sealer, _) = channels[0].session("s");
+        let (_, mut opener_1) = channels[1].session("s");
+        let sealed_for_2 = sealer.seal(2, false, b"for 2").unwrap();
+
+        // 头部写着发给 Party 2
+        assert!(opener_1
+            .open(&envelope("s", 0, Some(2), sealed_for_2.clone()))
+            .is_err());
+        // 改投给 Party 1: 密钥与 AAD 都不匹配
+        assert!(opener_1
+            .open(&envelope("s", 0, Some(1), sealed_for_2.clone()))
+            .is_err());
+        // 冒充发送方
+        assert!(opener_1
+            .open(&envelope("s", 2, Some(1), sealed_for_2.clone()))
+            .is_err());
+
+        let (_, mut opener_2) = channels[2].session("s");
+        assert_eq!(
+            opener_2
+                .open(&envelope("s", 0, Some(2), sealed_for_2))
+                .unwrap(),
+            b"for 2"
+        );
+    }
+
+    #[test]
+    fn open_rejects_tampering() {
+        let (keys, registry) = identities(2);
+        let channels = channels(&keys, &registry);
+        let (sealer, _) = channels[0].session("s");
+        let (_, mut opener) = channels[1].session("s");
+        let sealed = sealer.seal(1, false, b"secret").unwrap();
+
+        let mut flipped: SealedPayload = serde_json::from_slice(&sealed).unwrap();
+        flipped.ciphertext[0] ^= 0x01;
+        let flipped = serde_json::to_vec(&flipped).unwrap();
+        assert!(opener.open(&envelope("s", 0, Some(1), flipped)).is_err());
+
+        let mut reliable = envelope("s", 0, Some(1), sealed.clone());
+        reliable.reliable = true;
+        assert!(opener.open(&reliable).is_err());
+
+        assert!(opener
+            .open(&envelope("s", 0, Some(1), b"plaintext".to_vec()))
+            .is_err());
+        assert_eq!(
+            opener.open(&envelope("s", 0, Some(1), sealed)).unwrap(),
+            b"secret"
+        );
+    }
+
+    #[test]
+    fn broadcast_signature_is_checked() {
+        let (keys, registry) = identities(3);
+        let channels = channels(&keys, &registry);
+        let (sealer, _) = channels[0].session("s");
+        let (_, mut opener) = channels[1].session("s");
+
+        let signed = envelope("s", 0, None, sealer.sign_broadcast(false, b"hello"));
+        assert_eq!(opener.open_broadcast(&signed).unwrap(), b"hello");
+        assert!(opener.open_broadcast(&signed).is_err(), "重放的广播被接受");
+
+        let next = sealer.sign_broadcast(false, b"next");
+        let mut tampered = envelope("s", 0, None, next.clone());
+        *tampered.payload.last_mut().unwrap() ^= 0x01;
+        assert!(opener.open_broadcast(&tampered).is_err());
+
+        let mut reliable = envelope("s", 0, None, next.clone());
+        reliable.reliable = true;
+        assert!(opener.open_broadcast(&reliable).is_err());
+
+        // 由 Party 2 转发成自己的广播
+        assert!(opener
+            .open_broadcast(&envelope("s", 2, None, next.clone()))
+            .is_err());
+
+        let (_, mut other_session) = channels[1].session("t");
+        assert!(other_session
+            .open_broadcast(&envelope("t", 0, None, next.clone()))
+            .is_err());
+
+        assert!(opener
+            .open_broadcast(&envelope("s", 0, None, b"unsigned".to_vec()))
+            .is_err());
+        assert_eq!(
+            opener
+                .open_broadcast(&envelope("s", 0, None, next))
+                .unwrap(),
+            b"next"
+        );
+
+        let (reliable_sealer, _) = channels[2].session("s");
+        let mut reliable = envelope("s", 2, None, reliable_sealer.sign_broadcast(true, b"echo"));
+        reliable.reliable = true;
+        assert_eq!(opener.open_broadcast(&reliable).unwrap(), b"echo");
+    }
+}
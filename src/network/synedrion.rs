@@ -0,0 +1,278 @@
+//! 网络化的 manul / Synedrion 会话 (Networked manul Sessions)
+//!
+//! `manul::dev::tokio::run_async` 把所有参与方放在同一进程中。[`run_manul_session`] 只驱动**本方**的
+//! manul `Session`，消息经 [`NetworkNode`] 发往其他进程:
+//!
+//! **流程** (每一轮):
+//! 1. 为 `message_destinations()` 中的每个参与方生成并发送消息。
+//! 2. 处理上一轮缓存下来的提前到达的消息 (`RoundOutcome::AnotherRound::cached_messages`)。
+//! 3. 接收消息直到 `can_finalize`: `preprocess_message` 负责校验签名、丢弃重复消息，
+//!    并把属于后续轮次的消息缓存在 accumulator 中，因此消息的到达顺序不影响结果。
+//! 4. `finalize_round` 进入下一轮或结束。
+//!
+//! 消息的发送方由连接握手确定，再由 manul 用 `verifiers` 中对应的公钥验证签名；
+//! 生产环境应使用 `identity::NodeSessionParams`，`SimpleSessionParams` 不做任何认证。
+//! manul 的消息全部以 P2P 发送，节点启用加密 (`NetworkNode::enable_encryption`) 后每条消息都被加密。
+
+use super::node::NetworkNode;
+use crate::bridge;
+use crate::simulation::calc_recovery_id_from_pubkey;
+
+use anyhow::{anyhow, Context};
+use elliptic_curve::CurveArithmetic;
+use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use manul::protocol::{EntryPoint, Protocol};
+use manul::session::{
+    CanFinalize, Message, RoundOutcome, Session, SessionId, SessionOutcome, SessionParameters,
+};
+use rand_core::OsRng;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 驱动本方的一个 manul 会话直到结束
+///
+/// **参数**:
+/// - `session_id`: 所有参与方必须一致，同时用于派生 manul 的 `SessionId`。
+/// - `signer`: 本方的消息签名密钥。
+/// - `verifiers`: 本次会话全部参与方 (含本方) 的 party id -> manul 公钥。
+/// - `entry_point`: 本方的协议入口 (`AuxGen` / `KeyRefresh` / `InteractiveSigning` ...)。
+///
+/// **返回**: 本方的协议结果；协议被其他参与方的错误终止或连接断开时报错。
+pub async fn run_manul_session<SP, EP>(
+    node: &NetworkNode,
+    session_id: &str,
+    signer: SP::Signer,
+    verifiers: &BTreeMap<u16, SP::Verifier>,
+    entry_point: EP,
+) -> anyhow::Result<<EP::Protocol as Protocol<SP::Verifier>>::Result>
+where
+    SP: SessionParameters,
+    EP: EntryPoint<SP::Verifier>,
+{
+    let participants: Vec<u16> = verifiers.keys().copied().collect();
+    let party_of: BTreeMap<SP::Verifier, u16> =
+        verifiers.iter().map(|(&id, v)| (v.clone(), id)).collect();
+    let mut link = node.session(session_id, &participants)?;
+
+    let mut session = Session::<_, SP>::new(
+        &mut OsRng,
+        SessionId::from_seed::<SP>(session_id.as_bytes()),
+        signer,
+        entry_point,
+    )
+    .map_err(|e| anyhow!("Party {} failed to create session: {}", node.id(), e))?;
+    if party_of.get(&session.verifier()) != Some(&node.id()) {
+        return Err(anyhow!(
+            "Party {} 的签名密钥与 verifiers 中登记的公钥不一致",
+            node.id()
+        ));
+    }
+    let mut cached_messages = Vec::new();
+
+    let report = loop {
+        let mut accum = session.make_accumulator();
+
+        for destination in session.message_destinations().clone() {
+            let (message, artifact) = session
+                .make_message(&mut OsRng, &destination)
+                .map_err(|e| anyhow!("Party {} failed to make message: {}", node.id(), e))?;
+            let recipient = *party_of
+                .get(&destination)
+                .ok_or_else(|| anyhow!("未登记的消息接收方 {:?}", destination))?;
+            link.send(Some(recipient), false, serde_json::to_vec(&message)?)?;
+            session
+                .add_artifact(&mut accum, artifact)
+                .map_err(|e| anyhow!("{}", e))?;
+        }
+
+        for preprocessed in cached_messages {
+            let processed = session.process_message(preprocessed);
+            session
+                .add_processed_message(&mut accum, processed)
+                .map_err(|e| anyhow!("{}", e))?;
+        }
+
+        loop {
+            match session.can_finalize(&accum) {
+                CanFinalize::Yes => break,
+                CanFinalize::NotYet => {}
+                CanFinalize::Never => {
+                    eprintln!(
+                        "      [NET] Party {} 会话 {} 无法完成 (收到无效消息)，终止",
+                        node.id(),
+                        session_id
+                    );
+                    break;
+                }
+            }
+            // 解密失败说明消息在途中被篡改，直接中止而不是等待一条永远不会到达的消息
+            let envelope = link
+                .recv()
+                .await
+                .ok_or_else(|| anyhow!("Party {} 会话 {}: 连接已断开", node.id(), session_id))?
+                .with_context(|| format!("Party {} 会话 {}", node.id(), session_id))?;
+            let from = verifiers
+                .get(&envelope.sender)
+                .context("Unknown sender")?
+                .clone();
+            let message: Message<SP::Verifier> = match serde_json::from_slice(&envelope.payload) {
+                Ok(message) => message,
+                Err(e) => {
+                    eprintln!(
+                        "      [NET] 丢弃来自 Party {} 的无法解析的消息: {}",
+                        envelope.sender, e
+                    );
+                    continue;
+                }
+            };
+            // 签名无效、重复或属于其他会话的消息返回 None；属于后续轮次的消息被缓存在 accum 中
+            let preprocessed = session
+                .preprocess_message(&mut accum, &from, message)
+                .map_err(|e| anyhow!("{}", e))?;
+            if let Some(verified) = preprocessed.ok() {
+                let processed = session.process_message(verified);
+                session
+                    .add_processed_message(&mut accum, processed)
+                    .map_err(|e| anyhow!("{}", e))?;
+            }
+        }
+
+        if let CanFinalize::Never = session.can_finalize(&accum) {
+            break session
+                .terminate_due_to_errors(accum)
+                .map_err(|e| anyhow!("{}", e))?;
+        }
+        match session
+            .finalize_round(&mut OsRng, accum)
+            .map_err(|e| anyhow!("Party {} failed to finalize round: {}", node.id(), e))?
+        {
+            RoundOutcome::Finished(report) => break report,
+            RoundOutcome::AnotherRound {
+                session: new_session,
+                cached_messages: new_cached_messages,
+            } => {
+                session = new_session;
+                cached_messages = new_cached_messages;
+            }
+        }
+    };
+
+    match report.outcome {
+        SessionOutcome::Result(result) => Ok(result),
+        outcome => Err(anyhow!(
+            "Party {} 会话 {} 未完成: {}",
+            node.id(),
+            session_id,
+            outcome.brief()
+        )),
+    }
+}
+
+/// 运行本方的 Synedrion KeyInit (n-of-n DKG)
+pub async fn run_synedrion_key_init_party<P, SP>(
+    node: &NetworkNode,
+    session_id: &str,
+    signer: SP::Signer,
+    verifiers: &BTreeMap<u16, SP::Verifier>,
+) -> anyhow::Result<synedrion::KeyShare<P, SP::Verifier>>
+where
+    P: synedrion::SchemeParams + 'static,
+    SP: SessionParameters,
+{
+    eprintln!("      [NET] Party {} Synedrion KeyInit...", node.id());
+    let all: BTreeSet<SP::Verifier> = verifiers.values().cloned().collect();
+    let entry_point = synedrion::KeyInit::<P, SP::Verifier>::new(all)
+        .map_err(|e| anyhow!("Failed to create KeyInit: {}", e))?;
+    run_manul_session::<SP, _>(node, session_id, signer, verifiers, entry_point).await
+}
+
+/// 运行本方的 Synedrion AuxGen
+pub async fn run_synedrion_aux_gen_party<P, SP>(
+    node: &NetworkNode,
+    session_id: &str,
+    signer: SP::Signer,
+    verifiers: &BTreeMap<u16, SP::Verifier>,
+) -> anyhow::Result<synedrion::AuxInfo<P, SP::Verifier>>
+where
+    P: synedrion::SchemeParams + 'static,
+    SP: SessionParameters,
+{
+    eprintln!("      [NET] Party {} Synedrion AuxGen...", node.id());
+    let all: BTreeSet<SP::Verifier> = verifiers.values().cloned().collect();
+    let entry_point = synedrion::AuxGen::<P, SP::Verifier>::new(all)
+        .map_err(|e| anyhow!("Failed to create AuxGen: {}", e))?;
+    run_manul_session::<SP, _>(node, session_id, signer, verifiers, entry_point).await
+}
+
+/// 运行本方的 Synedrion KeyRefresh，并把 `KeyShareChange` 应用到 `key_share`
+///
+/// **返回**: 刷新后的 KeyShare 与新的 AuxInfo，共享公钥不变。
+pub async fn run_synedrion_refresh_party<P, SP>(
+    node: &NetworkNode,
+    session_id: &str,
+    signer: SP::Signer,
+    verifiers: &BTreeMap<u16, SP::Verifier>,
+    key_share: synedrion::KeyShare<P, SP::Verifier>,
+) -> anyhow::Result<(
+    synedrion::KeyShare<P, SP::Verifier>,
+    synedrion::AuxInfo<P, SP::Verifier>,
+)>
+where
+    P: synedrion::SchemeParams + 'static,
+    SP: SessionParameters,
+{
+    eprintln!("      [NET] Party {} Synedrion KeyRefresh...", node.id());
+    let all: BTreeSet<SP::Verifier> = verifiers.values().cloned().collect();
+    let entry_point = synedrion::KeyRefresh::<P, SP::Verifier>::new(all)
+        .map_err(|e| anyhow!("Failed to create KeyRefresh: {}", e))?;
+    let (change, aux) =
+        run_manul_session::<SP, _>(node, session_id, signer, verifiers, entry_point).await?;
+    let key_share = key_share
+        .update(change)
+        .map_err(|e| anyhow!("Party {} failed to apply KeyShareChange: {:?}", node.id(), e))?;
+    Ok((key_share, aux))
+}
+
+/// 运行本方的 Synedrion InteractiveSigning
+///
+/// **参数**:
+/// - `verifiers`: 签名者集合。`key_share` / `aux` 的 public 列表必须恰好是这些签名者
+///   (t-of-n 密钥先用 `simulation::threshold_signing_subset` 转换)。
+///
+/// **返回**: (r, s, v)，所有签名者得到相同的签名。
+pub async fn run_synedrion_signing_party<P, SP>(
+    node: &NetworkNode,
+    session_id: &str,
+    signer: SP::Signer,
+    verifiers: &BTreeMap<u16, SP::Verifier>,
+    key_share: synedrion::KeyShare<P, SP::Verifier>,
+    aux: synedrion::AuxInfo<P, SP::Verifier>,
+    message_hash: [u8; 32],
+) -> anyhow::Result<([u8; 32], [u8; 32], u8)>
+where
+    P: synedrion::SchemeParams + 'static,
+    SP: SessionParameters,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
+    elliptic_curve::FieldBytes<P::Curve>: From<[u8; 32]>,
+{
+    eprintln!(
+        "      [NET] Party {} Synedrion signing with {:?}...",
+        node.id(),
+        verifiers.keys().collect::<Vec<_>>()
+    );
+    let pubkey = bridge::get_global_public_key_point(&key_share)?
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let entry_point = synedrion::InteractiveSigning::new(message_hash.into(), key_share, aux)
+        .map_err(|e| anyhow!("Synedrion error: {:?}", e))?;
+    let signature =
+        run_manul_session::<SP, _>(node, session_id, signer, verifiers, entry_point).await?;
+
+    let (backend_sig, _) = signature.to_backend();
+    let (r_field, s_field) = backend_sig.split_bytes();
+    let r: [u8; 32] = r_field.to_vec().try_into().map_err(|_| anyhow!("r error"))?;
+    let s: [u8; 32] = s_field.to_vec().try_into().map_err(|_| anyhow!("s error"))?;
+    let v = calc_recovery_id_from_pubkey(&r, &s, &message_hash, &pubkey)?;
+    Ok((r, s, v))
+}
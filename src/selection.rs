@@ -0,0 +1,195 @@
+//! 签名者选择策略 (Signer Selection)
+//!
+//! 每次签名前所有参与方必须对签名者集合 S 达成一致。[`select_signers`] 是一个纯函数:
+//! 只要输入 (委员会、在线参与方、阈值、策略、种子) 相同，每个节点都会独立算出同一个 S，
+//! 不需要额外的协商轮次。
+//!
+//! **选择顺序**:
+//! 1. 去掉不在线 (`available` 之外) 与被排除 (`exclude`) 的参与方。
+//! 2. `preferred` 中的参与方按给定顺序优先入选。
+//! 3. 其余参与方按权重 (`weights`，默认 0) 从高到低入选；权重相同时按 SHA-256(seed || id) 排序，
+//!    seed 通常取消息哈希，使负载在权重相同的参与方之间轮换。
+//!
+//! 返回的集合按 party id 升序排列，可以直接作为 cggmp24 的 `participants` 或 Synedrion 的签名者列表。
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// 签名者选择策略，可写在 CLI 配置文件的 `signer_policy` 字段中
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct SignerPolicy {
+    /// 优先入选的参与方 (按顺序)
+    pub preferred: Vec<u16>,
+    /// 权重 (party id -> weight)，权重越大越优先，未列出的参与方权重为 0
+    pub weights: BTreeMap<u16, u32>,
+    /// 永不入选的参与方
+    pub exclude: BTreeSet<u16>,
+}
+
+/// 按策略从委员会中确定性地选出 `threshold` 个签名者
+///
+/// **参数**:
+/// - `committee`: 委员会全部 party id
+/// - `available`: 当前在线的 party id (活性检测的结果)
+/// - `seed`: 所有参与方都知道的公开数据 (例如消息哈希)，用于打破权重相同时的平局
+///
+/// **错误**: 合格的参与方少于 `threshold`，或 `preferred` 中出现了委员会之外的 id。
+pub fn select_signers(
+    committee: &[u16],
+    available: &BTreeSet<u16>,
+    threshold: u16,
+    policy: &SignerPolicy,
+    seed: &[u8],
+) -> anyhow::Result<Vec<u16>> {
+    if threshold == 0 {
+        return Err(anyhow!("Threshold must be at least 1"));
+    }
+    let committee: BTreeSet<u16> = committee.iter().copied().collect();
+    if let Some(id) = policy.preferred.iter().find(|id| !committee.contains(id)) {
+        return Err(anyhow!("优先参与方 {} 不在委员会 {:?} 中", id, committee));
+    }
+
+    let eligible: BTreeSet<u16> = committee
+        .iter()
+        .copied()
+        .filter(|id| available.contains(id) && !policy.exclude.contains(id))
+        .collect();
+    if eligible.len() < threshold as usize {
+        return Err(anyhow!(
+            "合格签名者不足: 需要 {}, 实际 {:?} (委员会 {:?}, 在线 {:?}, 排除 {:?})",
+            threshold,
+            eligible,
+            committee,
+            available,
+            policy.exclude
+        ));
+    }
+
+    let mut selected: Vec<u16> = Vec::with_capacity(threshold as usize);
+    for id in &policy.preferred {
+        if selected.len() == threshold as usize {
+            break;
+        }
+        if eligible.contains(id) && !selected.contains(id) {
+            selected.push(*id);
+        }
+    }
+
+    let mut rest: Vec<(u32, [u8; 32], u16)> = eligible
+        .iter()
+        .filter(|id| !selected.contains(id))
+        .map(|&id| {
+            let weight = policy.weights.get(&id).copied().unwrap_or(0);
+            (weight, tie_breaker(seed, id), id)
+        })
+        .collect();
+    // 权重降序，平局时按 tie breaker 升序
+    rest.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    selected.extend(
+        rest.into_iter()
+            .take(threshold as usize - selected.len())
+            .map(|(_, _, id)| id),
+    );
+
+    selected.sort();
+    Ok(selected)
+}
+
+fn tie_breaker(seed: &[u8], id: u16) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(seed);
+    hasher.update(id.to_be_bytes());
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all(ids: &[u16]) -> BTreeSet<u16> {
+        ids.iter().copied().collect()
+    }
+
+    #[test]
+    fn same_inputs_select_same_signers() {
+        let committee = [0, 1, 2, 3, 4];
+        let available = all(&committee);
+        let policy = SignerPolicy::default();
+        let first = select_signers(&committee, &available, 3, &policy, b"msg").unwrap();
+        for _ in 0..10 {
+            assert_eq!(
+                select_signers(&committee, &available, 3, &policy, b"msg").unwrap(),
+                first
+            );
+        }
+        assert_eq!(first.len(), 3);
+        assert!(first.windows(2).all(|w| w[0] < w[1]));
+        // 输入顺序不影响结果
+        let shuffled = [4, 2, 0, 3, 1];
+        assert_eq!(
+            select_signers(&shuffled, &available, 3, &policy, b"msg").unwrap(),
+            first
+        );
+    }
+
+    #[test]
+    fn seed_rotates_signers() {
+        let committee = [0, 1, 2, 3, 4];
+        let available = all(&committee);
+        let policy = SignerPolicy::default();
+        let selections: BTreeSet<Vec<u16>> = (0u8..32)
+            .map(|k| select_signers(&committee, &available, 3, &policy, &[k; 32]).unwrap())
+            .collect();
+        assert!(selections.len() > 1);
+    }
+
+    #[test]
+    fn policy_is_applied() {
+        let committee = [0, 1, 2, 3, 4];
+        let policy = SignerPolicy {
+            preferred: vec![4],
+            weights: BTreeMap::from([(1, 10), (2, 5)]),
+            exclude: BTreeSet::from([0]),
+        };
+        let selected = select_signers(&committee, &all(&committee), 3, &policy, b"msg").unwrap();
+        assert_eq!(selected, vec![1, 2, 4]);
+        // 不在线的参与方不会入选
+        let selected = select_signers(&committee, &all(&[0, 2, 3, 4]), 3, &policy, b"msg").unwrap();
+        assert_eq!(selected, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn too_few_online_is_an_error() {
+        let committee = [0, 1, 2, 3, 4];
+        let policy = SignerPolicy::default();
+        assert!(select_signers(&committee, &all(&[1, 3]), 3, &policy, b"msg").is_err());
+        let policy = SignerPolicy {
+            exclude: BTreeSet::from([1]),
+            ..Default::default()
+        };
+        assert!(select_signers(&committee, &all(&[1, 2, 3]), 3, &policy, b"msg").is_err());
+        // 委员会之外的 id 不计入在线参与方
+        assert!(select_signers(&committee, &all(&[3, 4, 7]), 3, &policy, b"msg").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_policy() {
+        let committee = [0, 1, 2];
+        let policy = SignerPolicy {
+            preferred: vec![9],
+            ..Default::default()
+        };
+        assert!(select_signers(&committee, &all(&committee), 2, &policy, b"msg").is_err());
+        assert!(select_signers(
+            &committee,
+            &all(&committee),
+            0,
+            &SignerPolicy::default(),
+            b"msg"
+        )
+        .is_err());
+    }
+}
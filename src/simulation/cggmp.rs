@@ -1,11 +1,14 @@
+use super::common::calc_recovery_id_from_pubkey;
+use super::presignature::{signer_set_key, PoolEntry, PresignaturePool};
 use crate::bridge;
-use crate::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
 
 use anyhow::{anyhow, Context};
 use cggmp24::security_level::SecurityLevel128;
+use cggmp24::key_share::AuxInfo;
 use cggmp24::{ExecutionId, KeyShare, PregeneratedPrimes};
-use rand_core::OsRng;
+use rand_core::{OsRng, RngCore};
 use round_based::sim::Simulation;
+use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::fs;
 use std::path::Path;
@@ -13,27 +16,82 @@ use std::path::Path;
 pub async fn run_cggmp_signing(
     shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
     message_hash_bytes: [u8; 32],
+) -> anyhow::Result<([u8; 32], [u8; 32], u8)> {
+    let participants: Vec<u16> = shares.iter().map(|s| s.core.i).collect();
+    eprintln!(
+        "      [SIGN] cggmp24 signing with {} participants: {:?}",
+        shares.len(),
+        participants
+    );
+    let eid = ExecutionId::new(b"initial-signing-session");
+    sign_with_participants(shares, &participants, eid, message_hash_bytes)
+}
+
+/// 批量签名 (Batch Signing)
+///
+/// **功能**: 签名者集合只确定与校验一次，随后依次为每个消息哈希运行签名协议。
+/// 每条消息使用独立的 ExecutionId (随机批次前缀 + 序号)，某条消息失败不会中断整个批次。
+///
+/// **返回**: 与 `message_hashes` 一一对应的结果列表；外层错误只表示签名者集合本身无效。
+pub async fn run_cggmp_batch_signing(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    message_hashes: &[[u8; 32]],
+) -> anyhow::Result<Vec<anyhow::Result<([u8; 32], [u8; 32], u8)>>> {
+    let first = shares.first().context("No key shares")?;
+    let participants: Vec<u16> = shares.iter().map(|s| s.core.i).collect();
+    if shares
+        .iter()
+        .any(|s| s.shared_public_key != first.shared_public_key)
+    {
+        return Err(anyhow!("签名者 {:?} 的共享公钥不一致", participants));
+    }
+    eprintln!(
+        "      [SIGN] cggmp24 batch signing {} messages with participants: {:?}",
+        message_hashes.len(),
+        participants
+    );
+
+    let mut batch_id = [0u8; 32];
+    OsRng.fill_bytes(&mut batch_id);
+    let mut results = Vec::with_capacity(message_hashes.len());
+    for (index, message_hash) in message_hashes.iter().enumerate() {
+        let mut eid_bytes = batch_id.to_vec();
+        eid_bytes.extend_from_slice(&(index as u64).to_be_bytes());
+        let result = sign_with_participants(
+            shares,
+            &participants,
+            ExecutionId::new(&eid_bytes),
+            *message_hash,
+        )
+        .with_context(|| format!("Message {} ({}) failed", index, hex::encode(message_hash)));
+        if let Err(e) = &result {
+            eprintln!("      [WARN] {:#}", e);
+        }
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// 在同一进程中为 `participants` 运行一次完整的 cggmp24 签名协议
+fn sign_with_participants(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    participants: &[u16],
+    eid: ExecutionId<'_>,
+    message_hash_bytes: [u8; 32],
 ) -> anyhow::Result<([u8; 32], [u8; 32], u8)> {
     type E = cggmp24::supported_curves::Secp256k1;
     let message_scalar =
         cggmp24::generic_ec::Scalar::<E>::from_be_bytes_mod_order(&message_hash_bytes);
     let prehashed_message = cggmp24::signing::PrehashedDataToSign::from_scalar(message_scalar);
-    let n = shares.len();
-    let participants: Vec<u16> = shares.iter().map(|s| s.core.i).collect();
-    println!(
-        "      [SIGN] cggmp24 signing with {} participants: {:?}",
-        n, participants
-    );
     let mut simulation = Simulation::empty();
-    let eid = ExecutionId::new(b"initial-signing-session");
 
-    for i in 0..n {
-        let share = shares[i].clone();
-        let participants = participants.clone();
-        let eid = eid.clone();
+    for (i, share) in shares.iter().enumerate() {
+        let share = share.clone();
+        let participants = participants.to_vec();
         let prehashed_message = prehashed_message;
         simulation.add_async_party(move |party| async move {
-            cggmp24::signing(eid, share.core.i, &participants, &share)
+            // 第一个参数是本方在签名者中的索引，participants[i] 才是 keygen 时的索引
+            cggmp24::signing(eid, i as u16, &participants, &share)
                 .set_digest::<Sha256>()
                 .sign(&mut OsRng, party, &prehashed_message)
                 .await
@@ -48,41 +106,267 @@ pub async fn run_cggmp_signing(
     let r = signature.r.to_be_bytes();
     let s = signature.s.to_be_bytes();
     let pubkey = shares[0].shared_public_key;
-    let expected_addr = compute_eth_address_from_pubkey(&pubkey.to_bytes(false));
-    let v = calc_recovery_id(
+    let v = calc_recovery_id_from_pubkey(
         &r.as_ref().try_into()?,
         &s.as_ref().try_into()?,
         &message_hash_bytes,
-        expected_addr,
-    )
-    .map_err(|e| anyhow!(e))?;
+        &pubkey.to_bytes(false),
+    )?;
     Ok((r.as_ref().try_into()?, s.as_ref().try_into()?, v))
 }
 
-/// 运行完整的 DKG 过程并保存结果
-pub async fn run_dkg_and_save(
-    execution_id: ExecutionId<'_>,
-    n: u16,
-    t: u16,
-) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+/// 单个签名者的 cggmp24 预签名 (Per-party Presignature)
+///
+/// 只保存在该签名者自己的预签名池中。同一次预签名协议中所有签名者的输出共用 `id`
+/// (该次协议的 ExecutionId)，在线阶段各方按 id 取出同一次协议的预签名。
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CggmpPresignature {
+    /// 预签名协议的 ExecutionId (hex)，所有签名者相同
+    pub id: String,
+    /// 本方的 `core.i`
+    pub party: u16,
+    /// 签名者的 `core.i` (升序)
+    pub participants: Vec<u16>,
+    pub presignature: cggmp24::signing::Presignature<cggmp24::supported_curves::Secp256k1>,
+    pub public_data:
+        cggmp24::signing::PresignaturePublicData<cggmp24::supported_curves::Secp256k1>,
+}
+
+/// 签名者 `party` 的预签名池文件 (`dir/party_{party}.json`)
+pub fn cggmp_presignature_pool_path(dir: impl AsRef<Path>, party: u16) -> std::path::PathBuf {
+    dir.as_ref().join(format!("party_{}.json", party))
+}
+
+/// 密钥分片的 epoch: 共享公钥与所有公钥分片的 SHA-256 (前 16 字节 hex)
+///
+/// 所有参与方的公钥分片相同，因此各方独立算出同一个值；Key Refresh / 重共享后公钥分片改变，
+/// epoch 随之改变。
+pub fn cggmp_key_share_epoch(
+    share: &KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>,
+) -> anyhow::Result<String> {
+    use sha2::Digest;
+    let mut hasher = Sha256::new();
+    hasher.update(share.core.shared_public_key.to_bytes(true));
+    hasher.update(serde_json::to_vec(&share.core.public_shares)?);
+    Ok(hex::encode(&hasher.finalize()[..16]))
+}
+
+/// 预签名池中 cggmp24 预签名的分组 key (共享公钥 + 密钥分片 epoch + 签名者集合)
+///
+/// 每个签名者只用自己的分片与签名者集合计算，得到的 key 在所有签名者之间一致。
+pub fn cggmp_presignature_key(
+    share: &KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>,
+    participants: &[u16],
+) -> anyhow::Result<String> {
+    let y_hex = hex::encode(share.shared_public_key.to_bytes(true));
+    Ok(signer_set_key(
+        &y_hex,
+        &cggmp_key_share_epoch(share)?,
+        participants,
+    ))
+}
+
+/// 把本方的一份预签名放入本方的预签名池 (以协议的 ExecutionId 作为池中的 id)
+pub fn store_cggmp_presignature(
+    pool: &mut PresignaturePool<CggmpPresignature>,
+    key: &str,
+    presignature: CggmpPresignature,
+) -> anyhow::Result<()> {
+    pool.insert(
+        key,
+        PoolEntry {
+            id: presignature.id.clone(),
+            presignature,
+        },
+    )
+}
+
+/// 离线阶段: 为签名者集合生成 `count` 份 cggmp24 预签名
+///
+/// **功能**: 运行 cggmp24 签名协议中与消息无关的部分 (`generate_presignature`)，
+/// 每份预签名使用独立的随机 ExecutionId。
+///
+/// **返回**: 按签名者 `core.i` 分组的预签名。每个签名者的预签名只应放入它自己的池
+/// (`store_cggmp_presignature`)，不能与其他签名者的预签名保存在一起。
+///
+/// **生产环境通信**: **涉及**。与完整签名协议的前三轮相同，但可以在空闲时提前完成。
+pub async fn generate_cggmp_presignatures(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    count: usize,
+) -> anyhow::Result<std::collections::BTreeMap<u16, Vec<CggmpPresignature>>> {
+    let mut shares = shares.to_vec();
+    shares.sort_by_key(|s| s.core.i);
+    let participants: Vec<u16> = shares.iter().map(|s| s.core.i).collect();
+    eprintln!(
+        "      [PRESIGN] 为 {:?} 生成 {} 份 cggmp24 预签名...",
+        participants, count
+    );
+
+    let mut by_party: std::collections::BTreeMap<u16, Vec<CggmpPresignature>> = participants
+        .iter()
+        .map(|&party| (party, Vec::with_capacity(count)))
+        .collect();
+    for _ in 0..count {
+        let mut eid_bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut eid_bytes);
+        let eid = ExecutionId::new(&eid_bytes);
+
+        let mut simulation = Simulation::empty();
+        for (index, share) in shares.iter().enumerate() {
+            let share = share.clone();
+            let participants = participants.clone();
+            simulation.add_async_party(move |party| async move {
+                cggmp24::signing(eid, index as u16, &participants, &share)
+                    .set_digest::<Sha256>()
+                    .generate_presignature(&mut OsRng, party)
+                    .await
+            });
+        }
+        let results = simulation
+            .run()
+            .map_err(|e| anyhow!("Presigning failed: {:?}", e))?;
+        for (&party, res) in participants.iter().zip(results) {
+            let (presignature, public_data) =
+                res.map_err(|e| anyhow!("Party {} presigning failed: {:?}", party, e))?;
+            by_party.entry(party).or_default().push(CggmpPresignature {
+                id: hex::encode(eid_bytes),
+                party,
+                participants: participants.clone(),
+                presignature,
+                public_data,
+            });
+        }
+    }
+    Ok(by_party)
+}
+
+/// 在线阶段 (单个签名者): 用本方的预签名对消息哈希生成部分签名
+///
+/// `presignature` 按值传入，调用后即被消耗。部分签名可以公开广播。
+pub fn issue_cggmp_partial_signature(
+    presignature: CggmpPresignature,
+    message_hash_bytes: [u8; 32],
+) -> cggmp24::signing::PartialSignature<cggmp24::supported_curves::Secp256k1> {
+    type E = cggmp24::supported_curves::Secp256k1;
+    let message_scalar =
+        cggmp24::generic_ec::Scalar::<E>::from_be_bytes_mod_order(&message_hash_bytes);
+    let prehashed_message = cggmp24::signing::PrehashedDataToSign::from_scalar(message_scalar);
+    presignature
+        .presignature
+        .issue_partial_signature(&prehashed_message)
+}
+
+/// 在线阶段 (任意一方): 把所有签名者的部分签名合成为完整的 ECDSA 签名
+pub fn combine_cggmp_partial_signatures(
+    partial_signatures: &[cggmp24::signing::PartialSignature<cggmp24::supported_curves::Secp256k1>],
+    public_data: &cggmp24::signing::PresignaturePublicData<cggmp24::supported_curves::Secp256k1>,
+    shared_public_key: &cggmp24::generic_ec::Point<cggmp24::supported_curves::Secp256k1>,
+    message_hash_bytes: [u8; 32],
+) -> anyhow::Result<([u8; 32], [u8; 32], u8)> {
     type E = cggmp24::supported_curves::Secp256k1;
-    type L = SecurityLevel128;
+    let message_scalar =
+        cggmp24::generic_ec::Scalar::<E>::from_be_bytes_mod_order(&message_hash_bytes);
+    let prehashed_message = cggmp24::signing::PrehashedDataToSign::from_scalar(message_scalar);
+
+    let signature = cggmp24::signing::PartialSignature::combine(
+        partial_signatures,
+        public_data,
+        &prehashed_message,
+    )
+    .context("Failed to combine partial signatures")?
+    .normalize_s();
+
+    let r: [u8; 32] = signature.r.to_be_bytes().as_ref().try_into()?;
+    let s: [u8; 32] = signature.s.to_be_bytes().as_ref().try_into()?;
+    let v = calc_recovery_id_from_pubkey(
+        &r,
+        &s,
+        &message_hash_bytes,
+        &shared_public_key.to_bytes(false),
+    )?;
+    Ok((r, s, v))
+}
+
+/// 低延迟签名: 优先使用各签名者自己的预签名池，池为空时退化为完整的交互式签名
+///
+/// **流程**:
+/// 1. 第一个签名者从自己的池中提议下一份预签名的 id。
+/// 2. 每个签名者从**自己的**池 (`pools[core.i]`) 中取出该 id 的预签名并生成部分签名。
+/// 3. 合成部分签名。任何签名者缺少该预签名时，已取出的预签名作废，退化为完整签名。
+pub async fn run_cggmp_signing_with_pool(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    pools: &mut std::collections::BTreeMap<u16, PresignaturePool<CggmpPresignature>>,
+    message_hash_bytes: [u8; 32],
+) -> anyhow::Result<([u8; 32], [u8; 32], u8)> {
+    let first = shares.first().context("No key shares")?;
+    let participants: Vec<u16> = shares.iter().map(|s| s.core.i).collect();
+    let key = cggmp_presignature_key(first, &participants)?;
+    let proposed = pools
+        .get(&first.core.i)
+        .and_then(|pool| pool.next_id(&key))
+        .map(str::to_string);
+    let Some(id) = proposed else {
+        eprintln!("      [SIGN] 预签名池为空，运行完整签名协议");
+        return run_cggmp_signing(shares, message_hash_bytes).await;
+    };
+
+    let mut partial_signatures = Vec::with_capacity(shares.len());
+    let mut public_data = None;
+    for share in shares {
+        let pool = pools
+            .get_mut(&share.core.i)
+            .with_context(|| format!("Party {} 没有预签名池", share.core.i))?;
+        let key = cggmp_presignature_key(share, &participants)?;
+        let Some(entry) = pool.take_id(&key, &id)? else {
+            eprintln!(
+                "      [WARN] Party {} 的池中没有预签名 {}，运行完整签名协议",
+                share.core.i, id
+            );
+            return run_cggmp_signing(shares, message_hash_bytes).await;
+        };
+        public_data.get_or_insert_with(|| entry.presignature.public_data.clone());
+        partial_signatures.push(issue_cggmp_partial_signature(
+            entry.presignature,
+            message_hash_bytes,
+        ));
+    }
+    eprintln!(
+        "      [SIGN] 使用预签名 {} (剩余 {})",
+        id,
+        pools.get(&first.core.i).map_or(0, |pool| pool.available(&key))
+    );
+    combine_cggmp_partial_signatures(
+        &partial_signatures,
+        &public_data.context("No presignature taken")?,
+        &first.shared_public_key,
+        message_hash_bytes,
+    )
+}
 
+/// 加载 data/primes.txt 中预生成的素数，不存在时生成并保存
+pub fn load_or_generate_primes() -> anyhow::Result<PregeneratedPrimes<SecurityLevel128>> {
     let primes_path = "data/primes.txt";
-    let primes = if Path::new(primes_path).exists() {
-        println!("      [DKG] 正在从 {} 加载预生成的素数...", primes_path);
+    if Path::new(primes_path).exists() {
+        eprintln!("      [DKG] 正在从 {} 加载预生成的素数...", primes_path);
         let content = fs::read_to_string(primes_path)?;
-        serde_json::from_str::<PregeneratedPrimes<L>>(&content)
-            .context("Failed to deserialize primes")?
+        serde_json::from_str::<PregeneratedPrimes<SecurityLevel128>>(&content)
+            .context("Failed to deserialize primes")
     } else {
-        println!("      [DKG] 正在预生成素数 (2048-bit RSA 较慢，请稍候)...");
-        let p = PregeneratedPrimes::<L>::generate(&mut OsRng);
+        eprintln!("      [DKG] 正在预生成素数 (2048-bit RSA 较慢，请稍候)...");
+        let p = PregeneratedPrimes::<SecurityLevel128>::generate(&mut OsRng);
+        fs::create_dir_all("data")?;
         fs::write(primes_path, serde_json::to_string_pretty(&p)?)?;
-        p
-    };
+        Ok(p)
+    }
+}
+
+/// 运行 cggmp24 AuxGen (Paillier 密钥等)，结果按协议索引 0..n 排列
+pub async fn run_cggmp_aux_gen(
+    execution_id: ExecutionId<'_>,
+    n: u16,
+) -> anyhow::Result<Vec<AuxInfo<SecurityLevel128>>> {
+    let primes = load_or_generate_primes()?;
 
-    // 1. Aux Gen
-    println!("      [DKG] 阶段 1/2: 生成辅助信息 (Paillier 密钥)...");
     let mut sim_aux = Simulation::empty();
     for i in 0..n {
         let primes = primes.clone();
@@ -95,18 +379,33 @@ pub async fn run_dkg_and_save(
     let aux_results = sim_aux
         .run()
         .map_err(|e| anyhow!("AuxGen failed: {:?}", e))?;
-    let mut aux_infos = Vec::new();
-    for (i, res) in aux_results.into_iter().enumerate() {
-        let aux = res.map_err(|e| anyhow!("Party {} AuxGen failed: {:?}", i, e))?;
+    aux_results
+        .into_iter()
+        .enumerate()
+        .map(|(i, res)| res.map_err(|e| anyhow!("Party {} AuxGen failed: {:?}", i, e)))
+        .collect()
+}
+
+/// 运行完整的 DKG 过程并保存结果
+pub async fn run_dkg_and_save(
+    execution_id: ExecutionId<'_>,
+    n: u16,
+    t: u16,
+) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+    type E = cggmp24::supported_curves::Secp256k1;
+
+    // 1. Aux Gen
+    eprintln!("      [DKG] 阶段 1/2: 生成辅助信息 (Paillier 密钥)...");
+    let aux_infos = run_cggmp_aux_gen(execution_id, n).await?;
+    for (i, aux) in aux_infos.iter().enumerate() {
         fs::write(
             format!("data/aux_info_party_{}.json", i),
-            serde_json::to_string_pretty(&aux)?,
+            serde_json::to_string_pretty(aux)?,
         )?;
-        aux_infos.push(aux);
     }
 
     // 2. Keygen
-    println!("      [DKG] 阶段 2/2: 生成私钥分片 (Secp256k1)...");
+    eprintln!("      [DKG] 阶段 2/2: 生成私钥分片 (Secp256k1)...");
     let mut sim_keygen = Simulation::empty();
     for i in 0..n {
         sim_keygen.add_async_party(move |party| async move {
@@ -144,6 +443,152 @@ pub async fn run_dkg_and_save(
     Ok(complete_shares)
 }
 
+/// 为新委员会构造完整的 cggmp24 KeyShare (Committee Shares from Portable)
+///
+/// **功能**: 委员会轮换 (`bridge::rotate_committee`) 之后，用新分片的公开数据构造每个成员的
+/// `IncompleteKeyShare`，并为新委员会重新运行 AuxGen。旧委员会的 AuxInfo 不能复用，
+/// 因为 Paillier 密钥与 ring-Pedersen 参数是按参与方集合生成的。
+///
+/// **返回**: 按 party id 升序排列，`core.i` 是在新委员会中的位置。
+pub async fn build_cggmp_committee_shares(
+    shares: &[bridge::PortableKeyShare],
+    execution_id: ExecutionId<'_>,
+) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+    type E = cggmp24::supported_curves::Secp256k1;
+
+    let mut cores = shares
+        .iter()
+        .map(bridge::from_portable_to_cggmp_core::<E>)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    cores.sort_by_key(|core| core.i);
+
+    eprintln!(
+        "      [ROTATE] 为新委员会 ({} 方) 生成 cggmp24 辅助信息...",
+        cores.len()
+    );
+    let aux_infos = run_cggmp_aux_gen(execution_id, cores.len() as u16).await?;
+
+    cores
+        .into_iter()
+        .zip(aux_infos)
+        .map(|(core, aux)| {
+            let i = core.i;
+            KeyShare::from_parts((core, aux))
+                .map_err(|e| anyhow!("Combine failed {}: {:?}", i, e))
+        })
+        .collect()
+}
+
+/// cggmp24 原生辅助信息刷新 (Aux Info Refresh)
+///
+/// **功能**: 为同一委员会重新运行 `aux_info_gen`，替换每个 KeyShare 中的 ring-Pedersen 参数与证明，
+/// 私钥分片 (`core`) 保持不变。
+///
+/// **注意**: 演示环境复用 `data/primes.txt` 中的素数，因此 Paillier 模数 N 不变，只有随机部分会更新。
+/// 生产环境每个参与方每次刷新都必须用 `PregeneratedPrimes::generate` 生成新的素数。
+pub async fn run_cggmp_aux_refresh(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    execution_id: ExecutionId<'_>,
+) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+    let mut cores: Vec<_> = shares.iter().map(|s| s.core.clone()).collect();
+    cores.sort_by_key(|core| core.i);
+
+    eprintln!("      [REFRESH] cggmp24 AuxInfo 刷新 ({} 方)...", cores.len());
+    let aux_infos = run_cggmp_aux_gen(execution_id, cores.len() as u16).await?;
+
+    cores
+        .into_iter()
+        .zip(aux_infos)
+        .map(|(core, aux)| {
+            let i = core.i;
+            KeyShare::from_parts((core, aux))
+                .map_err(|e| anyhow!("Combine failed {}: {:?}", i, e))
+        })
+        .collect()
+}
+
+/// cggmp24 原生 Key Refresh (Native Key Refresh, n-of-n)
+///
+/// **功能**: 运行 `cggmp24::key_refresh`，一次完成私钥分片与辅助信息的刷新，共享公钥不变。
+///
+/// **前提**: cggmp24 的 `key_refresh` 只支持非门限密钥 (没有 `vss_setup`)。
+/// t-of-n 密钥请使用 `run_bridge_reshare_refresh`。
+///
+/// **注意**: 与 `run_cggmp_aux_refresh` 相同，演示环境复用 `data/primes.txt` 中的素数。
+///
+/// **返回**: 按 `core.i` 升序排列。
+pub async fn run_cggmp_key_refresh(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    execution_id: ExecutionId<'_>,
+) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+    let mut shares = shares.to_vec();
+    shares.sort_by_key(|s| s.core.i);
+    if shares.iter().any(|s| s.core.vss_setup.is_some()) {
+        return Err(anyhow!(
+            "cggmp24::key_refresh 只支持 n-of-n 密钥，t-of-n 密钥请使用 run_bridge_reshare_refresh"
+        ));
+    }
+
+    eprintln!(
+        "      [REFRESH] {}-of-{} 密钥: 运行 cggmp24::key_refresh...",
+        shares.len(),
+        shares.len()
+    );
+    let primes = load_or_generate_primes()?;
+    let mut simulation = Simulation::empty();
+    for share in shares {
+        let primes = primes.clone();
+        simulation.add_async_party(move |party| async move {
+            cggmp24::key_refresh(execution_id, &share, primes)
+                .start(&mut OsRng, party)
+                .await
+        });
+    }
+    let results = simulation
+        .run()
+        .map_err(|e| anyhow!("Key refresh failed: {:?}", e))?;
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(i, res)| res.map_err(|e| anyhow!("Party {} key refresh failed: {:?}", i, e)))
+        .collect()
+}
+
+/// 门限密钥的重共享刷新 (Bridge Reshare Refresh, t-of-n)
+///
+/// **功能**: 不经过 Synedrion 的 `KeyRefresh`，用本库的重共享协议刷新 t-of-n 密钥:
+/// 以相同阈值运行 `bridge::reshare_with_new_threshold` (Proactive Refresh，旧分片失效)，
+/// 再为委员会运行 `aux_info_gen` 生成新的辅助信息 (见 `build_cggmp_committee_shares`)。
+/// 这不是 cggmp24 的 `key_refresh`，后者只支持 n-of-n 密钥 (见 `run_cggmp_key_refresh`)。
+///
+/// **参数**: `committee` 是 `shares` 所属委员会的 party id -> 求值点映射 (见 `bridge::from_cggmp_to_portable`)。
+///
+/// **注意**: 辅助信息同样复用 `data/primes.txt` 中的素数 (见 `run_cggmp_aux_refresh`)。
+///
+/// **返回**: 按 `core.i` 升序排列，委员会与阈值不变。
+pub async fn run_bridge_reshare_refresh(
+    shares: &[KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>],
+    committee: &bridge::EvaluationPoints,
+    execution_id: ExecutionId<'_>,
+) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+    let portable_shares = shares
+        .iter()
+        .map(|share| bridge::from_cggmp_to_portable(share, Some(committee)))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let threshold = portable_shares.first().context("No key shares")?.t;
+    eprintln!(
+        "      [REFRESH] {}-of-{} 门限密钥: 以相同阈值重共享私钥分片...",
+        threshold,
+        portable_shares.len()
+    );
+    let refreshed = bridge::reshare_with_new_threshold(
+        portable_shares,
+        threshold,
+        bridge::resharing::VssScheme::Feldman,
+    )?;
+    build_cggmp_committee_shares(&refreshed, execution_id).await
+}
+
 pub async fn mock_run_cggmp_dkg(
     party_id: u16,
 ) -> anyhow::Result<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>> {
@@ -155,7 +600,7 @@ pub async fn mock_run_cggmp_dkg(
 }
 
 /// 检查 DKG 所需的原始数据文件是否存在
-fn check_dkg_files_exist(n: u16) -> bool {
+pub fn check_dkg_files_exist(n: u16) -> bool {
     for i in 0..n {
         let core_path = format!("data/incomplete_key_share_party_{}.json", i);
         let aux_path = format!("data/aux_info_party_{}.json", i);
@@ -166,68 +611,69 @@ fn check_dkg_files_exist(n: u16) -> bool {
     true
 }
 
-/// 获取现有的 DKG 密钥分片，如果不存在或不匹配则运行新的 DKG
+/// 只加载已有的 DKG 密钥分片 (Load Only)
+///
+/// **功能**: 读取 `data/` 下 n 个参与方的 DKG 数据，按 Party ID 排序返回。
+/// 数据不存在或无法解析时直接报错，不会运行 DKG，也不会修改任何文件。
+/// 适用于 `address` / `sign` 等只读操作。
+pub async fn load_dkg(
+    n_parties: u16,
+) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
+    if !check_dkg_files_exist(n_parties) {
+        return Err(anyhow!(
+            "未找到 {} 个参与方的 DKG 数据 (data/incomplete_key_share_party_*.json, data/aux_info_party_*.json)",
+            n_parties
+        ));
+    }
+    let mut shares = Vec::with_capacity(n_parties as usize);
+    for i in 0..n_parties {
+        let share = mock_run_cggmp_dkg(i)
+            .await
+            .with_context(|| format!("加载 Party {} 的 DKG 数据失败", i))?;
+        shares.push(share);
+    }
+    // 必须对份额按 Party ID 进行排序，确保后续协议参与方顺序一致
+    shares.sort_by_key(|s| s.core.i);
+    Ok(shares)
+}
+
+/// 获取现有的 DKG 密钥分片，如果不存在则运行新的 DKG
+///
+/// **注意**: 已有数据的阈值与 `threshold` 不一致时直接报错，不会删除或覆盖已有的密钥分片
+/// (否则会丢失原有的私钥)。需要新的密钥时请先手动移走 `data/`。
 pub async fn run_dkg(
     n_parties: u16,
     threshold: u16,
     execution_id: ExecutionId<'_>,
 ) -> anyhow::Result<Vec<KeyShare<cggmp24::supported_curves::Secp256k1, SecurityLevel128>>> {
-    let mut cggmp_shares = vec![];
-    let mut should_run_dkg = true;
-
     if check_dkg_files_exist(n_parties) {
-        println!("      [INFO] 检测到现有 DKG 数据，正在加载...");
-        let mut loaded_shares = vec![];
-        let mut load_success = true;
-
-        for i in 0..n_parties {
-            match mock_run_cggmp_dkg(i).await {
-                Ok(share) => loaded_shares.push(share),
-                Err(e) => {
-                    println!(
-                        "      [WARN] 加载 Party {} 数据失败: {}。将重新运行 DKG。",
-                        i, e
-                    );
-                    load_success = false;
-                    break;
-                }
-            }
-        }
-
-        if load_success {
-            if let Some(first_share) = loaded_shares.first() {
-                let portable = bridge::from_cggmp_to_portable(first_share)?;
-                let loaded_threshold = portable.t;
-                if loaded_threshold > 0 {
-                    println!(
-                        "      [INFO] 已加载 DKG 数据: min_signers={}",
-                        loaded_threshold
-                    );
-
-                    if loaded_threshold == threshold {
-                        cggmp_shares = loaded_shares;
-                        should_run_dkg = false;
-                    } else {
-                        println!("      [WARN] 数据文件阈值 ({}) 与代码配置 ({}) 不匹配。正在清除旧数据并重新运行 DKG...", loaded_threshold, threshold);
-                        let _ = fs::remove_dir_all("data");
-                        should_run_dkg = true;
-                    }
-                }
-            }
+        eprintln!("      [INFO] 检测到现有 DKG 数据，正在加载...");
+        let shares = load_dkg(n_parties).await?;
+        let first_share = shares.first().context("No key shares loaded")?;
+        // DKG 委员会是连续的 party id 0..n
+        let committee = bridge::EvaluationPoints::contiguous(n_parties);
+        let loaded_threshold = bridge::from_cggmp_to_portable(first_share, Some(&committee))?.t;
+        eprintln!(
+            "      [INFO] 已加载 DKG 数据: min_signers={}",
+            loaded_threshold
+        );
+        if loaded_threshold != threshold {
+            return Err(anyhow!(
+                "数据文件阈值 ({}) 与请求的阈值 ({}) 不匹配。为避免丢失已有密钥，不会重新运行 DKG；请先移走 data/ 目录",
+                loaded_threshold,
+                threshold
+            ));
         }
+        return Ok(shares);
     }
 
-    if should_run_dkg {
-        println!("      [INFO] 正在启动交互式 DKG 流程...");
-        fs::create_dir_all("data")?;
-        cggmp_shares = run_dkg_and_save(execution_id, n_parties, threshold).await?;
-    }
-
+    eprintln!("      [INFO] 正在启动交互式 DKG 流程...");
+    fs::create_dir_all("data")?;
+    let mut cggmp_shares = run_dkg_and_save(execution_id, n_parties, threshold).await?;
     if cggmp_shares.is_empty() {
-        return Err(anyhow!("未加载到有效的 Key Shares,且 DKG 未运行。"));
+        return Err(anyhow!("DKG 未产生有效的 Key Shares。"));
     }
 
-    // 这个地方确认下partyid 和 signer id的区别
     // 必须对份额按 Party ID 进行排序，确保后续协议参与方顺序一致
     cggmp_shares.sort_by_key(|s| s.core.i);
     Ok(cggmp_shares)
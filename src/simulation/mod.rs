@@ -1,7 +1,18 @@
+//! 协议模拟器 (Simulation)
+//!
+//! 在单个进程内运行所有参与方，用于演示与测试。生产环境中每个参与方应运行在独立的节点上，
+//! 对应的单方协议见 `network` 模块 (`network::cggmp` / `network::synedrion`)。
+
 pub mod common;
+pub mod presignature;
+#[cfg(feature = "cggmp24")]
 pub mod cggmp;
+#[cfg(feature = "synedrion")]
 pub mod synedrion;
 
 pub use common::*;
+pub use presignature::*;
+#[cfg(feature = "cggmp24")]
 pub use cggmp::*;
+#[cfg(feature = "synedrion")]
 pub use synedrion::*;
@@ -0,0 +1,278 @@
+//! 预签名池 (Presignature Pool)
+//!
+//! 离线阶段为某个签名者集合预先生成预签名，在线阶段只需要消息哈希即可在一轮内完成签名。
+//! 预签名只能使用一次: 重复使用同一份预签名对两条不同消息签名会直接泄露私钥。
+//!
+//! **一次性保证**:
+//! - `take` 在返回预签名之前先把它从池中移除并落盘，进程在签名途中崩溃也不会重复使用。
+//! - 每份预签名带一个随机 id，已消费的 id 会被记录，从备份中恢复的旧条目不能再次放入池中。
+//!
+//! **存储**: 每个签名者只在自己的池中保存自己的预签名。把多个签名者的预签名放在一起
+//! (例如同一个文件) 等同于把私钥交给读取该文件的人: 各方的 (k_i, chi_i) 之和就是 k 与 k·x。
+
+use anyhow::{anyhow, Context};
+use rand_core::{OsRng, RngCore};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 池中的一份预签名
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PoolEntry<T> {
+    /// 随机 id (hex)，用于一次性校验
+    pub id: String,
+    pub presignature: T,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound(serialize = "T: Serialize", deserialize = "T: DeserializeOwned"))]
+struct PoolFile<T> {
+    entries: BTreeMap<String, VecDeque<PoolEntry<T>>>,
+    consumed: BTreeSet<String>,
+}
+
+impl<T> Default for PoolFile<T> {
+    fn default() -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            consumed: BTreeSet::new(),
+        }
+    }
+}
+
+/// 持久化的一次性预签名池，按 `key` (签名者集合 + 密钥标识) 分组
+pub struct PresignaturePool<T> {
+    path: PathBuf,
+    state: PoolFile<T>,
+}
+
+impl<T: Serialize + DeserializeOwned> PresignaturePool<T> {
+    /// 打开 `path` 处的预签名池，文件不存在时创建空池
+    pub fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state = if path.exists() {
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse presignature pool {}", path.display()))?
+        } else {
+            PoolFile::default()
+        };
+        Ok(Self { path, state })
+    }
+
+    /// 放入一份新生成的预签名并落盘，返回其 id
+    pub fn push(&mut self, key: &str, presignature: T) -> anyhow::Result<String> {
+        let mut id_bytes = [0u8; 16];
+        OsRng.fill_bytes(&mut id_bytes);
+        let id = hex::encode(id_bytes);
+        self.insert(key, PoolEntry { id: id.clone(), presignature })?;
+        Ok(id)
+    }
+
+    /// 放入一份已有 id 的预签名 (例如从其他节点同步)，已消费过的 id 会被拒绝
+    pub fn insert(&mut self, key: &str, entry: PoolEntry<T>) -> anyhow::Result<()> {
+        let duplicate = self
+            .state
+            .entries
+            .values()
+            .flatten()
+            .any(|e| e.id == entry.id);
+        if self.state.consumed.contains(&entry.id) || duplicate {
+            return Err(anyhow!("预签名 {} 已被使用或已在池中", entry.id));
+        }
+        self.state
+            .entries
+            .entry(key.to_string())
+            .or_default()
+            .push_back(entry);
+        self.save()
+    }
+
+    /// 取出一份预签名 (先进先出)。返回前已从池中移除并落盘，调用方必须只使用一次。
+    pub fn take(&mut self, key: &str) -> anyhow::Result<Option<PoolEntry<T>>> {
+        match self.next_id(key).map(str::to_string) {
+            Some(id) => self.take_id(key, &id),
+            None => Ok(None),
+        }
+    }
+
+    /// `key` 下下一份预签名的 id (不取出)
+    ///
+    /// 各签名者的预签名以同一个 id 存放在各自的池中，在线阶段由一方提议 id，所有签名者用
+    /// `take_id` 取出同一次预签名协议的输出。
+    pub fn next_id(&self, key: &str) -> Option<&str> {
+        self.state
+            .entries
+            .get(key)
+            .and_then(|q| q.front())
+            .map(|e| e.id.as_str())
+    }
+
+    /// 取出 `key` 下 id 为 `id` 的预签名。返回前已从池中移除并落盘，调用方必须只使用一次。
+    pub fn take_id(&mut self, key: &str, id: &str) -> anyhow::Result<Option<PoolEntry<T>>> {
+        let entry = match self.state.entries.get_mut(key).and_then(|q| {
+            let position = q.iter().position(|e| e.id == id)?;
+            q.remove(position)
+        }) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        if self.state.entries.get(key).is_some_and(|q| q.is_empty()) {
+            self.state.entries.remove(key);
+        }
+        self.state.consumed.insert(entry.id.clone());
+        self.save()?;
+        Ok(Some(entry))
+    }
+
+    /// `key` 下剩余的预签名数量
+    pub fn available(&self, key: &str) -> usize {
+        self.state.entries.get(key).map_or(0, |q| q.len())
+    }
+
+    /// 丢弃 `key` 下的全部预签名 (例如密钥刷新后旧预签名失效)，返回丢弃的数量
+    pub fn discard(&mut self, key: &str) -> anyhow::Result<usize> {
+        let removed = self.state.entries.remove(key).unwrap_or_default();
+        self.state
+            .consumed
+            .extend(removed.iter().map(|e| e.id.clone()));
+        self.save()?;
+        Ok(removed.len())
+    }
+
+    /// 先写临时文件再重命名，避免写到一半时崩溃留下损坏的池文件
+    fn save(&self) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(&self.state)?)?;
+        fs::rename(&tmp_path, &self.path)
+            .with_context(|| format!("Failed to persist {}", self.path.display()))
+    }
+}
+
+/// 预签名池的分组 key: 共享公钥 + 密钥分片 epoch + 签名者集合 (升序)
+///
+/// `key_epoch` 标识生成预签名时的密钥分片 (例如公钥分片的哈希)。密钥刷新后 epoch 改变，
+/// 旧预签名不会再被取出。
+pub fn signer_set_key(public_key_hex: &str, key_epoch: &str, signers: &[u16]) -> String {
+    let mut signers = signers.to_vec();
+    signers.sort();
+    let ids: Vec<String> = signers.iter().map(|id| id.to_string()).collect();
+    format!("{}/{}:{}", public_key_hex, key_epoch, ids.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每个测试使用独立的池文件，避免并行测试互相干扰
+    fn pool_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "mpc-bridge-presignature-{}-{}.json",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn entry(id: &str, presignature: u32) -> PoolEntry<u32> {
+        PoolEntry {
+            id: id.to_string(),
+            presignature,
+        }
+    }
+
+    #[test]
+    fn take_removes_entry_and_records_consumed_id() {
+        let path = pool_path("take");
+        let mut pool = PresignaturePool::open(&path).unwrap();
+        let first = pool.push("k", 1).unwrap();
+        let second = pool.push("k", 2).unwrap();
+        assert_eq!(pool.next_id("k"), Some(first.as_str()));
+
+        let taken = pool.take("k").unwrap().unwrap();
+        assert_eq!((taken.id.as_str(), taken.presignature), (first.as_str(), 1));
+        assert!(pool.state.consumed.contains(&first));
+        assert_eq!(pool.available("k"), 1);
+
+        let taken = pool.take_id("k", &second).unwrap().unwrap();
+        assert_eq!(taken.presignature, 2);
+        assert!(pool.state.consumed.contains(&second));
+        assert_eq!(pool.available("k"), 0);
+        assert!(!pool.state.entries.contains_key("k"));
+        assert!(pool.take("k").unwrap().is_none());
+        assert!(pool.take_id("k", &second).unwrap().is_none());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn insert_rejects_consumed_and_duplicate_ids() {
+        let path = pool_path("insert");
+        let mut pool = PresignaturePool::open(&path).unwrap();
+        pool.insert("k", entry("a", 1)).unwrap();
+        // 同一 id 不能再次放入，即使分组 key 不同
+        assert!(pool.insert("k", entry("a", 2)).is_err());
+        assert!(pool.insert("other", entry("a", 2)).is_err());
+
+        pool.take_id("k", "a").unwrap().unwrap();
+        // 从备份恢复的已消费条目被拒绝
+        assert!(pool.insert("k", entry("a", 1)).is_err());
+        assert_eq!(pool.available("k"), 0);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reopened_pool_keeps_entries_and_consumed_ids() {
+        let path = pool_path("reopen");
+        let consumed = {
+            let mut pool = PresignaturePool::open(&path).unwrap();
+            pool.insert("k", entry("a", 1)).unwrap();
+            pool.insert("k", entry("b", 2)).unwrap();
+            pool.take("k").unwrap().unwrap().id
+        };
+        assert_eq!(consumed, "a");
+
+        let mut pool = PresignaturePool::<u32>::open(&path).unwrap();
+        assert!(pool.state.consumed.contains("a"));
+        assert_eq!(pool.available("k"), 1);
+        assert_eq!(pool.next_id("k"), Some("b"));
+        assert!(pool.insert("k", entry("a", 1)).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn discard_marks_entries_consumed() {
+        let path = pool_path("discard");
+        let mut pool = PresignaturePool::open(&path).unwrap();
+        pool.insert("old", entry("a", 1)).unwrap();
+        pool.insert("old", entry("b", 2)).unwrap();
+        pool.insert("new", entry("c", 3)).unwrap();
+
+        assert_eq!(pool.discard("old").unwrap(), 2);
+        assert_eq!(pool.available("old"), 0);
+        assert_eq!(pool.available("new"), 1);
+        assert!(pool.state.consumed.contains("a") && pool.state.consumed.contains("b"));
+        assert!(pool.insert("old", entry("a", 1)).is_err());
+        assert_eq!(pool.discard("missing").unwrap(), 0);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn signer_set_key_ignores_signer_order() {
+        assert_eq!(
+            signer_set_key("02ab", "e1", &[3, 0, 2]),
+            signer_set_key("02ab", "e1", &[0, 2, 3])
+        );
+        assert_eq!(signer_set_key("02ab", "e1", &[2, 0]), "02ab/e1:0,2");
+        assert_ne!(
+            signer_set_key("02ab", "e1", &[0, 2]),
+            signer_set_key("02ab", "e2", &[0, 2])
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use super::common::{
-    truncate_hex, FastSecp256k1, SimpleSessionParams, SimpleSigner, SimpleVerifier,
+    calc_recovery_id_from_pubkey, truncate_hex, FastSecp256k1, SimpleSessionParams,
+    SimpleSigner, SimpleVerifier,
 };
 use crate::bridge;
-use crate::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
 use anyhow::{anyhow, Context};
 use elliptic_curve::CurveArithmetic;
 use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
@@ -11,7 +11,15 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::Path;
 
+/// AuxGen 缓存文件，按参与方集合区分 (例如 `data/synedrion_aux_gen_0-2-3.json`)
+fn aux_gen_cache_path(party_ids: &BTreeSet<u16>) -> String {
+    let ids: Vec<String> = party_ids.iter().map(|id| id.to_string()).collect();
+    format!("data/synedrion_aux_gen_{}.json", ids.join("-"))
+}
+
 /// 运行 Synedrion 原生的 AuxGen 协议生成辅助信息 (Paillier 密钥等)
+///
+/// 结果按参与方集合缓存，不同委员会的 AuxInfo 互不覆盖。
 pub async fn run_synedrion_aux_gen<P>(
     party_ids: BTreeSet<u16>,
 ) -> anyhow::Result<BTreeMap<u16, synedrion::AuxInfo<P, u16>>>
@@ -24,31 +32,32 @@ where
     use manul::dev::tokio::run_async;
     use synedrion::AuxGen;
 
-    let cache_path = "data/synedrion_aux_gen.json";
+    let cache_path = aux_gen_cache_path(&party_ids);
+    let cache_path = cache_path.as_str();
     if Path::new(cache_path).exists() {
-        println!("      [INFO] 检测到 Synedrion AuxGen 缓存文件，尝试加载...");
+        eprintln!("      [INFO] 检测到 Synedrion AuxGen 缓存文件，尝试加载...");
         match fs::read_to_string(cache_path) {
             Ok(content) => {
                 match serde_json::from_str::<BTreeMap<u16, synedrion::AuxInfo<P, u16>>>(&content) {
                     Ok(cached_data) => {
                         let cached_keys: BTreeSet<u16> = cached_data.keys().cloned().collect();
                         if cached_keys == party_ids {
-                            println!("      [INFO] 成功加载缓存的 AuxInfo");
+                            eprintln!("      [INFO] 成功加载缓存的 AuxInfo");
                             return Ok(cached_data);
                         }
-                        println!(
+                        eprintln!(
                             "      [WARN] 缓存的参与方 ({:?}) 与请求 ({:?}) 不匹配，将重新生成。",
                             cached_keys, party_ids
                         );
                     }
-                    Err(e) => println!("      [WARN] 缓存文件解析失败: {}，将重新生成。", e),
+                    Err(e) => eprintln!("      [WARN] 缓存文件解析失败: {}，将重新生成。", e),
                 }
             }
-            Err(e) => println!("      [WARN] 读取缓存文件失败: {}，将重新生成。", e),
+            Err(e) => eprintln!("      [WARN] 读取缓存文件失败: {}，将重新生成。", e),
         }
     }
 
-    println!("      (模拟) Synedrion AuxGen Protocol Start...");
+    eprintln!("      (模拟) Synedrion AuxGen Protocol Start...");
 
     let mut entry_points = Vec::new();
     let verifiers: BTreeSet<SimpleVerifier> =
@@ -82,15 +91,124 @@ where
 
     if let Ok(json) = serde_json::to_string_pretty(&converted_results) {
         let _ = fs::write(cache_path, json);
-        println!("      [INFO] Synedrion AuxInfo 已保存至 {}", cache_path);
+        eprintln!("      [INFO] Synedrion AuxInfo 已保存至 {}", cache_path);
     }
 
     Ok(converted_results)
 }
 
+/// 运行 Synedrion 原生的 KeyInit 协议 (DKG)
+///
+/// **功能**: 不依赖 cggmp24，直接由 Synedrion 为 `party_ids` 生成新密钥。
+/// 输出的 `KeyShare` 是 n-of-n 加法分片 (sum x_i = x)，按 party id 索引。
+///
+/// **生产环境通信**: **涉及**。每个参与方在自己的节点上运行 `KeyInit`。
+pub async fn run_synedrion_key_init<P>(
+    party_ids: BTreeSet<u16>,
+) -> anyhow::Result<BTreeMap<u16, synedrion::KeyShare<P, u16>>>
+where
+    P: synedrion::SchemeParams + Send + Sync + 'static,
+    <P::Curve as CurveArithmetic>::ProjectivePoint: Send + Sync,
+    <P::Curve as CurveArithmetic>::Scalar: Send + Sync,
+{
+    use manul::dev::tokio::run_async;
+    use synedrion::KeyInit;
+
+    eprintln!("      (模拟) Synedrion KeyInit Protocol Start...");
+
+    let verifiers: BTreeSet<SimpleVerifier> =
+        party_ids.iter().map(|&id| SimpleVerifier(id)).collect();
+    let mut entry_points = Vec::new();
+    for &id in &party_ids {
+        let signer = SimpleSigner {
+            id,
+            verifier: SimpleVerifier(id),
+        };
+        let entry_point = KeyInit::<P, SimpleVerifier>::new(verifiers.clone())
+            .map_err(|e| anyhow!("Failed to create KeyInit: {}", e))?;
+        entry_points.push((signer, entry_point));
+    }
+
+    let execution_result = run_async::<_, SimpleSessionParams>(&mut OsRng, entry_points, true)
+        .await
+        .map_err(|e| anyhow!("KeyInit simulation failed: {}", e))?;
+    let results = execution_result
+        .results()
+        .map_err(|e| anyhow!("KeyInit protocol execution failed: {}", e))?;
+
+    let mut shares = BTreeMap::new();
+    for (verifier, share) in results {
+        let share = bridge::convert_synedrion_key_share::<P, SimpleVerifier, u16>(&share)?;
+        shares.insert(verifier.0, share);
+    }
+    Ok(shares)
+}
+
+/// 将 Synedrion KeyInit 生成的密钥迁移到 cggmp24 (synedrion -> portable -> cggmp24)
+///
+/// **功能**: 面向先用 Synedrion 建钥、之后迁移到 cggmp24 的团队。
+///
+/// **流程**:
+/// 1. `from_synedrion_to_portable` 导出每个参与方的加法分片 (KeyInit 输出是 n-of-n)。
+/// 2. `additive_portable_to_shamir_portable` 以阈值 `threshold` 重共享为 Shamir 分片，
+///    求值点 x = id + 1，全局公钥不变。
+/// 3. `build_cggmp_committee_shares` 构造 `IncompleteKeyShare` 并运行 cggmp24 AuxGen。
+///
+/// **返回**: 按 party id 升序排列，`core.i` 是在委员会中的位置。
+#[cfg(feature = "cggmp24")]
+pub async fn synedrion_key_init_to_cggmp<P>(
+    shares: &BTreeMap<u16, synedrion::KeyShare<P, u16>>,
+    threshold: u16,
+    execution_id: cggmp24::ExecutionId<'_>,
+) -> anyhow::Result<
+    Vec<
+        cggmp24::KeyShare<
+            cggmp24::supported_curves::Secp256k1,
+            cggmp24::security_level::SecurityLevel128,
+        >,
+    >,
+>
+where
+    P: synedrion::SchemeParams,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
+{
+    let first = shares.values().next().context("No Synedrion key shares")?;
+    let y_hex = hex::encode(
+        bridge::get_global_public_key_point(first)?
+            .to_encoded_point(true)
+            .as_bytes(),
+    );
+    let n = shares.len() as u16;
+
+    let mut additive_shares = Vec::with_capacity(shares.len());
+    for share in shares.values() {
+        let mut portable = bridge::from_synedrion_to_portable(share, y_hex.clone())?;
+        portable.kind = bridge::ShareKind::Additive {
+            signers: shares.keys().copied().collect(),
+        };
+        portable.t = n;
+        additive_shares.push(portable);
+    }
+
+    let points =
+        bridge::EvaluationPoints::from_ids(shares.keys().copied(), bridge::IndexBase::Zero)
+            .map_err(|e| anyhow!(e))?;
+    let shamir_shares = bridge::additive_portable_to_shamir_portable(
+        additive_shares,
+        &points,
+        threshold,
+        bridge::resharing::VssScheme::Feldman,
+    )?;
+    super::cggmp::build_cggmp_committee_shares(&shamir_shares, execution_id).await
+}
+
+/// 运行 Synedrion KeyRefresh 模拟
+///
+/// **参与方**: `committee` 中的 party id，必须与分片的 owner 一一对应 (不要求连续)。
 pub async fn run_synedrion_refresh_simulation<P>(
     shares: Vec<synedrion::KeyShare<P, u16>>,
-    _t: u16,
+    committee: &bridge::EvaluationPoints,
 ) -> anyhow::Result<
     BTreeMap<
         SimpleVerifier,
@@ -111,12 +229,19 @@ where
     use manul::dev::tokio::run_async;
     use synedrion::KeyRefresh;
 
-    println!("      (模拟) Synedrion Refresh Protocol Start...");
-    let n = shares.len() as u16;
-    if n == 0 {
+    eprintln!("      (模拟) Synedrion Refresh Protocol Start...");
+    if shares.is_empty() {
         return Ok(BTreeMap::new());
     }
-    let party_ids: BTreeSet<u16> = (0..n).collect();
+    let party_ids: BTreeSet<u16> = committee.ids().into_iter().collect();
+    let owners: BTreeSet<u16> = shares.iter().map(|s| *s.owner()).collect();
+    if owners != party_ids {
+        return Err(anyhow!(
+            "分片 owner {:?} 与委员会 {:?} 不一致",
+            owners,
+            party_ids
+        ));
+    }
 
     let mut original_public_shares = BTreeMap::new();
     for share in &shares {
@@ -126,12 +251,12 @@ where
     }
 
     let mut entry_points = Vec::new();
-    for (i, share) in shares.iter().enumerate() {
+    for share in &shares {
+        let id = *share.owner();
         let signer = SimpleSigner {
-            id: i as u16,
-            verifier: SimpleVerifier(i as u16),
+            id,
+            verifier: SimpleVerifier(id),
         };
-        let _share_conv = bridge::convert_synedrion_key_share::<P, u16, SimpleVerifier>(share)?;
         let ids_conv: BTreeSet<SimpleVerifier> =
             party_ids.iter().map(|&id| SimpleVerifier(id)).collect();
         let entry_point = KeyRefresh::<P, SimpleVerifier>::new(ids_conv)
@@ -145,7 +270,7 @@ where
     let results = execution_result
         .results()
         .map_err(|e| anyhow!("Protocol execution failed: {}", e))?;
-    println!("\n      === Refresh 结果摘要 ===");
+    eprintln!("\n      === Refresh 结果摘要 ===");
     for (verifier, (new_share, new_aux)) in &results {
         let new_n =
             bridge::get_aux_n_hex(new_aux, *verifier).unwrap_or_else(|_| "unknown".to_string());
@@ -166,15 +291,16 @@ where
             .map(|p| hex::encode(p.to_encoded_point(true).as_bytes()))
             .unwrap_or_else(|| "N/A".to_string());
 
-        println!("       Party {}:", verifier.0);
-        println!(
+        eprintln!("       Party {}:", verifier.0);
+        eprintln!(
             "        - [ECDSA] 全局公钥 (Refresh后): 0x{}",
             refreshed_pk_hex
         );
-        println!("        - [Paillier] 新 N = {}", truncate_hex(&new_n));
+        eprintln!("        - [Paillier] 新 N = {}", truncate_hex(&new_n));
     }
     Ok(results)
 }
+
 pub async fn run_synedrion_signing_simulation<P>(
     parties_data: &BTreeMap<
         SimpleVerifier,
@@ -191,28 +317,81 @@ where
         FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
     elliptic_curve::FieldBytes<P::Curve>: From<[u8; 32]>,
 {
-    use manul::dev::tokio::run_async;
-    use synedrion::InteractiveSigning;
+    eprintln!("      Synedrion Signing Protocol Start...");
+    let signing_inputs = filter_signing_inputs(parties_data)?;
+    sign_with_inputs(&signing_inputs, message_hash).await
+}
 
-    println!("      Synedrion Signing Protocol Start...");
-    for (verifier, (_, aux)) in parties_data {
-        let n = bridge::get_aux_n_hex(aux, *verifier).unwrap_or_else(|_| "unknown".to_string());
+/// Synedrion 批量签名 (Batch Signing)
+///
+/// **功能**: 签名者集合与输入过滤只做一次，随后依次为每个消息哈希运行 `InteractiveSigning`。
+/// 某条消息失败不会中断整个批次。
+///
+/// **返回**: 与 `message_hashes` 一一对应的结果列表；外层错误只表示签名者输入本身无效。
+pub async fn run_synedrion_batch_signing<P>(
+    parties_data: &BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<P, SimpleVerifier>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+    message_hashes: &[[u8; 32]],
+) -> anyhow::Result<Vec<anyhow::Result<([u8; 32], [u8; 32], u8)>>>
+where
+    P: synedrion::SchemeParams + Send + Sync + 'static,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
+    elliptic_curve::FieldBytes<P::Curve>: From<[u8; 32]>,
+{
+    eprintln!(
+        "      Synedrion batch signing {} messages with participants: {:?}",
+        message_hashes.len(),
+        parties_data.keys().map(|v| v.0).collect::<Vec<_>>()
+    );
+    let signing_inputs = filter_signing_inputs(parties_data)?;
+
+    let mut results = Vec::with_capacity(message_hashes.len());
+    for (index, message_hash) in message_hashes.iter().enumerate() {
+        let result = sign_with_inputs(&signing_inputs, *message_hash)
+            .await
+            .with_context(|| format!("Message {} ({}) failed", index, hex::encode(message_hash)));
+        if let Err(e) = &result {
+            eprintln!("      [WARN] {:#}", e);
+        }
+        results.push(result);
     }
- 
+    Ok(results)
+}
+
+/// 过滤 KeyShare / AuxInfo 中的 public 列表，只保留活跃的签名者
+///
+/// 否则 Synedrion 协议会尝试向未参与的节点发送消息，导致 "channel closed unexpectedly" 错误,
+/// 只会在本地模拟环境出现这个问题。Synedrion 还要求 KeyShare 和 AuxInfo 包含相同的参与方集合。
+fn filter_signing_inputs<P>(
+    parties_data: &BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<P, SimpleVerifier>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+) -> anyhow::Result<
+    BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<P, SimpleVerifier>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+>
+where
+    P: synedrion::SchemeParams,
+{
     // 确定当前参与签名的活跃节点集合
     let active_verifiers: BTreeSet<SimpleVerifier> = parties_data.keys().cloned().collect();
-
-    let mut entry_points = Vec::new();
-    for (verifier, (share, aux)) in parties_data {
-        let signer = SimpleSigner {
-            id: verifier.0,
-            verifier: *verifier,
-        };
-
-        // 过滤 KeyShare 中的 public 列表，只保留活跃的参与方
-        // 否则 Synedrion 协议会尝试向未参与的节点发送消息，导致 "channel closed unexpectedly" 错误, 只会在本地模拟环境出现这个问题
-        let mut share_val = serde_json::to_value(share)?;
-        if let Some(public_list) = share_val.get_mut("public").and_then(|v| v.as_array_mut()) {
+    let retain_active = |value: &mut serde_json::Value| {
+        if let Some(public_list) = value.get_mut("public").and_then(|v| v.as_array_mut()) {
             public_list.retain(|item| {
                 if let Some(id_val) = item.get(0) {
                     if let Ok(id) = serde_json::from_value::<SimpleVerifier>(id_val.clone()) {
@@ -222,25 +401,52 @@ where
                 false
             });
         }
-        let filtered_share: synedrion::KeyShare<P, SimpleVerifier> =
-            serde_json::from_value(share_val)?;
+    };
 
-        // [Fix] 同样过滤 AuxInfo 中的 public 列表，确保与 KeyShare 的参与方集合一致
-        // Synedrion 要求 KeyShare 和 AuxInfo 包含相同的参与方集合，否则报错
+    let mut filtered = BTreeMap::new();
+    for (verifier, (share, aux)) in parties_data {
+        let mut share_val = serde_json::to_value(share)?;
+        retain_active(&mut share_val);
         let mut aux_val = serde_json::to_value(aux)?;
-        if let Some(public_list) = aux_val.get_mut("public").and_then(|v| v.as_array_mut()) {
-            public_list.retain(|item| {
-                if let Some(id_val) = item.get(0) {
-                    if let Ok(id) = serde_json::from_value::<SimpleVerifier>(id_val.clone()) {
-                        return active_verifiers.contains(&id);
-                    }
-                }
-                false
-            });
-        }
-        let filtered_aux: synedrion::AuxInfo<P, SimpleVerifier> = serde_json::from_value(aux_val)?;
+        retain_active(&mut aux_val);
+        filtered.insert(
+            *verifier,
+            (
+                serde_json::from_value(share_val)?,
+                serde_json::from_value(aux_val)?,
+            ),
+        );
+    }
+    Ok(filtered)
+}
 
-        let protocol = InteractiveSigning::new(message_hash.into(), filtered_share, filtered_aux)
+/// 用已过滤的输入运行一次完整的 `InteractiveSigning`
+async fn sign_with_inputs<P>(
+    signing_inputs: &BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<P, SimpleVerifier>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+    message_hash: [u8; 32],
+) -> anyhow::Result<([u8; 32], [u8; 32], u8)>
+where
+    P: synedrion::SchemeParams + Send + Sync + 'static,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
+    elliptic_curve::FieldBytes<P::Curve>: From<[u8; 32]>,
+{
+    use manul::dev::tokio::run_async;
+    use synedrion::InteractiveSigning;
+
+    let mut entry_points = Vec::new();
+    for (verifier, (share, aux)) in signing_inputs {
+        let signer = SimpleSigner {
+            id: verifier.0,
+            verifier: *verifier,
+        };
+        let protocol = InteractiveSigning::new(message_hash.into(), share.clone(), aux.clone())
             .map_err(|e| anyhow!("Synedrion error: {:?}", e))?;
         entry_points.push((signer, protocol));
     }
@@ -264,13 +470,10 @@ where
         .try_into()
         .map_err(|_| anyhow!("s error"))?;
 
-    let first_share = &parties_data.values().next().unwrap().0;
-    let global_pk_point =
-        bridge::get_global_public_key_point(first_share).expect("Failed to get global pk");
+    let first_share = &signing_inputs.values().next().context("No signers")?.0;
+    let global_pk_point = bridge::get_global_public_key_point(first_share)?;
     let pk_bytes = global_pk_point.to_encoded_point(false).as_bytes().to_vec();
-    let expected_addr = compute_eth_address_from_pubkey(&pk_bytes);
-    let v = calc_recovery_id(&r_bytes, &s_bytes, &message_hash, expected_addr)
-        .map_err(|e| anyhow!(e))?;
+    let v = calc_recovery_id_from_pubkey(&r_bytes, &s_bytes, &message_hash, &pk_bytes)?;
 
     Ok((r_bytes, s_bytes, v))
 }
@@ -280,7 +483,7 @@ pub async fn run_refresh_workflow(
         synedrion::KeyShare<FastSecp256k1, u16>,
         synedrion::AuxInfo<FastSecp256k1, u16>,
     )>,
-    threshold: u16,
+    committee: &bridge::EvaluationPoints,
     cache_path: &str,
     force_refresh: bool,
 ) -> anyhow::Result<
@@ -296,28 +499,22 @@ pub async fn run_refresh_workflow(
     let mut cache_loaded = false;
 
     if Path::new(cache_path).exists() && !force_refresh {
-        let result: anyhow::Result<()> = (|| {
-            let content = fs::read_to_string(cache_path)?;
-            updated_shares = serde_json::from_str(&content)?;
-            Ok(())
-        })();
-
-        if let Ok(_) = result {
-            println!("       Synedrion 刷新后数据缓存加载完成。");
-            cache_loaded = true;
-        } else {
-            println!(
-                "       缓存数据格式不匹配或损坏 ({:?})，将重新运行 Key Refresh...",
-                result.err().unwrap()
-            );
-            updated_shares.clear();
-        }
+        let content = fs::read_to_string(cache_path)
+            .with_context(|| format!("Failed to read refresh cache {}", cache_path))?;
+        updated_shares = serde_json::from_str(&content).with_context(|| {
+            format!(
+                "刷新缓存 {} 格式不匹配或损坏 (可使用 force_refresh 重新运行 Key Refresh)",
+                cache_path
+            )
+        })?;
+        eprintln!("       Synedrion 刷新后数据缓存加载完成。");
+        cache_loaded = true;
     }
 
     if !cache_loaded {
         let synedrion_data_backup = synedrion_data.clone();
         let shares_only: Vec<_> = synedrion_data.into_iter().map(|(s, _)| s).collect();
-        let refreshed_changes = run_synedrion_refresh_simulation(shares_only, threshold).await?;
+        let refreshed_changes = run_synedrion_refresh_simulation(shares_only, committee).await?;
 
         for (share, aux) in synedrion_data_backup {
             let (share_conv, aux_conv) =
@@ -327,15 +524,373 @@ pub async fn run_refresh_workflow(
             let verifier = *share_conv.owner();
 
             if let Some((change, _new_aux)) = refreshed_changes.get(&verifier) {
-                let updated_share = share_conv.update(change.clone()).expect("Update failed");
+                let updated_share = share_conv.update(change.clone()).map_err(|e| {
+                    anyhow!("Failed to apply KeyShareChange for {:?}: {:?}", verifier, e)
+                })?;
                 updated_shares.insert(verifier, (updated_share, aux_conv));
             }
         }
 
         let json = serde_json::to_string_pretty(&updated_shares)?;
         fs::write(cache_path, json)?;
-        println!("       刷新后的 Synedrion 数据已保存至 {}", cache_path);
+        eprintln!("       刷新后的 Synedrion 数据已保存至 {}", cache_path);
     }
 
     Ok(updated_shares)
 }
+
+/// 将 cggmp24 分片批量转换为 Synedrion 分片 (cggmp24 -> portable -> synedrion)
+///
+/// **功能**: 对每个参与方执行 `from_cggmp_to_portable` + `from_portable_to_synedrion`。
+/// cggmp24 的 KeyShare 已经包含所有人的公钥分片，它们随 PortableKeyShare 一起导入，
+/// 因此不需要额外一轮广播来交换公钥分片。
+///
+/// **索引映射**: `committee` 给出 cggmp24 分片所属委员会的 party id 与求值点，`aux_map` 按 `core.i` 查找。
+#[cfg(feature = "cggmp24")]
+pub fn cggmp_shares_to_synedrion<P>(
+    cggmp_shares: &[cggmp24::KeyShare<
+        cggmp24::supported_curves::Secp256k1,
+        cggmp24::security_level::SecurityLevel128,
+    >],
+    committee: &bridge::EvaluationPoints,
+    aux_map: &BTreeMap<u16, synedrion::AuxInfo<P, u16>>,
+) -> anyhow::Result<Vec<(synedrion::KeyShare<P, u16>, synedrion::AuxInfo<P, u16>)>>
+where
+    P: synedrion::SchemeParams,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve>,
+{
+    let mut synedrion_data = Vec::with_capacity(cggmp_shares.len());
+    for share in cggmp_shares {
+        let portable = bridge::from_cggmp_to_portable(share, Some(committee))?;
+        let synedrion_share = bridge::from_portable_to_synedrion::<P>(&portable)?;
+        let aux = aux_map
+            .get(&share.core.i)
+            .cloned()
+            .context("Missing generated AuxInfo for party")?;
+        synedrion_data.push((synedrion_share, aux));
+    }
+    Ok(synedrion_data)
+}
+
+/// 将刷新后的 Synedrion 分片写回 cggmp24 (synedrion -> portable -> cggmp24)
+///
+/// **功能**: Synedrion 的刷新增量是加法形式，先由每个参与方用 `bridge::deal_refresh_delta`
+/// 把自己的增量重共享为 t-of-n 分片，再用 `bridge::apply_refresh_deltas` 换算到 Shamir 域，
+/// 每个参与方只用自己的私钥分片 + 收到的子分片与公开数据更新自己的 cggmp24 模板。
+/// 加法增量 D_j 取自刷新前后的公钥分片之差 (与 `KeyShareChange` 中的公钥增量相同)。
+///
+/// **索引映射**: 模板的 `core.i` 是 cggmp24 中的位置，对应 `committee` 中第 `core.i` 个 party id。
+#[cfg(feature = "cggmp24")]
+pub fn synedrion_shares_to_cggmp(
+    templates: &[cggmp24::KeyShare<
+        cggmp24::supported_curves::Secp256k1,
+        cggmp24::security_level::SecurityLevel128,
+    >],
+    updated_shares: &BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<FastSecp256k1, SimpleVerifier>,
+            synedrion::AuxInfo<FastSecp256k1, SimpleVerifier>,
+        ),
+    >,
+    y_hex: &str,
+    threshold: u16,
+    committee: &bridge::EvaluationPoints,
+) -> anyhow::Result<
+    Vec<
+        cggmp24::KeyShare<
+            cggmp24::supported_curves::Secp256k1,
+            cggmp24::security_level::SecurityLevel128,
+        >,
+    >,
+> {
+    // 1. 每个参与方分发自己的刷新增量 (模拟中子分片直接在进程内传递)
+    let mut old_portables = Vec::with_capacity(templates.len());
+    let mut synedrion_public_shares = None;
+    let mut dealings = Vec::with_capacity(templates.len());
+    for template in templates {
+        let old = bridge::from_cggmp_to_portable(template, Some(committee))?;
+        if old.t != threshold {
+            return Err(anyhow!(
+                "Party {} 的阈值 {} 与 {} 不一致",
+                old.i,
+                old.t,
+                threshold
+            ));
+        }
+        let (share, _) = updated_shares
+            .get(&SimpleVerifier(old.i))
+            .with_context(|| format!("Missing refreshed data for party {}", old.i))?;
+        let refreshed = bridge::from_synedrion_to_portable(share, y_hex.to_string())?;
+        dealings.push(bridge::deal_refresh_delta(
+            &old,
+            &refreshed.x_hex,
+            committee,
+        )?);
+        synedrion_public_shares = synedrion_public_shares.or(refreshed.public_shares);
+        old_portables.push(old);
+    }
+
+    // 2. 加法增量 D_j = X_j(刷新后) - X_j(刷新前)
+    let first = old_portables.first().context("No key shares provided")?;
+    let old_public_shares = first
+        .public_shares
+        .as_deref()
+        .context("Template KeyShare has no public shares")?;
+    let synedrion_public_shares =
+        synedrion_public_shares.context("刷新后的 Synedrion 分片没有公钥分片列表")?;
+    let mut additive_deltas = BTreeMap::new();
+    for ps in old_public_shares {
+        let refreshed = synedrion_public_shares
+            .iter()
+            .find(|r| r.i == ps.i)
+            .with_context(|| format!("Missing refreshed public share for party {}", ps.i))?;
+        additive_deltas.insert(
+            ps.i,
+            bridge::parse_k256_point(&refreshed.point_hex)?
+                - bridge::parse_k256_point(&ps.point_hex)?,
+        );
+    }
+
+    // 3. 每个参与方在本地换算到 Shamir 域并更新自己的模板
+    let mut updated_cggmp_shares = Vec::with_capacity(templates.len());
+    for (template, old) in templates.iter().zip(&old_portables) {
+        let portable = bridge::apply_refresh_deltas(old, &additive_deltas, &dealings)?;
+        updated_cggmp_shares.push(bridge::from_portable_to_cggmp(
+            template, &portable, None, None,
+        )?);
+    }
+    Ok(updated_cggmp_shares)
+}
+
+/// 为新委员会构造 Synedrion 分片 (portable -> synedrion)
+///
+/// **功能**: 委员会轮换后，新成员的 Shamir 分片连同所有人的公钥分片导入 Synedrion，
+/// 并按新委员会的 party id 集合运行 AuxGen。party id 可以不连续。
+pub async fn portable_shares_to_synedrion<P>(
+    shares: &[bridge::PortableKeyShare],
+) -> anyhow::Result<BTreeMap<u16, (synedrion::KeyShare<P, u16>, synedrion::AuxInfo<P, u16>)>>
+where
+    P: synedrion::SchemeParams + Send + Sync + 'static,
+    P::Paillier: Send + Sync,
+    <P::Curve as CurveArithmetic>::ProjectivePoint:
+        FromEncodedPoint<P::Curve> + ToEncodedPoint<P::Curve> + Send + Sync,
+    <P::Curve as CurveArithmetic>::Scalar: Send + Sync,
+{
+    let party_ids: BTreeSet<u16> = shares.iter().map(|s| s.i).collect();
+    let mut aux_map = run_synedrion_aux_gen::<P>(party_ids).await?;
+
+    let mut synedrion_data = BTreeMap::new();
+    for share in shares {
+        let synedrion_share = bridge::from_portable_to_synedrion::<P>(share)?;
+        let aux = aux_map
+            .remove(&share.i)
+            .with_context(|| format!("Missing generated AuxInfo for party {}", share.i))?;
+        synedrion_data.insert(share.i, (synedrion_share, aux));
+    }
+    Ok(synedrion_data)
+}
+
+/// 将 Synedrion 分片一次性导入为门限分片 (KeyShare -> portable -> ThresholdKeyShare)
+///
+/// **功能**: 把 Shamir 分片连同阈值与委员会求值点导入 `synedrion::ThresholdKeyShare`。
+/// 导入只需进行一次，之后任意 t 个签名者都可以通过 `threshold_signing_subset` 直接签名。
+///
+/// **生产环境通信**: **不涉及**。每个参与方在本地转换自己的分片。
+pub fn synedrion_threshold_shares<P>(
+    updated_shares: &BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<P, SimpleVerifier>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+    committee: &bridge::EvaluationPoints,
+    threshold: u16,
+    y_hex: &str,
+) -> anyhow::Result<
+    BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::ThresholdKeyShare<P, u16>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+>
+where
+    P: synedrion::SchemeParams,
+{
+    let mut threshold_shares = BTreeMap::new();
+    for (key, (share, aux)) in updated_shares {
+        let mut portable = bridge::from_synedrion_to_portable(share, y_hex.to_string())?;
+        portable.t = threshold; // 恢复阈值信息
+        portable.evaluation_points = Some(committee.clone());
+        let threshold_share = bridge::from_portable_to_synedrion_threshold::<P>(&portable)?;
+        threshold_shares.insert(*key, (threshold_share, aux.clone()));
+    }
+    Ok(threshold_shares)
+}
+
+/// 为指定签名者集合准备 Synedrion 签名输入 (ThresholdKeyShare -> KeyShare)
+///
+/// **功能**: 由 Synedrion 的 `ThresholdKeyShare::to_key_share` 针对签名者集合 S 完成 Lagrange 转换，
+/// 得到一个 |S|-of-|S| 的签名组，不需要经过 PortableKeyShare 重新导入。
+///
+/// **生产环境通信**: **不涉及**。只要所有签名者对集合 S 达成共识，各自在本地完成转换。
+pub fn threshold_signing_subset<P>(
+    threshold_shares: &BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::ThresholdKeyShare<P, u16>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+    signers: &[SimpleVerifier],
+) -> anyhow::Result<
+    BTreeMap<
+        SimpleVerifier,
+        (
+            synedrion::KeyShare<P, SimpleVerifier>,
+            synedrion::AuxInfo<P, SimpleVerifier>,
+        ),
+    >,
+>
+where
+    P: synedrion::SchemeParams,
+{
+    let signer_ids: BTreeSet<u16> = signers.iter().map(|k| k.0).collect();
+    if let Some(missing) = signers.iter().find(|k| !threshold_shares.contains_key(k)) {
+        return Err(anyhow!("Missing threshold share for party {}", missing.0));
+    }
+
+    let mut signing_subset = BTreeMap::new();
+    for key in signers {
+        let (threshold_share, aux) = &threshold_shares[key];
+        if (signer_ids.len() as u32) < threshold_share.threshold() as u32 {
+            return Err(anyhow!(
+                "签名者不足: 需要 {}, 实际 {}",
+                threshold_share.threshold(),
+                signer_ids.len()
+            ));
+        }
+        let key_share = threshold_share.to_key_share(&signer_ids);
+        let key_share =
+            bridge::convert_synedrion_key_share::<P, u16, SimpleVerifier>(&key_share)?;
+        signing_subset.insert(*key, (key_share, aux.clone()));
+    }
+    Ok(signing_subset)
+}
+
+/// 运行 Synedrion 原生的 KeyResharing 协议 (Old Holders -> New Holders)
+///
+/// **功能**: 把门限密钥从旧持有者 `old_shares` 转移到新持有者 `new_holders` (阈值 `new_threshold`)，
+/// 全局公钥不变。这是与 `bridge::rotate_committee` 相互独立的第二种实现，可用于交叉验证。
+///
+/// **参与方**: 旧持有者 ∪ 新持有者。旧持有者至少需要旧阈值个，既是旧持有者又是新持有者的参与方
+/// 同时扮演两个角色。新持有者只需知道全局公钥 `y_hex`、旧阈值与旧持有者集合。
+///
+/// **返回**: 新持有者的 `ThresholdKeyShare`，按 party id 索引；离开的旧持有者不会出现在结果中。
+pub async fn run_synedrion_resharing(
+    old_shares: &BTreeMap<u16, synedrion::ThresholdKeyShare<FastSecp256k1, u16>>,
+    y_hex: &str,
+    new_holders: BTreeSet<u16>,
+    new_threshold: u16,
+) -> anyhow::Result<BTreeMap<u16, synedrion::ThresholdKeyShare<FastSecp256k1, u16>>> {
+    use manul::dev::tokio::run_async;
+    use synedrion::{KeyResharing, NewHolder, OldHolder};
+
+    eprintln!("      (模拟) Synedrion KeyResharing Protocol Start...");
+
+    let old_threshold = old_shares
+        .values()
+        .next()
+        .context("No old holder shares")?
+        .threshold();
+    let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&hex::decode(
+        bridge::strip_0x(y_hex),
+    )?)
+    .map_err(|e| anyhow!("Invalid public key: {}", e))?;
+    let old_holders: BTreeSet<SimpleVerifier> =
+        old_shares.keys().map(|&id| SimpleVerifier(id)).collect();
+    let new_holders_set: BTreeSet<SimpleVerifier> =
+        new_holders.iter().map(|&id| SimpleVerifier(id)).collect();
+
+    let mut entry_points = Vec::new();
+    for &id in old_shares.keys().chain(new_holders.iter()).collect::<BTreeSet<_>>() {
+        let signer = SimpleSigner {
+            id,
+            verifier: SimpleVerifier(id),
+        };
+        let old_holder = match old_shares.get(&id) {
+            Some(share) => Some(OldHolder {
+                key_share: bridge::convert_synedrion_threshold_share::<_, u16, SimpleVerifier>(
+                    share,
+                )?,
+            }),
+            None => None,
+        };
+        let new_holder = new_holders.contains(&id).then(|| NewHolder {
+            verifying_key,
+            old_threshold,
+            old_holders: old_holders.clone(),
+        });
+        let entry_point = KeyResharing::<FastSecp256k1, SimpleVerifier>::new(
+            old_holder,
+            new_holder,
+            new_holders_set.clone(),
+            new_threshold as usize,
+        );
+        entry_points.push((signer, entry_point));
+    }
+
+    let execution_result = run_async::<_, SimpleSessionParams>(&mut OsRng, entry_points, true)
+        .await
+        .map_err(|e| anyhow!("KeyResharing simulation failed: {}", e))?;
+    let results = execution_result
+        .results()
+        .map_err(|e| anyhow!("KeyResharing protocol execution failed: {}", e))?;
+
+    let mut new_shares = BTreeMap::new();
+    for (verifier, share) in results {
+        if let Some(share) = share {
+            let share =
+                bridge::convert_synedrion_threshold_share::<_, SimpleVerifier, u16>(&share)?;
+            new_shares.insert(verifier.0, share);
+        }
+    }
+    if new_shares.keys().copied().collect::<BTreeSet<_>>() != new_holders {
+        return Err(anyhow!(
+            "KeyResharing 输出 {:?} 与新持有者 {:?} 不一致",
+            new_shares.keys().collect::<Vec<_>>(),
+            new_holders
+        ));
+    }
+    Ok(new_shares)
+}
+
+/// 将 Synedrion 门限分片写回 cggmp24 (ThresholdKeyShare -> portable -> cggmp24)
+///
+/// **功能**: `from_synedrion_threshold_to_portable` 导出私钥分片、阈值与 Synedrion 分配的求值点
+/// (`share_ids`)，再由 `build_cggmp_committee_shares` 构造 `IncompleteKeyShare` 并运行 cggmp24 AuxGen。
+///
+/// **返回**: 按 party id 升序排列，`core.i` 是在委员会中的位置。
+#[cfg(feature = "cggmp24")]
+pub async fn synedrion_threshold_shares_to_cggmp(
+    shares: &BTreeMap<u16, synedrion::ThresholdKeyShare<FastSecp256k1, u16>>,
+    y_hex: &str,
+    execution_id: cggmp24::ExecutionId<'_>,
+) -> anyhow::Result<
+    Vec<
+        cggmp24::KeyShare<
+            cggmp24::supported_curves::Secp256k1,
+            cggmp24::security_level::SecurityLevel128,
+        >,
+    >,
+> {
+    let portable_shares = shares
+        .values()
+        .map(|share| bridge::from_synedrion_threshold_to_portable(share, y_hex.to_string()))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    super::cggmp::build_cggmp_committee_shares(&portable_shares, execution_id).await
+}
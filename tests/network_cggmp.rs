@@ -0,0 +1,173 @@
+//! 5 个独立进程经本机 TCP 全连接完成 cggmp24 keygen (3-of-5)、aux_info_gen 与签名
+//!
+//! 测试进程分配端口后用 `current_exe()` 重新运行本测试 5 次，通过环境变量告诉子进程扮演哪个参与方；
+//! 子进程在 stdout 输出一行 `REPORT <json>`，测试进程汇总并校验。
+
+use anyhow::{anyhow, Context};
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::network::{self, NetworkNode};
+use my_mpc_bridge::selection::{select_signers, SignerPolicy};
+use my_mpc_bridge::simulation;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{SocketAddr, TcpListener};
+use std::process::Stdio;
+use std::time::Duration;
+
+const PARTIES: u16 = 5;
+const THRESHOLD: u16 = 3;
+const TEST_NAME: &str = "five_processes_keygen_aux_gen_and_sign";
+const PARTY_ENV: &str = "MPC_BRIDGE_TEST_PARTY";
+const PEERS_ENV: &str = "MPC_BRIDGE_TEST_PEERS";
+const SESSION_ENV: &str = "MPC_BRIDGE_TEST_SESSION";
+const REPORT_PREFIX: &str = "REPORT ";
+
+#[derive(Serialize, Deserialize, Debug)]
+struct PartyReport {
+    party: u16,
+    public_key: String,
+    signers: Vec<u16>,
+    signature: Option<(String, String, u8)>,
+}
+
+fn message_hash() -> [u8; 32] {
+    Sha256::digest(b"networked cggmp24 test").into()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn five_processes_keygen_aux_gen_and_sign() -> anyhow::Result<()> {
+    match std::env::var(PARTY_ENV) {
+        Ok(party) => run_party(party.parse()?).await,
+        Err(_) => run_coordinator().await,
+    }
+}
+
+async fn run_party(my_id: u16) -> anyhow::Result<()> {
+    let peers: BTreeMap<u16, SocketAddr> = std::env::var(PEERS_ENV)?
+        .split(',')
+        .enumerate()
+        .map(|(id, addr)| Ok((id as u16, addr.parse()?)))
+        .collect::<anyhow::Result<_>>()?;
+    let session = std::env::var(SESSION_ENV)?;
+
+    let node = NetworkNode::connect_mesh(my_id, &peers).await?;
+    let primes = simulation::load_or_generate_primes()?;
+    let share = network::run_cggmp_dkg_party(&node, &session, THRESHOLD, primes).await?;
+
+    let hash = message_hash();
+    let committee = node.parties().to_vec();
+    let available: BTreeSet<u16> = committee.iter().copied().collect();
+    let signers = select_signers(
+        &committee,
+        &available,
+        THRESHOLD,
+        &SignerPolicy::default(),
+        &hash,
+    )?;
+    let signature = if signers.contains(&my_id) {
+        let (r, s, v) = network::run_cggmp_signing_party(
+            &node,
+            &format!("{}/sign", session),
+            &share,
+            &signers,
+            hash,
+        )
+        .await?;
+        Some((hex::encode(r), hex::encode(s), v))
+    } else {
+        None
+    };
+    node.shutdown().await;
+
+    let report = PartyReport {
+        party: my_id,
+        public_key: hex::encode(share.shared_public_key.to_bytes(true)),
+        signers,
+        signature,
+    };
+    println!("{}{}", REPORT_PREFIX, serde_json::to_string(&report)?);
+    Ok(())
+}
+
+async fn run_coordinator() -> anyhow::Result<()> {
+    // 子进程共用素数缓存，避免 5 个进程同时生成
+    simulation::load_or_generate_primes()?;
+
+    let listeners = (0..PARTIES)
+        .map(|_| TcpListener::bind("127.0.0.1:0"))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let addrs = listeners
+        .iter()
+        .map(|l| l.local_addr().map(|a| a.to_string()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    drop(listeners);
+    let mut session_bytes = [0u8; 8];
+    OsRng.fill_bytes(&mut session_bytes);
+    let session = format!("network-test-{}", hex::encode(session_bytes));
+
+    let exe = std::env::current_exe()?;
+    let mut children = Vec::new();
+    for party in 0..PARTIES {
+        let child = tokio::process::Command::new(&exe)
+            .args([TEST_NAME, "--exact", "--nocapture", "--test-threads=1"])
+            .env(PARTY_ENV, party.to_string())
+            .env(PEERS_ENV, addrs.join(","))
+            .env(SESSION_ENV, &session)
+            .stdout(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn Party {}", party))?;
+        children.push(child);
+    }
+
+    let mut reports = Vec::new();
+    for (party, child) in children.into_iter().enumerate() {
+        let output = tokio::time::timeout(Duration::from_secs(900), child.wait_with_output())
+            .await
+            .map_err(|_| anyhow!("Party {} 超时", party))??;
+        let stdout = String::from_utf8(output.stdout)?;
+        assert!(
+            output.status.success(),
+            "Party {} 失败: {}\n{}",
+            party,
+            output.status,
+            stdout
+        );
+        let line = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(REPORT_PREFIX))
+            .with_context(|| format!("Party {} 没有输出结果:\n{}", party, stdout))?;
+        let report: PartyReport = serde_json::from_str(line)?;
+        assert_eq!(report.party, party as u16);
+        reports.push(report);
+    }
+
+    let public_key = &reports[0].public_key;
+    assert!(
+        reports.iter().all(|r| &r.public_key == public_key),
+        "公钥不一致"
+    );
+    let signers = &reports[0].signers;
+    assert_eq!(signers.len(), THRESHOLD as usize);
+    assert!(
+        reports.iter().all(|r| &r.signers == signers),
+        "签名者集合不一致"
+    );
+    for report in &reports {
+        assert_eq!(report.signature.is_some(), signers.contains(&report.party));
+    }
+    let signatures: Vec<_> = reports.iter().filter_map(|r| r.signature.clone()).collect();
+    assert!(signatures.iter().all(|s| s == &signatures[0]), "签名不一致");
+
+    let address = compute_eth_address_from_pubkey(&hex::decode(public_key)?);
+    let (r_hex, s_hex, v) = &signatures[0];
+    let r: [u8; 32] = hex::decode(r_hex)?.as_slice().try_into()?;
+    let s: [u8; 32] = hex::decode(s_hex)?.as_slice().try_into()?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash(), address).map_err(|e| anyhow!(e))?,
+        *v
+    );
+    Ok(())
+}
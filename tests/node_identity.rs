@@ -0,0 +1,139 @@
+//! `NodeSessionParams` 认证 manul 消息: 冒充、篡改、未登记密钥与跨会话重放的消息被 `Session` 拒绝
+
+use manul::dev::tokio::run_async;
+use manul::session::{Message, Session, SessionId};
+use my_mpc_bridge::identity::{NodeSessionParams, NodeSigner, NodeVerifier};
+use my_mpc_bridge::simulation::FastSecp256k1;
+use rand_core::OsRng;
+use std::collections::BTreeSet;
+use synedrion::signature::Keypair;
+use synedrion::KeyInit;
+
+type Params = FastSecp256k1;
+
+fn key_init(verifiers: &BTreeSet<NodeVerifier>) -> KeyInit<Params, NodeVerifier> {
+    KeyInit::new(verifiers.clone()).expect("valid KeyInit parameters")
+}
+
+fn new_session(
+    session_id: &[u8],
+    signer: &NodeSigner,
+    verifiers: &BTreeSet<NodeVerifier>,
+) -> Session<KeyInit<Params, NodeVerifier>, NodeSessionParams> {
+    Session::new(
+        &mut OsRng,
+        SessionId::from_seed::<NodeSessionParams>(session_id),
+        signer.clone(),
+        key_init(verifiers),
+    )
+    .expect("session created")
+}
+
+/// `sender` 发给 `recipient` 的第一轮消息
+fn first_message(
+    sender: &Session<KeyInit<Params, NodeVerifier>, NodeSessionParams>,
+    recipient: &NodeVerifier,
+) -> Message<NodeVerifier> {
+    sender
+        .make_message(&mut OsRng, recipient)
+        .expect("message created")
+        .0
+}
+
+/// 篡改消息中最长的字节字段 (字节数组或 hex/base64 字符串) 的第一个字节
+fn tamper(message: &Message<NodeVerifier>) -> Option<Message<NodeVerifier>> {
+    fn longest_field(value: &serde_json::Value, path: String, best: &mut Option<(usize, String)>) {
+        let len = match value {
+            serde_json::Value::String(text) => text.len(),
+            serde_json::Value::Array(items) if items.iter().all(|v| v.is_u64()) => items.len(),
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    longest_field(item, format!("{}/{}", path, i), best);
+                }
+                return;
+            }
+            serde_json::Value::Object(fields) => {
+                for (key, item) in fields {
+                    longest_field(item, format!("{}/{}", path, key), best);
+                }
+                return;
+            }
+            _ => return,
+        };
+        if len > best.as_ref().map_or(0, |(best_len, _)| *best_len) {
+            *best = Some((len, path));
+        }
+    }
+    let mut value = serde_json::to_value(message).ok()?;
+    let mut best = None;
+    longest_field(&value, String::new(), &mut best);
+    match value.pointer_mut(&best?.1)? {
+        serde_json::Value::String(text) => {
+            let first = if text.starts_with('0') { "1" } else { "0" };
+            text.replace_range(..1, first);
+        }
+        serde_json::Value::Array(items) => items[0] = (items[0].as_u64()? ^ 1).into(),
+        _ => return None,
+    }
+    serde_json::from_value(value).ok()
+}
+
+#[tokio::test]
+async fn key_init_runs_with_node_identities() -> anyhow::Result<()> {
+    let signers: Vec<NodeSigner> = (0..3).map(NodeSigner::generate).collect();
+    let verifiers: BTreeSet<NodeVerifier> = signers.iter().map(|s| s.verifying_key()).collect();
+    let entry_points = signers
+        .iter()
+        .map(|signer| (signer.clone(), key_init(&verifiers)))
+        .collect();
+    let results = run_async::<_, NodeSessionParams>(&mut OsRng, entry_points, true)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .results()
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(results.len(), 3);
+    Ok(())
+}
+
+#[test]
+fn forged_manul_messages_are_rejected() {
+    let signers: Vec<NodeSigner> = (0..3).map(NodeSigner::generate).collect();
+    let verifiers: BTreeSet<NodeVerifier> = signers.iter().map(|s| s.verifying_key()).collect();
+    let [v0, v1, v2] = [0, 1, 2].map(|i| signers[i].verifying_key());
+
+    let receiver = new_session(b"identity-test", &signers[0], &verifiers);
+    let sender = new_session(b"identity-test", &signers[1], &verifiers);
+    let mut accum = receiver.make_accumulator();
+    let mut accepts = |from: &NodeVerifier, message: Message<NodeVerifier>| {
+        receiver
+            .preprocess_message(&mut accum, from, message)
+            .expect("no local error")
+            .ok()
+            .is_some()
+    };
+
+    // Party 1 的消息冒充 Party 2
+    assert!(!accepts(&v2, first_message(&sender, &v0)));
+
+    // 未登记的密钥冒充 Party 2: 攻击者在自己的视图中把 Party 2 的公钥换成自己的
+    let attacker = NodeSigner::generate(2);
+    let attacker_view: BTreeSet<NodeVerifier> =
+        [v0.clone(), v1.clone(), attacker.verifying_key()].into();
+    let attacker_session = new_session(b"identity-test", &attacker, &attacker_view);
+    let forged = first_message(&attacker_session, &v0);
+    assert!(!accepts(&v2, forged));
+
+    // 其他会话中 Party 1 的合法消息被重放到本会话
+    let other_session = new_session(b"another-session", &signers[1], &verifiers);
+    assert!(!accepts(&v1, first_message(&other_session, &v0)));
+
+    // 篡改后的消息
+    let genuine = first_message(&sender, &v0);
+    let tampered = tamper(&genuine).expect("message has a byte field to tamper with");
+    assert!(!accepts(&v1, tampered));
+
+    // 合法消息被接受，重复投递被丢弃
+    let duplicate = serde_json::from_slice(&serde_json::to_vec(&genuine).unwrap()).unwrap();
+    assert!(accepts(&v1, genuine));
+    assert!(!accepts(&v1, duplicate));
+}
@@ -0,0 +1,186 @@
+//! 重共享刷新与 Synedrion 往返刷新: 分片与辅助信息都已更新，签名都恢复出同一个地址
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation::{self, FastSecp256k1, SimpleVerifier};
+use std::collections::BTreeSet;
+
+#[tokio::test]
+async fn reshare_and_synedrion_refresh_sign_for_same_address() -> anyhow::Result<()> {
+    let threshold = 3;
+    let cggmp_shares =
+        simulation::run_dkg(5, threshold, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let y_hex = hex::encode(&pubkey_bytes);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    let committee = bridge::EvaluationPoints::contiguous(5);
+
+    let reshare_shares = simulation::run_bridge_reshare_refresh(
+        &cggmp_shares,
+        &committee,
+        ExecutionId::new(b"test-reshare-refresh"),
+    )
+    .await?;
+
+    let party_ids: BTreeSet<u16> = cggmp_shares.iter().map(|s| s.core.i).collect();
+    let aux_map = simulation::run_synedrion_aux_gen::<FastSecp256k1>(party_ids).await?;
+    let synedrion_data =
+        simulation::cggmp_shares_to_synedrion(&cggmp_shares, &committee, &aux_map)?;
+    let cache_path = std::env::temp_dir().join(format!(
+        "mpc-bridge-refresh-comparison-{}.json",
+        std::process::id()
+    ));
+    let refreshed = simulation::run_refresh_workflow(
+        synedrion_data,
+        &committee,
+        cache_path.to_str().unwrap(),
+        true,
+    )
+    .await;
+    let _ = std::fs::remove_file(&cache_path);
+    let synedrion_shares = simulation::synedrion_shares_to_cggmp(
+        &cggmp_shares,
+        &refreshed?,
+        &y_hex,
+        threshold,
+        &committee,
+    )?;
+
+    for ((old, reshared), synedrion) in cggmp_shares
+        .iter()
+        .zip(&reshare_shares)
+        .zip(&synedrion_shares)
+    {
+        let old_x = bridge::from_cggmp_to_portable(old, Some(&committee))?.x_hex;
+        let reshared_x = bridge::from_cggmp_to_portable(reshared, Some(&committee))?.x_hex;
+        let synedrion_x = bridge::from_cggmp_to_portable(synedrion, Some(&committee))?.x_hex;
+        assert_eq!(
+            (reshared.core.i, synedrion.core.i),
+            (old.core.i, old.core.i)
+        );
+        assert_ne!(
+            reshared_x, old_x,
+            "重共享刷新未更新 Party {} 的分片",
+            old.core.i
+        );
+        assert_ne!(
+            serde_json::to_value(&reshared.aux)?,
+            serde_json::to_value(&old.aux)?,
+            "重共享刷新未更新 Party {} 的辅助信息",
+            old.core.i
+        );
+        assert_ne!(
+            synedrion_x, old_x,
+            "Synedrion 刷新未更新 Party {} 的分片",
+            old.core.i
+        );
+        assert_ne!(reshared_x, synedrion_x);
+        for share in [reshared, synedrion] {
+            assert_eq!(
+                share.shared_public_key.to_bytes(true).as_ref(),
+                pubkey_bytes.as_ref()
+            );
+            assert_eq!(
+                bridge::from_cggmp_to_portable(share, Some(&committee))?.t,
+                threshold
+            );
+        }
+    }
+
+    let message_hash = [0x13u8; 32];
+    for shares in [&reshare_shares, &synedrion_shares] {
+        let signers: Vec<_> = [0usize, 2, 4].iter().map(|&k| shares[k].clone()).collect();
+        let (r, s, v) = simulation::run_cggmp_signing(&signers, message_hash).await?;
+        assert_eq!(
+            calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+            v
+        );
+    }
+
+    Ok(())
+}
+
+/// t < n: Synedrion KeyRefresh 的加法增量经重共享换算到 Shamir 域后，
+/// 每个参与方只用自己的 KeyShareChange 与收到的 dealing 更新自己的 cggmp24 分片
+#[tokio::test]
+async fn per_party_update_from_refresh_change_keeps_threshold() -> anyhow::Result<()> {
+    let threshold = 3;
+    let cggmp_shares =
+        simulation::run_dkg(5, threshold, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let y_hex = hex::encode(&pubkey_bytes);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    let committee = bridge::EvaluationPoints::contiguous(5);
+
+    let party_ids: BTreeSet<u16> = committee.ids().into_iter().collect();
+    let aux_map = simulation::run_synedrion_aux_gen::<FastSecp256k1>(party_ids).await?;
+    let synedrion_data =
+        simulation::cggmp_shares_to_synedrion(&cggmp_shares, &committee, &aux_map)?;
+    let changes = simulation::run_synedrion_refresh_simulation(
+        synedrion_data
+            .iter()
+            .map(|(share, _)| share.clone())
+            .collect(),
+        &committee,
+    )
+    .await?;
+
+    // 每个参与方应用自己的 KeyShareChange，并分发自己的加法增量
+    let mut dealings = Vec::new();
+    for ((share, _), template) in synedrion_data.iter().zip(&cggmp_shares) {
+        let (change, _) = &changes[&SimpleVerifier(*share.owner())];
+        let refreshed =
+            bridge::convert_synedrion_key_share::<FastSecp256k1, u16, SimpleVerifier>(share)?
+                .update(change.clone())
+                .map_err(|e| anyhow::anyhow!("Failed to apply KeyShareChange: {:?}", e))?;
+        let refreshed = bridge::from_synedrion_to_portable(&refreshed, y_hex.clone())?;
+        let old = bridge::from_cggmp_to_portable(template, Some(&committee))?;
+        dealings.push(bridge::deal_refresh_delta(
+            &old,
+            &refreshed.x_hex,
+            &committee,
+        )?);
+    }
+
+    let mut updated_shares = Vec::new();
+    for template in &cggmp_shares {
+        // DKG 委员会中位置即 party id
+        let (change, _) = &changes[&SimpleVerifier(template.core.i)];
+        updated_shares.push(bridge::update_cggmp_share_from_refresh_change(
+            template,
+            change,
+            &dealings,
+            &committee,
+            SimpleVerifier,
+        )?);
+    }
+
+    for (old, new) in cggmp_shares.iter().zip(&updated_shares) {
+        let old_portable = bridge::from_cggmp_to_portable(old, Some(&committee))?;
+        let new_portable = bridge::from_cggmp_to_portable(new, Some(&committee))?;
+        assert_ne!(
+            new_portable.x_hex, old_portable.x_hex,
+            "Party {} 的分片未更新",
+            old.core.i
+        );
+        assert_eq!(new_portable.t, threshold);
+        assert_eq!(
+            new.shared_public_key.to_bytes(true).as_ref(),
+            pubkey_bytes.as_ref()
+        );
+    }
+
+    let message_hash = [0x24u8; 32];
+    let signers: Vec<_> = [1usize, 3, 4]
+        .iter()
+        .map(|&k| updated_shares[k].clone())
+        .collect();
+    let (r, s, v) = simulation::run_cggmp_signing(&signers, message_hash).await?;
+    assert_eq!(
+        calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+        v
+    );
+
+    Ok(())
+}
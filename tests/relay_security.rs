@@ -0,0 +1,223 @@
+//! 不可信的中继: 握手伪造被拒绝，篡改或重放的 P2P 消息与广播被接收方发现；
+//! 接收方队列已满时发送方被断开，已排队的消息不会被丢弃
+
+use k256::ecdsa::{SigningKey, VerifyingKey};
+use my_mpc_bridge::network::{
+    read_frame, write_frame, Envelope, NetworkNode, Relay, RelayChallenge, RelayHello, Routed,
+    SealedPayload, SessionLink, MAX_QUEUED_PER_PARTY,
+};
+use rand_core::OsRng;
+use std::collections::BTreeMap;
+use std::io;
+use std::time::Duration;
+use tokio::net::TcpStream;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+fn identities(parties: u16) -> (Vec<SigningKey>, BTreeMap<u16, VerifyingKey>) {
+    let keys: Vec<SigningKey> = (0..parties)
+        .map(|_| SigningKey::random(&mut OsRng))
+        .collect();
+    let registry = keys
+        .iter()
+        .enumerate()
+        .map(|(id, key)| (id as u16, *key.verifying_key()))
+        .collect();
+    (keys, registry)
+}
+
+/// 用原始 TCP 连接完成握手，返回中继是否保持连接并转发了排队的消息
+async fn handshake(relay: &Relay, reply: impl FnOnce(&RelayChallenge) -> RelayHello) -> bool {
+    let mut stream = TcpStream::connect(relay.local_addr()).await.unwrap();
+    let challenge: RelayChallenge = read_frame(&mut stream).await.unwrap().unwrap();
+    write_frame(&mut stream, &reply(&challenge)).await.unwrap();
+    matches!(
+        tokio::time::timeout(TIMEOUT, read_frame::<_, Envelope>(&mut stream)).await,
+        Ok(Ok(Some(_)))
+    )
+}
+
+/// 用原始 TCP 连接以 `party` 的身份完成握手
+async fn connect_raw(relay: &Relay, party: u16, key: &SigningKey) -> TcpStream {
+    let mut stream = TcpStream::connect(relay.local_addr()).await.unwrap();
+    let challenge: RelayChallenge = read_frame(&mut stream).await.unwrap().unwrap();
+    write_frame(&mut stream, &RelayHello::sign(party, &challenge, key))
+        .await
+        .unwrap();
+    stream
+}
+
+async fn encrypted_nodes(
+    relay: &Relay,
+    keys: &[SigningKey],
+    registry: &BTreeMap<u16, VerifyingKey>,
+) -> Vec<NetworkNode> {
+    let committee: Vec<u16> = registry.keys().copied().collect();
+    let connect = keys.iter().enumerate().map(|(id, key)| {
+        let committee = &committee;
+        async move {
+            let mut node =
+                NetworkNode::connect_relay(id as u16, committee, relay.local_addr(), key).await?;
+            node.enable_encryption(key, registry).await?;
+            Ok::<_, anyhow::Error>(node)
+        }
+    });
+    futures_util::future::try_join_all(connect).await.unwrap()
+}
+
+async fn recv(receiver: &mut SessionLink) -> io::Result<Envelope> {
+    tokio::time::timeout(TIMEOUT, receiver.recv())
+        .await
+        .expect("message delivered")
+        .expect("connection open")
+}
+
+#[tokio::test]
+async fn relay_rejects_forged_hello() {
+    let (keys, registry) = identities(2);
+    let relay = Relay::bind("127.0.0.1:0".parse().unwrap(), registry)
+        .await
+        .unwrap();
+
+    // 先为 Party 0 排一条消息，握手成功的连接会立即收到它
+    let sender = NetworkNode::connect_relay(1, &[0, 1], relay.local_addr(), &keys[1])
+        .await
+        .unwrap();
+    let link = sender.session("queued", &[0, 1]).unwrap();
+    link.send(Some(0), false, b"for party 0".to_vec()).unwrap();
+
+    // 未登记的密钥冒充 Party 0
+    let attacker = SigningKey::random(&mut OsRng);
+    assert!(!handshake(&relay, |c| RelayHello::sign(0, c, &attacker)).await);
+    // Party 1 的密钥冒充 Party 0
+    assert!(!handshake(&relay, |c| RelayHello::sign(0, c, &keys[1])).await);
+    // 登记表之外的 party id
+    assert!(!handshake(&relay, |c| RelayHello::sign(7, c, &attacker)).await);
+    // 重放对另一个挑战的签名
+    let stale = RelayChallenge {
+        nonce: vec![0x42; 32],
+    };
+    assert!(!handshake(&relay, |_| RelayHello::sign(0, &stale, &keys[0])).await);
+    assert!(!relay.connected_parties().contains(&0));
+
+    // 排队的消息没有交给冒充者，真正的 Party 0 仍能收到
+    assert!(handshake(&relay, |c| RelayHello::sign(0, c, &keys[0])).await);
+    drop(link);
+    sender.shutdown().await;
+}
+
+#[tokio::test]
+async fn encryption_must_be_enabled_before_sessions() {
+    let (keys, registry) = identities(2);
+    let relay = Relay::bind("127.0.0.1:0".parse().unwrap(), registry.clone())
+        .await
+        .unwrap();
+    let mut node = NetworkNode::connect_relay(0, &[0, 1], relay.local_addr(), &keys[0])
+        .await
+        .unwrap();
+    let _early = node.session("opened-too-early", &[0, 1]).unwrap();
+    assert!(node.enable_encryption(&keys[0], &registry).await.is_err());
+}
+
+#[tokio::test]
+async fn tampered_and_replayed_messages_are_rejected() {
+    let (keys, registry) = identities(2);
+    let relay = Relay::bind("127.0.0.1:0".parse().unwrap(), registry.clone())
+        .await
+        .unwrap();
+    let mut first_payloads: BTreeMap<(String, bool), Vec<u8>> = BTreeMap::new();
+    relay.intercept(move |envelope| match envelope.session.as_str() {
+        "tamper-p2p" if !envelope.is_broadcast() => {
+            let mut sealed: SealedPayload = serde_json::from_slice(&envelope.payload).unwrap();
+            sealed.ciphertext[0] ^= 0x01;
+            envelope.payload = serde_json::to_vec(&sealed).unwrap();
+        }
+        "tamper-broadcast" if envelope.is_broadcast() => {
+            if let Some(last) = envelope.payload.last_mut() {
+                *last ^= 0x01;
+            }
+        }
+        "replay" => {
+            // 第一条之后的同类消息都换成第一条的 payload
+            let first = first_payloads
+                .entry((envelope.session.clone(), envelope.is_broadcast()))
+                .or_insert_with(|| envelope.payload.clone());
+            envelope.payload = first.clone();
+        }
+        _ => {}
+    });
+    let nodes = encrypted_nodes(&relay, &keys, &registry).await;
+
+    for (session, broadcast) in [("tamper-p2p", false), ("tamper-broadcast", true)] {
+        let sender = nodes[0].session(session, &[0, 1]).unwrap();
+        let mut receiver = nodes[1].session(session, &[0, 1]).unwrap();
+        let recipient = (!broadcast).then_some(1);
+        sender.send(recipient, false, b"payload".to_vec()).unwrap();
+        let error = recv(&mut receiver).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{}", session);
+    }
+
+    let sender = nodes[0].session("replay", &[0, 1]).unwrap();
+    let mut receiver = nodes[1].session("replay", &[0, 1]).unwrap();
+    for recipient in [Some(1), None] {
+        sender.send(recipient, false, b"first".to_vec()).unwrap();
+        sender.send(recipient, false, b"second".to_vec()).unwrap();
+        assert_eq!(recv(&mut receiver).await.unwrap().payload, b"first");
+        let error = recv(&mut receiver).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData, "{:?}", recipient);
+    }
+
+    // 未经篡改的会话不受影响
+    let sender = nodes[0].session("clean", &[0, 1]).unwrap();
+    let mut receiver = nodes[1].session("clean", &[0, 1]).unwrap();
+    sender.send(Some(1), false, b"hello".to_vec()).unwrap();
+    assert_eq!(recv(&mut receiver).await.unwrap().payload, b"hello");
+}
+
+#[tokio::test]
+async fn full_queue_disconnects_sender_and_keeps_queued_messages() {
+    let (keys, registry) = identities(2);
+    let relay = Relay::bind("127.0.0.1:0".parse().unwrap(), registry)
+        .await
+        .unwrap();
+
+    // Party 0 未连接，Party 1 发给它的消息全部排队；超出上限的那条使 Party 1 被断开
+    let mut sender = connect_raw(&relay, 1, &keys[1]).await;
+    for index in 0..=MAX_QUEUED_PER_PARTY {
+        let routed = Routed {
+            to: vec![0],
+            envelope: Envelope {
+                session: "flood".to_string(),
+                sender: 1,
+                recipient: Some(0),
+                reliable: false,
+                payload: (index as u32).to_be_bytes().to_vec(),
+            },
+        };
+        if write_frame(&mut sender, &routed).await.is_err() {
+            break;
+        }
+    }
+    let closed = tokio::time::timeout(TIMEOUT, read_frame::<_, Envelope>(&mut sender))
+        .await
+        .expect("relay closes the sender");
+    assert!(matches!(closed, Ok(None) | Err(_)));
+    assert!(!relay.connected_parties().contains(&1));
+
+    // 已排队的消息按顺序全部交付，被拒绝的那条不在其中
+    let mut receiver = connect_raw(&relay, 0, &keys[0]).await;
+    for index in 0..MAX_QUEUED_PER_PARTY {
+        let envelope: Envelope = tokio::time::timeout(TIMEOUT, read_frame(&mut receiver))
+            .await
+            .expect("queued message delivered")
+            .unwrap()
+            .unwrap();
+        assert_eq!(envelope.payload, (index as u32).to_be_bytes());
+    }
+    assert!(tokio::time::timeout(
+        Duration::from_millis(200),
+        read_frame::<_, Envelope>(&mut receiver)
+    )
+    .await
+    .is_err());
+}
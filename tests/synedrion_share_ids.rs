@@ -0,0 +1,70 @@
+//! Synedrion 分配的 share id 经 `from_synedrion_threshold_to_portable` 导出并写回 cggmp24 后保持不变，
+//! 超出 u16 的 id 被拒绝而不是截断
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge;
+use my_mpc_bridge::simulation::{self, FastSecp256k1};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[tokio::test]
+async fn synedrion_share_ids_round_trip_into_cggmp() -> anyhow::Result<()> {
+    let dealers = [0u16, 2, 3];
+    let new_holders: BTreeSet<u16> = [0u16, 2, 3, 7, 9].into_iter().collect();
+
+    let cggmp_shares = simulation::run_dkg(5, 3, ExecutionId::new(b"test-share-ids-dkg")).await?;
+    let y_hex = hex::encode(cggmp_shares[0].shared_public_key.to_bytes(true));
+    let old_committee = bridge::EvaluationPoints::contiguous(5);
+    let mut old_shares = BTreeMap::new();
+    for share in cggmp_shares.iter().filter(|s| dealers.contains(&s.core.i)) {
+        let portable = bridge::from_cggmp_to_portable(share, Some(&old_committee))?;
+        old_shares.insert(
+            share.core.i,
+            bridge::from_portable_to_synedrion_threshold(&portable)?,
+        );
+    }
+    let new_shares =
+        simulation::run_synedrion_resharing(&old_shares, &y_hex, new_holders.clone(), 3).await?;
+
+    let mut portable_shares = BTreeMap::new();
+    for (id, share) in &new_shares {
+        let portable = bridge::from_synedrion_threshold_to_portable(share, y_hex.clone())?;
+        assert_eq!(portable.i, *id);
+        assert_eq!(portable.t, 3);
+        portable_shares.insert(*id, portable);
+    }
+    let committee = portable_shares[&0].committee_points()?;
+    assert_eq!(
+        committee.ids().into_iter().collect::<BTreeSet<_>>(),
+        new_holders
+    );
+    for portable in portable_shares.values() {
+        assert_eq!(portable.committee_points()?, committee);
+    }
+
+    let new_cggmp_shares = simulation::synedrion_threshold_shares_to_cggmp(
+        &new_shares,
+        &y_hex,
+        ExecutionId::new(b"test-share-ids-aux"),
+    )
+    .await?;
+    // core.i 是委员会中的位置，按 party id 升序对应 new_holders
+    for (share, id) in new_cggmp_shares.iter().zip(&new_holders) {
+        let back = bridge::from_cggmp_to_portable(share, Some(&committee))?;
+        let original = &portable_shares[id];
+        assert_eq!(back.i, *id);
+        assert_eq!(back.t, original.t);
+        assert_eq!(back.x_hex, original.x_hex);
+        assert_eq!(back.committee_points()?, committee);
+    }
+
+    // 以更宽的 Id 类型承载超出 u16 的 owner，导出时报错而不是截断成 70000 % 65536
+    let mut value = serde_json::to_value(&new_shares[&7])?;
+    value["owner"] = serde_json::json!(70_000u32);
+    let wide: synedrion::ThresholdKeyShare<FastSecp256k1, u32> = serde_json::from_value(value)?;
+    let err = bridge::from_synedrion_threshold_to_portable(&wide, y_hex).unwrap_err();
+    assert!(
+        err.to_string().contains("70000"),
+        "unexpected error: {err:#}"
+    );
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! 门限变更 (t -> t') 后共享公钥与以太坊地址不变
+
+use cggmp24::ExecutionId;
+use my_mpc_bridge::bridge::resharing::VssScheme;
+use my_mpc_bridge::bridge::{self, PortableKeyShare};
+use my_mpc_bridge::eth_utils::{calc_recovery_id, compute_eth_address_from_pubkey};
+use my_mpc_bridge::simulation;
+
+#[tokio::test]
+async fn address_unchanged_after_threshold_change() -> anyhow::Result<()> {
+    let cggmp_shares =
+        simulation::run_dkg(5, 3, ExecutionId::new(b"demo-mixed-mpc-session")).await?;
+    let pubkey_bytes = cggmp_shares[0].shared_public_key.to_bytes(true);
+    let address = compute_eth_address_from_pubkey(&pubkey_bytes);
+    let committee = bridge::EvaluationPoints::contiguous(5);
+    let portable_shares = cggmp_shares
+        .iter()
+        .map(|share| bridge::from_cggmp_to_portable(share, Some(&committee)))
+        .collect::<anyhow::Result<Vec<PortableKeyShare>>>()?;
+
+    for (scheme, new_threshold) in [(VssScheme::Feldman, 4), (VssScheme::Pedersen, 2)] {
+        let reshared =
+            bridge::reshare_with_new_threshold(portable_shares.clone(), new_threshold, scheme)?;
+        let new_shares = cggmp_shares
+            .iter()
+            .zip(&reshared)
+            .map(|(template, share)| bridge::from_portable_to_cggmp(template, share, None, None))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for (old, new) in portable_shares.iter().zip(&new_shares) {
+            let new_pubkey_bytes = new.shared_public_key.to_bytes(true);
+            assert_eq!(compute_eth_address_from_pubkey(&new_pubkey_bytes), address);
+            let portable = bridge::from_cggmp_to_portable(new, Some(&committee))?;
+            assert_eq!(portable.t, new_threshold);
+            assert_ne!(portable.x_hex, old.x_hex, "{:?}: 私钥分片未更新", scheme);
+        }
+
+        // 签名者不是委员会的前缀 (跳过 Party 0)
+        let message_hash = [new_threshold as u8; 32];
+        let signers = &new_shares[5 - new_threshold as usize..];
+        let (r, s, v) = simulation::run_cggmp_signing(signers, message_hash).await?;
+        assert_eq!(
+            calc_recovery_id(&r, &s, &message_hash, address).map_err(|e| anyhow::anyhow!(e))?,
+            v,
+            "{:?} {}-of-5 的签名恢复出了其他地址",
+            scheme,
+            new_threshold
+        );
+
+        // 少于新阈值的签名者无法签名
+        let too_few = &new_shares[..new_threshold as usize - 1];
+        assert!(simulation::run_cggmp_signing(too_few, message_hash)
+            .await
+            .is_err());
+    }
+    Ok(())
+}